@@ -0,0 +1,285 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `#[derive(BnType)]`, re-exported as `binaryninja::types::BnType` by the `derive` feature of
+//! the `binaryninja` crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericParam, Ident, Lit,
+    Meta, NestedMeta, Result,
+};
+
+#[proc_macro_derive(BnType)]
+pub fn derive_bn_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[derive(Default)]
+struct Repr {
+    c: bool,
+    packed: bool,
+    align: Option<u64>,
+    int: Option<Ident>,
+}
+
+fn parse_repr(input: &DeriveInput) -> Result<Repr> {
+    let mut repr = Repr::default();
+
+    for attr in input.attrs.iter().filter(|a| a.path.is_ident("repr")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(Error::new(meta.span(), "malformed repr attribute")),
+        };
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    let ident = path
+                        .get_ident()
+                        .ok_or_else(|| Error::new(path.span(), "unsupported repr"))?;
+
+                    match ident.to_string().as_str() {
+                        "C" => repr.c = true,
+                        "packed" => repr.packed = true,
+                        "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64"
+                        | "isize" => repr.int = Some(ident.clone()),
+                        _ => return Err(Error::new(ident.span(), "unsupported repr")),
+                    }
+                }
+                NestedMeta::Meta(Meta::List(list)) => {
+                    let value = match list.nested.first() {
+                        Some(NestedMeta::Lit(Lit::Int(i))) if list.nested.len() == 1 => {
+                            i.base10_parse::<u64>()?
+                        }
+                        _ => return Err(Error::new(list.span(), "malformed repr attribute")),
+                    };
+
+                    if list.path.is_ident("packed") {
+                        if value != 1 {
+                            return Err(Error::new(list.span(), "BnType only supports packed(1)"));
+                        }
+                        repr.packed = true;
+                    } else if list.path.is_ident("align") {
+                        repr.align = Some(value);
+                    } else {
+                        return Err(Error::new(list.span(), "unsupported repr"));
+                    }
+                }
+                nested => return Err(Error::new(nested.span(), "unsupported repr")),
+            }
+        }
+    }
+
+    Ok(repr)
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2> {
+    let repr = parse_repr(&input)?;
+
+    // every type parameter has to map to a type as well
+    for param in input.generics.params.iter_mut() {
+        if let GenericParam::Type(param) = param {
+            param
+                .bounds
+                .push(parse_quote!(::binaryninja::types::BnType));
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            if !repr.c {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "BnType can only be derived for #[repr(C)] structs",
+                ));
+            }
+
+            expand_struct(&repr, &data.fields, false)
+        }
+        Data::Union(data) => {
+            if !repr.c {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "BnType can only be derived for #[repr(C)] unions",
+                ));
+            }
+
+            expand_struct(&repr, &Fields::Named(data.fields.clone()), true)
+        }
+        Data::Enum(data) => {
+            if let Some(variant) = data
+                .variants
+                .iter()
+                .find(|v| !matches!(v.fields, Fields::Unit))
+            {
+                return Err(Error::new(
+                    variant.span(),
+                    "BnType can only be derived for enums without fields",
+                ));
+            }
+
+            let variants = data.variants.iter().map(|v| &v.ident);
+            let variant_names = data.variants.iter().map(|v| v.ident.to_string());
+
+            let (width, signed) = match repr.int.as_ref().map(|i| i.to_string()) {
+                Some(int) => {
+                    let signed = int.starts_with('i');
+                    let width = match &int[1..] {
+                        "size" => quote!(arch.address_size()),
+                        bits => {
+                            let bytes = bits.parse::<usize>().unwrap() / 8;
+                            quote!(#bytes)
+                        }
+                    };
+
+                    (width, signed)
+                }
+                // C enums are ints
+                None if repr.c => (quote!(4), true),
+                None => return Err(Error::new(
+                    input.ident.span(),
+                    "BnType can only be derived for enums with #[repr(C)] or #[repr(<integer>)]",
+                )),
+            };
+
+            quote! {
+                let mut builder = ::binaryninja::types::EnumerationBuilder::new();
+                #(
+                    builder.insert(#variant_names, Self::#variants as i64 as u64);
+                )*
+
+                ::binaryninja::types::Type::enumeration(&builder.finalize(), #width, #signed.into())
+            }
+        }
+    };
+
+    let pointee = match &input.data {
+        Data::Enum(_) => quote!(),
+        data => {
+            let class = match data {
+                Data::Union(_) => quote!(UnionNamedTypeClass),
+                _ => quote!(StructNamedTypeClass),
+            };
+            let type_name = name.to_string();
+
+            quote! {
+                fn bn_pointee_type<A: ::binaryninja::architecture::Architecture>(
+                    _arch: &A,
+                ) -> ::binaryninja::rc::Ref<::binaryninja::types::Type> {
+                    ::binaryninja::types::Type::named_type(
+                        &::binaryninja::types::NamedTypeReference::new(
+                            ::binaryninja::types::NamedTypeReferenceClass::#class,
+                            "",
+                            #type_name.into(),
+                        ),
+                    )
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::binaryninja::types::BnType for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn bn_type<A: ::binaryninja::architecture::Architecture>(
+                arch: &A,
+            ) -> ::binaryninja::rc::Ref<::binaryninja::types::Type> {
+                #[allow(unused_imports)]
+                use ::binaryninja::architecture::Architecture;
+
+                #body
+            }
+
+            #pointee
+        }
+    })
+}
+
+fn expand_struct(repr: &Repr, fields: &Fields, union: bool) -> TokenStream2 {
+    let field_types = fields.iter().map(|f| &f.ty);
+    let field_names = fields.iter().enumerate().map(|(i, f)| match &f.ident {
+        Some(ident) => ident.to_string(),
+        None => format!("field_{}", i),
+    });
+
+    let packed = repr.packed;
+    let structure_type = if union {
+        quote!(UnionStructureType)
+    } else {
+        quote!(StructStructureType)
+    };
+
+    let set_alignment = repr.align.map(|align| {
+        let align = align as usize;
+        quote!(builder.set_alignment(#align);)
+    });
+
+    // structure members are laid out one after another, union members all start at 0
+    let add_member = if union {
+        quote! {
+            builder.insert(
+                &*member,
+                name,
+                0,
+                false,
+                ::binaryninja::types::MemberAccess::NoAccess,
+                ::binaryninja::types::MemberScope::NoScope,
+            );
+        }
+    } else {
+        quote! {
+            builder.append(
+                &*member,
+                name,
+                ::binaryninja::types::MemberAccess::NoAccess,
+                ::binaryninja::types::MemberScope::NoScope,
+            );
+        }
+    };
+
+    quote! {
+        let mut builder = ::binaryninja::types::StructureBuilder::with_options(
+            ::binaryninja::types::StructureType::#structure_type,
+            #packed,
+        );
+        #set_alignment
+
+        #(
+            let member = <#field_types as ::binaryninja::types::BnType>::bn_type(arch);
+            let name = #field_names;
+            #add_member
+        )*
+
+        // pad to a multiple of the alignment, like a C compiler would, so arrays of this type
+        // have the right stride
+        let alignment = builder.alignment() as u64;
+        if !#packed && alignment > 1 && builder.width() % alignment != 0 {
+            let width = builder.width() + alignment - builder.width() % alignment;
+            builder.set_width(width);
+        }
+
+        ::binaryninja::types::Type::structure(&builder.finalize())
+    }
+}
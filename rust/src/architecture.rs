@@ -20,14 +20,18 @@ use std::borrow::{Borrow, Cow};
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::hash::Hash;
+use std::marker::PhantomData;
 use std::mem::zeroed;
 use std::ops;
 use std::ops::Drop;
+use std::os::raw::c_char;
 use std::ptr;
 use std::slice;
 
 use crate::callingconvention::CallingConvention;
+use crate::databuffer::DataBuffer;
 use crate::platform::Platform;
+use crate::types::{Conf, NameAndType, Type};
 use crate::{BranchType, Endianness};
 
 use crate::llil::{get_default_flag_cond_llil, get_default_flag_write_llil};
@@ -384,17 +388,81 @@ pub trait FlagGroup: Sized + Clone + Copy {
     fn flag_conditions(&self) -> HashMap<Self::FlagClass, FlagCondition>;
 }
 
+pub trait RegisterStackInfo: Sized {
+    type RegStackType: RegisterStack<InfoType = Self>;
+    type RegType: Register<InfoType = Self::RegInfoType>;
+    type RegInfoType: RegisterInfo<RegType = Self::RegType>;
+
+    /// Returns the first register backing the storage of this stack, as well
+    /// as the number of storage registers. Storage registers *MUST* have
+    /// consecutive ids starting at the returned register.
+    fn storage_regs(&self) -> (Self::RegType, u32);
+
+    /// Returns the first register used to access the stack relative to its
+    /// current top, as well as the number of such registers, if the stack
+    /// can be accessed that way. These registers *MUST* have consecutive ids.
+    fn top_relative_regs(&self) -> Option<(Self::RegType, u32)>;
+
+    /// The register holding the index of the current top of the stack.
+    fn stack_top_reg(&self) -> Self::RegType;
+}
+
+pub trait RegisterStack: Sized + Clone + Copy {
+    type InfoType: RegisterStackInfo<
+        RegType = Self::RegType,
+        RegInfoType = Self::RegInfoType,
+        RegStackType = Self,
+    >;
+    type RegType: Register<InfoType = Self::RegInfoType>;
+    type RegInfoType: RegisterInfo<RegType = Self::RegType>;
+
+    fn name(&self) -> Cow<str>;
+    fn info(&self) -> Self::InfoType;
+
+    /// Unique identifier for this `RegisterStack`.
+    ///
+    /// *MUST* be in the range [0, 0x7fff_ffff]
+    fn id(&self) -> u32;
+}
+
+pub trait Intrinsic: Sized + Clone + Copy {
+    fn name(&self) -> Cow<str>;
+
+    /// Unique identifier for this `Intrinsic`.
+    ///
+    /// *MUST* be in the range [0, 0x7fff_ffff]
+    fn id(&self) -> u32;
+
+    /// Returns the list of the input names and types for this intrinsic.
+    fn inputs(&self) -> Vec<NameAndType<String>>;
+
+    /// Returns the list of the output types for this intrinsic.
+    fn outputs(&self) -> Vec<Conf<Ref<Type>>>;
+}
+
 pub trait Architecture: 'static + Sized + AsRef<CoreArchitecture> {
     type Handle: Borrow<Self> + Clone;
 
     type RegisterInfo: RegisterInfo<RegType = Self::Register>;
     type Register: Register<InfoType = Self::RegisterInfo>;
+    type RegisterStackInfo: RegisterStackInfo<
+        RegType = Self::Register,
+        RegInfoType = Self::RegisterInfo,
+        RegStackType = Self::RegisterStack,
+    >;
+    type RegisterStack: RegisterStack<
+        InfoType = Self::RegisterStackInfo,
+        RegType = Self::Register,
+        RegInfoType = Self::RegisterInfo,
+    >;
 
     type Flag: Flag<FlagClass = Self::FlagClass>;
     type FlagWrite: FlagWrite<FlagType = Self::Flag, FlagClass = Self::FlagClass>;
     type FlagClass: FlagClass;
     type FlagGroup: FlagGroup<FlagType = Self::Flag, FlagClass = Self::FlagClass>;
 
+    type Intrinsic: Intrinsic;
+
     type InstructionTextContainer: Into<Vec<InstructionTextToken>>;
 
     fn endianness(&self) -> Endianness;
@@ -490,6 +558,13 @@ pub trait Architecture: 'static + Sized + AsRef<CoreArchitecture> {
     fn registers_global(&self) -> Vec<Self::Register>;
     fn registers_system(&self) -> Vec<Self::Register>;
 
+    /// Architectures that don't make use of register stacks can rely on the
+    /// default implementation and use `UnusedRegisterStack` for the
+    /// `RegisterStack` associated type.
+    fn register_stacks(&self) -> Vec<Self::RegisterStack> {
+        Vec::new()
+    }
+
     fn flags(&self) -> Vec<Self::Flag>;
     fn flag_write_types(&self) -> Vec<Self::FlagWrite>;
     fn flag_classes(&self) -> Vec<Self::FlagClass>;
@@ -499,11 +574,79 @@ pub trait Architecture: 'static + Sized + AsRef<CoreArchitecture> {
     fn link_reg(&self) -> Option<Self::Register>;
 
     fn register_from_id(&self, id: u32) -> Option<Self::Register>;
+    fn register_stack_from_id(&self, _id: u32) -> Option<Self::RegisterStack> {
+        None
+    }
     fn flag_from_id(&self, id: u32) -> Option<Self::Flag>;
     fn flag_write_from_id(&self, id: u32) -> Option<Self::FlagWrite>;
     fn flag_class_from_id(&self, id: u32) -> Option<Self::FlagClass>;
     fn flag_group_from_id(&self, id: u32) -> Option<Self::FlagGroup>;
 
+    /// Architectures that don't make use of intrinsics can rely on the
+    /// default implementation and use `UnusedIntrinsic` for the
+    /// `Intrinsic` associated type.
+    fn intrinsics(&self) -> Vec<Self::Intrinsic> {
+        Vec::new()
+    }
+    fn intrinsic_from_id(&self, _id: u32) -> Option<Self::Intrinsic> {
+        None
+    }
+
+    fn can_assemble(&self) -> bool {
+        false
+    }
+
+    /// Assembles `code` as if it were located at `addr`, returning the encoded bytes or a
+    /// description of the errors encountered.
+    fn assemble(&self, _code: &str, _addr: u64) -> Result<Vec<u8>, String> {
+        Err("Assemble unsupported".into())
+    }
+
+    fn is_never_branch_patch_available(&self, _data: &[u8], _addr: u64) -> bool {
+        false
+    }
+    fn is_always_branch_patch_available(&self, _data: &[u8], _addr: u64) -> bool {
+        false
+    }
+    fn is_invert_branch_patch_available(&self, _data: &[u8], _addr: u64) -> bool {
+        false
+    }
+    fn is_skip_and_return_zero_patch_available(&self, _data: &[u8], _addr: u64) -> bool {
+        false
+    }
+    fn is_skip_and_return_value_patch_available(&self, _data: &[u8], _addr: u64) -> bool {
+        false
+    }
+
+    /// Overwrites the instruction in `data` with no-op instructions of the same total length.
+    ///
+    /// Returns `false` if the patch could not be applied, in which case `data` must be left untouched.
+    fn convert_to_nop(&self, _data: &mut [u8], _addr: u64) -> bool {
+        false
+    }
+
+    /// Rewrites the conditional branch in `data` into an unconditional branch to the same target.
+    ///
+    /// Returns `false` if the patch could not be applied, in which case `data` must be left untouched.
+    fn always_branch(&self, _data: &mut [u8], _addr: u64) -> bool {
+        false
+    }
+
+    /// Inverts the condition of the conditional branch in `data`.
+    ///
+    /// Returns `false` if the patch could not be applied, in which case `data` must be left untouched.
+    fn invert_branch(&self, _data: &mut [u8], _addr: u64) -> bool {
+        false
+    }
+
+    /// Rewrites the call instruction in `data` so that it is skipped and `value` is placed in the
+    /// return value register instead.
+    ///
+    /// Returns `false` if the patch could not be applied, in which case `data` must be left untouched.
+    fn skip_and_return_value(&self, _data: &mut [u8], _addr: u64, _value: u64) -> bool {
+        false
+    }
+
     fn handle(&self) -> Self::Handle;
 }
 
@@ -564,6 +707,117 @@ impl Register for CoreRegister {
     }
 }
 
+pub struct CoreRegisterStackInfo(*mut BNArchitecture, BNRegisterStackInfo);
+
+impl RegisterStackInfo for CoreRegisterStackInfo {
+    type RegStackType = CoreRegisterStack;
+    type RegType = CoreRegister;
+    type RegInfoType = CoreRegisterInfo;
+
+    fn storage_regs(&self) -> (Self::RegType, u32) {
+        (
+            CoreRegister(self.0, self.1.firstStorageReg),
+            self.1.storageCount,
+        )
+    }
+
+    fn top_relative_regs(&self) -> Option<(Self::RegType, u32)> {
+        if self.1.topRelativeCount == 0 {
+            None
+        } else {
+            Some((
+                CoreRegister(self.0, self.1.firstTopRelativeReg),
+                self.1.topRelativeCount,
+            ))
+        }
+    }
+
+    fn stack_top_reg(&self) -> Self::RegType {
+        CoreRegister(self.0, self.1.stackTopReg)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CoreRegisterStack(*mut BNArchitecture, u32);
+
+impl RegisterStack for CoreRegisterStack {
+    type InfoType = CoreRegisterStackInfo;
+    type RegType = CoreRegister;
+    type RegInfoType = CoreRegisterInfo;
+
+    fn name(&self) -> Cow<str> {
+        unsafe {
+            let name = BNGetArchitectureRegisterStackName(self.0, self.1);
+
+            // We need to guarantee ownership, as if we're still
+            // a Borrowed variant we're about to free the underlying
+            // memory.
+            let res = CStr::from_ptr(name);
+            let res = res.to_string_lossy().into_owned().into();
+
+            BNFreeString(name);
+
+            res
+        }
+    }
+
+    fn info(&self) -> CoreRegisterStackInfo {
+        CoreRegisterStackInfo(self.0, unsafe {
+            BNGetArchitectureRegisterStackInfo(self.0, self.1)
+        })
+    }
+
+    fn id(&self) -> u32 {
+        self.1
+    }
+}
+
+/// Placeholder `RegisterStackInfo` for architectures without register stacks.
+pub struct UnusedRegisterStackInfo<R: Register> {
+    _reg: PhantomData<R>,
+}
+
+/// Placeholder `RegisterStack` for architectures without register stacks.
+///
+/// This type can't be constructed, so `Architecture::register_stacks` and
+/// `Architecture::register_stack_from_id` must keep their default implementations.
+#[derive(Copy, Clone)]
+pub struct UnusedRegisterStack<R: Register> {
+    _reg: PhantomData<R>,
+}
+
+impl<R: Register> RegisterStackInfo for UnusedRegisterStackInfo<R> {
+    type RegStackType = UnusedRegisterStack<R>;
+    type RegType = R;
+    type RegInfoType = R::InfoType;
+
+    fn storage_regs(&self) -> (Self::RegType, u32) {
+        unreachable!()
+    }
+    fn top_relative_regs(&self) -> Option<(Self::RegType, u32)> {
+        unreachable!()
+    }
+    fn stack_top_reg(&self) -> Self::RegType {
+        unreachable!()
+    }
+}
+
+impl<R: Register> RegisterStack for UnusedRegisterStack<R> {
+    type InfoType = UnusedRegisterStackInfo<R>;
+    type RegType = R;
+    type RegInfoType = R::InfoType;
+
+    fn name(&self) -> Cow<str> {
+        unreachable!()
+    }
+    fn id(&self) -> u32 {
+        unreachable!()
+    }
+    fn info(&self) -> Self::InfoType {
+        unreachable!()
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub struct CoreFlag(*mut BNArchitecture, u32);
 impl Flag for CoreFlag {
@@ -752,6 +1006,90 @@ impl FlagGroup for CoreFlagGroup {
     }
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CoreIntrinsic(*mut BNArchitecture, u32);
+
+impl Intrinsic for CoreIntrinsic {
+    fn name(&self) -> Cow<str> {
+        unsafe {
+            let name = BNGetArchitectureIntrinsicName(self.0, self.1);
+
+            // We need to guarantee ownership, as if we're still
+            // a Borrowed variant we're about to free the underlying
+            // memory.
+            let res = CStr::from_ptr(name);
+            let res = res.to_string_lossy().into_owned().into();
+
+            BNFreeString(name);
+
+            res
+        }
+    }
+
+    fn id(&self) -> u32 {
+        self.1
+    }
+
+    fn inputs(&self) -> Vec<NameAndType<String>> {
+        unsafe {
+            let mut count: usize = 0;
+            let inputs = BNGetArchitectureIntrinsicInputs(self.0, self.1, &mut count as *mut _);
+
+            let ret = slice::from_raw_parts_mut(inputs, count)
+                .iter()
+                .map(NameAndType::from_raw)
+                .collect();
+
+            BNFreeNameAndTypeList(inputs, count);
+
+            ret
+        }
+    }
+
+    fn outputs(&self) -> Vec<Conf<Ref<Type>>> {
+        unsafe {
+            let mut count: usize = 0;
+            let outputs = BNGetArchitectureIntrinsicOutputs(self.0, self.1, &mut count as *mut _);
+
+            let ret = slice::from_raw_parts_mut(outputs, count)
+                .iter()
+                .map(|output| {
+                    let t = Type {
+                        handle: output.type_,
+                    };
+                    Conf::new(t.to_owned(), output.confidence)
+                })
+                .collect();
+
+            BNFreeOutputTypeList(outputs, count);
+
+            ret
+        }
+    }
+}
+
+/// Placeholder `Intrinsic` for architectures without intrinsics.
+///
+/// This type can't be constructed, so `Architecture::intrinsics` and
+/// `Architecture::intrinsic_from_id` must keep their default implementations.
+#[derive(Copy, Clone)]
+pub enum UnusedIntrinsic {}
+
+impl Intrinsic for UnusedIntrinsic {
+    fn name(&self) -> Cow<str> {
+        match *self {}
+    }
+    fn id(&self) -> u32 {
+        match *self {}
+    }
+    fn inputs(&self) -> Vec<NameAndType<String>> {
+        match *self {}
+    }
+    fn outputs(&self) -> Vec<Conf<Ref<Type>>> {
+        match *self {}
+    }
+}
+
 pub struct CoreArchitectureList(*mut *mut BNArchitecture, usize);
 impl ops::Deref for CoreArchitectureList {
     type Target = [CoreArchitecture];
@@ -839,10 +1177,13 @@ impl Architecture for CoreArchitecture {
 
     type RegisterInfo = CoreRegisterInfo;
     type Register = CoreRegister;
+    type RegisterStackInfo = CoreRegisterStackInfo;
+    type RegisterStack = CoreRegisterStack;
     type Flag = CoreFlag;
     type FlagWrite = CoreFlagWrite;
     type FlagClass = CoreFlagClass;
     type FlagGroup = CoreFlagGroup;
+    type Intrinsic = CoreIntrinsic;
 
     type InstructionTextContainer = InstructionTextTokenList;
 
@@ -1020,6 +1361,22 @@ impl Architecture for CoreArchitecture {
         }
     }
 
+    fn register_stacks(&self) -> Vec<CoreRegisterStack> {
+        unsafe {
+            let mut count: usize = 0;
+            let regs = BNGetAllArchitectureRegisterStacks(self.0, &mut count as *mut _);
+
+            let ret = slice::from_raw_parts_mut(regs, count)
+                .iter()
+                .map(|reg| CoreRegisterStack(self.0, *reg))
+                .collect();
+
+            BNFreeRegisterList(regs);
+
+            ret
+        }
+    }
+
     fn flags(&self) -> Vec<CoreFlag> {
         unsafe {
             let mut count: usize = 0;
@@ -1130,6 +1487,11 @@ impl Architecture for CoreArchitecture {
         Some(CoreRegister(self.0, id))
     }
 
+    fn register_stack_from_id(&self, id: u32) -> Option<CoreRegisterStack> {
+        // TODO validate in debug builds
+        Some(CoreRegisterStack(self.0, id))
+    }
+
     fn flag_from_id(&self, id: u32) -> Option<CoreFlag> {
         // TODO validate in debug builds
         Some(CoreFlag(self.0, id))
@@ -1150,6 +1512,116 @@ impl Architecture for CoreArchitecture {
         Some(CoreFlagGroup(self.0, id))
     }
 
+    fn intrinsics(&self) -> Vec<CoreIntrinsic> {
+        unsafe {
+            let mut count: usize = 0;
+            let intrinsics = BNGetAllArchitectureIntrinsics(self.0, &mut count as *mut _);
+
+            let ret = slice::from_raw_parts_mut(intrinsics, count)
+                .iter()
+                .map(|intrinsic| CoreIntrinsic(self.0, *intrinsic))
+                .collect();
+
+            BNFreeRegisterList(intrinsics);
+
+            ret
+        }
+    }
+
+    fn intrinsic_from_id(&self, id: u32) -> Option<CoreIntrinsic> {
+        // TODO validate in debug builds
+        Some(CoreIntrinsic(self.0, id))
+    }
+
+    fn can_assemble(&self) -> bool {
+        unsafe { BNCanArchitectureAssemble(self.0) }
+    }
+
+    fn assemble(&self, code: &str, addr: u64) -> Result<Vec<u8>, String> {
+        let code = CString::new(code).map_err(|_| "Invalid encoding in code string".to_string())?;
+
+        let result = match DataBuffer::new(&[]) {
+            Ok(result) => result,
+            Err(_) => return Err("Result buffer allocation failed".to_string()),
+        };
+        let mut error_raw: *mut c_char = ptr::null_mut();
+        let res = unsafe {
+            BNAssemble(
+                self.0,
+                code.as_ptr(),
+                addr,
+                result.0,
+                &mut error_raw as *mut *mut c_char,
+            )
+        };
+
+        let error = raw_to_string(error_raw);
+        if !error_raw.is_null() {
+            unsafe {
+                BNFreeString(error_raw);
+            }
+        }
+
+        if res {
+            Ok(result.get_data().to_vec())
+        } else {
+            Err(error.unwrap_or_else(|| "Assemble failed".into()))
+        }
+    }
+
+    fn is_never_branch_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        unsafe {
+            BNIsArchitectureNeverBranchPatchAvailable(self.0, data.as_ptr(), addr, data.len())
+        }
+    }
+
+    fn is_always_branch_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        unsafe {
+            BNIsArchitectureAlwaysBranchPatchAvailable(self.0, data.as_ptr(), addr, data.len())
+        }
+    }
+
+    fn is_invert_branch_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        unsafe {
+            BNIsArchitectureInvertBranchPatchAvailable(self.0, data.as_ptr(), addr, data.len())
+        }
+    }
+
+    fn is_skip_and_return_zero_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        unsafe {
+            BNIsArchitectureSkipAndReturnZeroPatchAvailable(self.0, data.as_ptr(), addr, data.len())
+        }
+    }
+
+    fn is_skip_and_return_value_patch_available(&self, data: &[u8], addr: u64) -> bool {
+        unsafe {
+            BNIsArchitectureSkipAndReturnValuePatchAvailable(
+                self.0,
+                data.as_ptr(),
+                addr,
+                data.len(),
+            )
+        }
+    }
+
+    fn convert_to_nop(&self, data: &mut [u8], addr: u64) -> bool {
+        unsafe { BNArchitectureConvertToNop(self.0, data.as_mut_ptr(), addr, data.len()) }
+    }
+
+    fn always_branch(&self, data: &mut [u8], addr: u64) -> bool {
+        unsafe { BNArchitectureAlwaysBranch(self.0, data.as_mut_ptr(), addr, data.len()) }
+    }
+
+    fn invert_branch(&self, data: &mut [u8], addr: u64) -> bool {
+        unsafe { BNArchitectureInvertBranch(self.0, data.as_mut_ptr(), addr, data.len()) }
+    }
+
+    fn skip_and_return_value(&self, data: &mut [u8], addr: u64, value: u64) -> bool {
+        unsafe {
+            BNArchitectureSkipAndReturnValue(self.0, data.as_mut_ptr(), addr, data.len(), value)
+        }
+    }
+
     fn handle(&self) -> CoreArchitecture {
         *self
     }
@@ -1199,6 +1671,13 @@ pub trait ArchitectureExt: Architecture {
         }
     }
 
+    fn register_stack_for_register(&self, reg: Self::Register) -> Option<Self::RegisterStack> {
+        match unsafe { BNGetArchitectureRegisterStackForRegister(self.as_ref().0, reg.id()) } {
+            0xffff_ffff => None,
+            stack => self.register_stack_from_id(stack),
+        }
+    }
+
     cc_func!(
         get_default_calling_convention,
         BNGetArchitectureDefaultCallingConvention,
@@ -1249,7 +1728,7 @@ where
     F: FnOnce(CustomArchitectureHandle<A>, CoreArchitecture) -> A,
 {
     use std::mem;
-    use std::os::raw::{c_char, c_void};
+    use std::os::raw::c_void;
 
     #[repr(C)]
     struct ArchitectureBuilder<A, F>
@@ -1899,152 +2378,356 @@ where
         }
     }
 
-    extern "C" fn cb_reg_stack_name<A>(ctxt: *mut c_void, _stack: u32) -> *mut c_char
+    extern "C" fn cb_reg_stack_name<A>(ctxt: *mut c_void, stack: u32) -> *mut c_char
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
-        BnString::new("reg_stack").into_raw()
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+
+        match custom_arch.register_stack_from_id(stack) {
+            Some(stack) => BnString::new(stack.name().as_ref()).into_raw(),
+            None => BnString::new("invalid_reg_stack").into_raw(),
+        }
     }
 
     extern "C" fn cb_reg_stacks<A>(ctxt: *mut c_void, count: *mut usize) -> *mut u32
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let stacks = custom_arch.register_stacks();
 
-        unsafe {
-            *count = 0;
-        }
-        ptr::null_mut()
+        alloc_register_list(stacks.iter().map(|r| r.id()), unsafe { &mut *count })
     }
 
     extern "C" fn cb_reg_stack_info<A>(
         ctxt: *mut c_void,
-        _stack: u32,
-        _info: *mut BNRegisterStackInfo,
+        stack: u32,
+        result: *mut BNRegisterStackInfo,
     ) where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let result = unsafe { &mut *result };
+
+        if let Some(stack) = custom_arch.register_stack_from_id(stack) {
+            let info = stack.info();
+
+            let (reg, count) = info.storage_regs();
+            result.firstStorageReg = reg.id();
+            result.storageCount = count;
+
+            if let Some((reg, count)) = info.top_relative_regs() {
+                result.firstTopRelativeReg = reg.id();
+                result.topRelativeCount = count;
+            } else {
+                result.firstTopRelativeReg = 0xffff_ffff;
+                result.topRelativeCount = 0;
+            }
+
+            result.stackTopReg = info.stack_top_reg().id();
+        }
     }
 
-    extern "C" fn cb_intrinsic_name<A>(ctxt: *mut c_void, _intrinsic: u32) -> *mut c_char
+    extern "C" fn cb_intrinsic_name<A>(ctxt: *mut c_void, intrinsic: u32) -> *mut c_char
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
-        BnString::new("intrinsic").into_raw()
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+
+        match custom_arch.intrinsic_from_id(intrinsic) {
+            Some(intrinsic) => BnString::new(intrinsic.name().as_ref()).into_raw(),
+            None => BnString::new("invalid_intrinsic").into_raw(),
+        }
     }
 
     extern "C" fn cb_intrinsics<A>(ctxt: *mut c_void, count: *mut usize) -> *mut u32
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let intrinsics = custom_arch.intrinsics();
 
-        unsafe {
-            *count = 0;
-        }
-        ptr::null_mut()
+        alloc_register_list(intrinsics.iter().map(|i| i.id()), unsafe { &mut *count })
     }
 
     extern "C" fn cb_intrinsic_inputs<A>(
         ctxt: *mut c_void,
-        _intrinsic: u32,
+        intrinsic: u32,
         count: *mut usize,
     ) -> *mut BNNameAndType
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+
+        let inputs = match custom_arch.intrinsic_from_id(intrinsic) {
+            Some(intrinsic) => intrinsic.inputs(),
+            None => Vec::new(),
+        };
 
         unsafe {
-            *count = 0;
+            *count = inputs.len();
+        }
+
+        if inputs.is_empty() {
+            return ptr::null_mut();
         }
-        ptr::null_mut()
+
+        let res: Box<[_]> = inputs
+            .into_iter()
+            .map(|input| {
+                let t = input.type_with_confidence();
+
+                BNNameAndType {
+                    name: BnString::new(input.name).into_raw(),
+                    type_: unsafe { Ref::into_raw(t.contents) }.handle,
+                    typeConfidence: t.confidence,
+                }
+            })
+            .collect();
+
+        Box::into_raw(res) as *mut BNNameAndType
     }
 
     extern "C" fn cb_free_name_and_types<A>(
-        ctxt: *mut c_void,
-        _nt: *mut BNNameAndType,
-        _count: usize,
+        _ctxt: *mut c_void,
+        nt: *mut BNNameAndType,
+        count: usize,
     ) where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        if nt.is_null() {
+            return;
+        }
+
+        unsafe {
+            let nt = Box::from_raw(ptr::slice_from_raw_parts_mut(nt, count));
+
+            for input in nt.iter() {
+                let _name = BnString::from_raw(input.name);
+                let _type = Type::ref_from_raw(input.type_);
+            }
+        }
     }
 
     extern "C" fn cb_intrinsic_outputs<A>(
         ctxt: *mut c_void,
-        _intrinsic: u32,
+        intrinsic: u32,
         count: *mut usize,
     ) -> *mut BNTypeWithConfidence
     where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+
+        let outputs = match custom_arch.intrinsic_from_id(intrinsic) {
+            Some(intrinsic) => intrinsic.outputs(),
+            None => Vec::new(),
+        };
 
         unsafe {
-            *count = 0;
+            *count = outputs.len();
+        }
+
+        if outputs.is_empty() {
+            return ptr::null_mut();
         }
-        ptr::null_mut()
+
+        let res: Box<[_]> = outputs
+            .into_iter()
+            .map(|output| BNTypeWithConfidence {
+                type_: unsafe { Ref::into_raw(output.contents) }.handle,
+                confidence: output.confidence,
+            })
+            .collect();
+
+        Box::into_raw(res) as *mut BNTypeWithConfidence
     }
 
     extern "C" fn cb_free_type_list<A>(
-        ctxt: *mut c_void,
-        _tl: *mut BNTypeWithConfidence,
-        _count: usize,
+        _ctxt: *mut c_void,
+        tl: *mut BNTypeWithConfidence,
+        count: usize,
     ) where
         A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
     {
-        let _custom_arch = unsafe { &*(ctxt as *mut A) };
+        if tl.is_null() {
+            return;
+        }
+
+        unsafe {
+            let tl = Box::from_raw(ptr::slice_from_raw_parts_mut(tl, count));
+
+            for output in tl.iter() {
+                let _type = Type::ref_from_raw(output.type_);
+            }
+        }
     }
 
-    // TODO : I have no idea what I'm doing and this is likely wrong!
-    extern "C" fn cb_can_assemble(_ctxt: *mut c_void) -> bool {
-        false
+    extern "C" fn cb_can_assemble<A>(ctxt: *mut c_void) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        custom_arch.can_assemble()
     }
 
-    extern "C" fn cb_assemble(
-        _ctxt: *mut c_void,
-        _code: *const c_char,
-        _addr: u64,
-        _result: *mut BNDataBuffer,
+    extern "C" fn cb_assemble<A>(
+        ctxt: *mut c_void,
+        code: *const c_char,
+        addr: u64,
+        buffer: *mut BNDataBuffer,
         errors: *mut *mut c_char,
-    ) -> bool {
-        unsafe {
-            *errors = ptr::null_mut();
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let code = raw_to_string(code).unwrap_or_default();
+
+        match custom_arch.assemble(&code, addr) {
+            Ok(result) => {
+                unsafe {
+                    BNSetDataBufferContents(buffer, result.as_ptr() as *mut _, result.len());
+                    *errors = BnString::new("").into_raw();
+                }
+                true
+            }
+            Err(result) => {
+                unsafe {
+                    *errors = BnString::new(result).into_raw();
+                }
+                false
+            }
         }
-        false
     }
 
-    extern "C" fn cb_patch_unavailable(
-        _ctxt: *mut c_void,
-        _data: *const u8,
-        _addr: u64,
-        _len: usize,
-    ) -> bool {
-        false
+    extern "C" fn cb_is_never_branch_patch_available<A>(
+        ctxt: *mut c_void,
+        data: *const u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts(data, len) };
+        custom_arch.is_never_branch_patch_available(data, addr)
     }
 
-    extern "C" fn cb_do_patch_unavailable(
-        _ctxt: *mut c_void,
-        _data: *mut u8,
-        _addr: u64,
-        _len: usize,
-    ) -> bool {
-        false
+    extern "C" fn cb_is_always_branch_patch_available<A>(
+        ctxt: *mut c_void,
+        data: *const u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts(data, len) };
+        custom_arch.is_always_branch_patch_available(data, addr)
     }
 
-    extern "C" fn cb_skip_patch_unavailable(
-        _ctxt: *mut c_void,
-        _data: *mut u8,
-        _addr: u64,
-        _len: usize,
-        _val: u64,
-    ) -> bool {
-        false
+    extern "C" fn cb_is_invert_branch_patch_available<A>(
+        ctxt: *mut c_void,
+        data: *const u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts(data, len) };
+        custom_arch.is_invert_branch_patch_available(data, addr)
+    }
+
+    extern "C" fn cb_is_skip_and_return_zero_patch_available<A>(
+        ctxt: *mut c_void,
+        data: *const u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts(data, len) };
+        custom_arch.is_skip_and_return_zero_patch_available(data, addr)
+    }
+
+    extern "C" fn cb_is_skip_and_return_value_patch_available<A>(
+        ctxt: *mut c_void,
+        data: *const u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts(data, len) };
+        custom_arch.is_skip_and_return_value_patch_available(data, addr)
+    }
+
+    extern "C" fn cb_convert_to_nop<A>(
+        ctxt: *mut c_void,
+        data: *mut u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts_mut(data, len) };
+        custom_arch.convert_to_nop(data, addr)
+    }
+
+    extern "C" fn cb_always_branch<A>(
+        ctxt: *mut c_void,
+        data: *mut u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts_mut(data, len) };
+        custom_arch.always_branch(data, addr)
+    }
+
+    extern "C" fn cb_invert_branch<A>(
+        ctxt: *mut c_void,
+        data: *mut u8,
+        addr: u64,
+        len: usize,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts_mut(data, len) };
+        custom_arch.invert_branch(data, addr)
+    }
+
+    extern "C" fn cb_skip_and_return_value<A>(
+        ctxt: *mut c_void,
+        data: *mut u8,
+        addr: u64,
+        len: usize,
+        val: u64,
+    ) -> bool
+    where
+        A: 'static + Architecture<Handle = CustomArchitectureHandle<A>> + Send + Sync,
+    {
+        let custom_arch = unsafe { &*(ctxt as *mut A) };
+        let data = unsafe { slice::from_raw_parts_mut(data, len) };
+        custom_arch.skip_and_return_value(data, addr, val)
     }
 
     let name = name.as_bytes_with_nul();
@@ -2119,19 +2802,19 @@ where
         getIntrinsicOutputs: Some(cb_intrinsic_outputs::<A>),
         freeTypeList: Some(cb_free_type_list::<A>),
 
-        canAssemble: Some(cb_can_assemble),
-        assemble: Some(cb_assemble),
+        canAssemble: Some(cb_can_assemble::<A>),
+        assemble: Some(cb_assemble::<A>),
 
-        isNeverBranchPatchAvailable: Some(cb_patch_unavailable),
-        isAlwaysBranchPatchAvailable: Some(cb_patch_unavailable),
-        isInvertBranchPatchAvailable: Some(cb_patch_unavailable),
-        isSkipAndReturnZeroPatchAvailable: Some(cb_patch_unavailable),
-        isSkipAndReturnValuePatchAvailable: Some(cb_patch_unavailable),
+        isNeverBranchPatchAvailable: Some(cb_is_never_branch_patch_available::<A>),
+        isAlwaysBranchPatchAvailable: Some(cb_is_always_branch_patch_available::<A>),
+        isInvertBranchPatchAvailable: Some(cb_is_invert_branch_patch_available::<A>),
+        isSkipAndReturnZeroPatchAvailable: Some(cb_is_skip_and_return_zero_patch_available::<A>),
+        isSkipAndReturnValuePatchAvailable: Some(cb_is_skip_and_return_value_patch_available::<A>),
 
-        convertToNop: Some(cb_do_patch_unavailable),
-        alwaysBranch: Some(cb_do_patch_unavailable),
-        invertBranch: Some(cb_do_patch_unavailable),
-        skipAndReturnValue: Some(cb_skip_patch_unavailable),
+        convertToNop: Some(cb_convert_to_nop::<A>),
+        alwaysBranch: Some(cb_always_branch::<A>),
+        invertBranch: Some(cb_invert_branch::<A>),
+        skipAndReturnValue: Some(cb_skip_and_return_value::<A>),
     };
 
     unsafe {
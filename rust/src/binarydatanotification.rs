@@ -0,0 +1,476 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Receive notifications when the contents or analysis of a `BinaryView` change.
+
+use binaryninjacore_sys::*;
+
+use std::os::raw::c_void;
+
+use crate::binaryview::{BinaryView, StringType};
+use crate::datavariable::DataVariable;
+use crate::function::Function;
+use crate::rc::*;
+use crate::symbol::Symbol;
+use crate::tags::{TagReference, TagType};
+use crate::types::{QualifiedName, Type};
+
+/// Callbacks invoked by the core as a `BinaryView` is modified and analyzed.
+///
+/// Every method has a no-op default, so implementors only need to override the
+/// events they are interested in. Notifications may be delivered from analysis
+/// threads, and must not block for extended periods of time.
+pub trait BinaryDataNotification: 'static + Send + Sync {
+    fn data_written(&self, _view: &BinaryView, _offset: u64, _len: usize) {}
+    fn data_inserted(&self, _view: &BinaryView, _offset: u64, _len: usize) {}
+    fn data_removed(&self, _view: &BinaryView, _offset: u64, _len: u64) {}
+
+    fn function_added(&self, _view: &BinaryView, _func: &Function) {}
+    fn function_removed(&self, _view: &BinaryView, _func: &Function) {}
+    fn function_updated(&self, _view: &BinaryView, _func: &Function) {}
+
+    fn data_variable_added(&self, _view: &BinaryView, _var: &DataVariable) {}
+    fn data_variable_removed(&self, _view: &BinaryView, _var: &DataVariable) {}
+    fn data_variable_updated(&self, _view: &BinaryView, _var: &DataVariable) {}
+
+    fn tag_type_updated(&self, _view: &BinaryView, _tag_type: &TagType) {}
+    fn tag_added(&self, _view: &BinaryView, _tag_ref: &TagReference) {}
+    fn tag_updated(&self, _view: &BinaryView, _tag_ref: &TagReference) {}
+    fn tag_removed(&self, _view: &BinaryView, _tag_ref: &TagReference) {}
+
+    fn symbol_added(&self, _view: &BinaryView, _sym: &Symbol) {}
+    fn symbol_removed(&self, _view: &BinaryView, _sym: &Symbol) {}
+    fn symbol_updated(&self, _view: &BinaryView, _sym: &Symbol) {}
+
+    fn type_defined(&self, _view: &BinaryView, _name: &QualifiedName, _ty: &Type) {}
+    fn type_undefined(&self, _view: &BinaryView, _name: &QualifiedName, _ty: &Type) {}
+
+    fn string_found(&self, _view: &BinaryView, _ty: StringType, _offset: u64, _len: usize) {}
+    fn string_removed(&self, _view: &BinaryView, _ty: StringType, _offset: u64, _len: usize) {}
+}
+
+/// Keeps a `BinaryDataNotification` registered with its `BinaryView`.
+///
+/// The notification is unregistered and dropped when the guard is dropped.
+pub struct NotificationGuard<N: BinaryDataNotification> {
+    view: Ref<BinaryView>,
+    ctxt: *mut N,
+    // the core identifies registered notifications by the address of this struct
+    callbacks: Box<BNBinaryDataNotification>,
+}
+
+unsafe impl<N: BinaryDataNotification> Send for NotificationGuard<N> {}
+unsafe impl<N: BinaryDataNotification> Sync for NotificationGuard<N> {}
+
+impl<N: BinaryDataNotification> NotificationGuard<N> {
+    pub fn view(&self) -> &BinaryView {
+        &self.view
+    }
+
+    pub fn notification(&self) -> &N {
+        unsafe { &*self.ctxt }
+    }
+}
+
+impl<N: BinaryDataNotification> Drop for NotificationGuard<N> {
+    fn drop(&mut self) {
+        unsafe {
+            BNUnregisterDataNotification(self.view.handle, &mut *self.callbacks);
+
+            let _notification = Box::from_raw(self.ctxt);
+        }
+    }
+}
+
+pub fn register<N: BinaryDataNotification>(
+    view: &BinaryView,
+    notification: N,
+) -> NotificationGuard<N> {
+    let raw = Box::into_raw(Box::new(notification));
+    let mut callbacks = Box::new(BNBinaryDataNotification {
+        context: raw as *mut _,
+        dataWritten: Some(cb_data_written::<N>),
+        dataInserted: Some(cb_data_inserted::<N>),
+        dataRemoved: Some(cb_data_removed::<N>),
+        functionAdded: Some(cb_function_added::<N>),
+        functionRemoved: Some(cb_function_removed::<N>),
+        functionUpdated: Some(cb_function_updated::<N>),
+        functionUpdateRequested: Some(cb_ignore_function),
+        dataVariableAdded: Some(cb_data_variable_added::<N>),
+        dataVariableRemoved: Some(cb_data_variable_removed::<N>),
+        dataVariableUpdated: Some(cb_data_variable_updated::<N>),
+        dataMetadataUpdated: Some(cb_ignore_offset),
+        tagTypeUpdated: Some(cb_tag_type_updated::<N>),
+        tagAdded: Some(cb_tag_added::<N>),
+        tagUpdated: Some(cb_tag_updated::<N>),
+        tagRemoved: Some(cb_tag_removed::<N>),
+        symbolAdded: Some(cb_symbol_added::<N>),
+        symbolUpdated: Some(cb_symbol_updated::<N>),
+        symbolRemoved: Some(cb_symbol_removed::<N>),
+        stringFound: Some(cb_string_found::<N>),
+        stringRemoved: Some(cb_string_removed::<N>),
+        typeDefined: Some(cb_type_defined::<N>),
+        typeUndefined: Some(cb_type_undefined::<N>),
+        typeReferenceChanged: Some(cb_ignore_type),
+        typeFieldReferenceChanged: Some(cb_ignore_type_field),
+    });
+
+    unsafe {
+        BNRegisterDataNotification(view.handle, &mut *callbacks);
+    }
+
+    NotificationGuard {
+        view: view.to_owned(),
+        ctxt: raw,
+        callbacks,
+    }
+}
+
+extern "C" fn cb_data_written<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    offset: u64,
+    len: usize,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::data_written", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+
+        notification.data_written(&view, offset, len);
+    })
+}
+
+extern "C" fn cb_data_inserted<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    offset: u64,
+    len: usize,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::data_inserted", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+
+        notification.data_inserted(&view, offset, len);
+    })
+}
+
+extern "C" fn cb_data_removed<N>(ctxt: *mut c_void, view: *mut BNBinaryView, offset: u64, len: u64)
+where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::data_removed", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+
+        notification.data_removed(&view, offset, len);
+    })
+}
+
+extern "C" fn cb_function_added<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    func: *mut BNFunction,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::function_added", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let func = Function { handle: func };
+
+        notification.function_added(&view, &func);
+    })
+}
+
+extern "C" fn cb_function_removed<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    func: *mut BNFunction,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::function_removed", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let func = Function { handle: func };
+
+        notification.function_removed(&view, &func);
+    })
+}
+
+extern "C" fn cb_function_updated<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    func: *mut BNFunction,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::function_updated", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let func = Function { handle: func };
+
+        notification.function_updated(&view, &func);
+    })
+}
+
+extern "C" fn cb_data_variable_added<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    var: *mut BNDataVariable,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::data_variable_added", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let var = DataVariable::from_borrowed_raw(&*var);
+
+        notification.data_variable_added(&view, &var);
+    })
+}
+
+extern "C" fn cb_data_variable_removed<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    var: *mut BNDataVariable,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::data_variable_removed", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let var = DataVariable::from_borrowed_raw(&*var);
+
+        notification.data_variable_removed(&view, &var);
+    })
+}
+
+extern "C" fn cb_data_variable_updated<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    var: *mut BNDataVariable,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::data_variable_updated", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let var = DataVariable::from_borrowed_raw(&*var);
+
+        notification.data_variable_updated(&view, &var);
+    })
+}
+
+extern "C" fn cb_tag_type_updated<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    tag_type: *mut BNTagType,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::tag_type_updated", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let tag_type = TagType { handle: tag_type };
+
+        notification.tag_type_updated(&view, &tag_type);
+    })
+}
+
+extern "C" fn cb_tag_added<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    tag_ref: *mut BNTagReference,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::tag_added", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let tag_ref = &*(tag_ref as *const TagReference);
+
+        notification.tag_added(&view, tag_ref);
+    })
+}
+
+extern "C" fn cb_tag_updated<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    tag_ref: *mut BNTagReference,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::tag_updated", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let tag_ref = &*(tag_ref as *const TagReference);
+
+        notification.tag_updated(&view, tag_ref);
+    })
+}
+
+extern "C" fn cb_tag_removed<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    tag_ref: *mut BNTagReference,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::tag_removed", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let tag_ref = &*(tag_ref as *const TagReference);
+
+        notification.tag_removed(&view, tag_ref);
+    })
+}
+
+extern "C" fn cb_symbol_added<N>(ctxt: *mut c_void, view: *mut BNBinaryView, sym: *mut BNSymbol)
+where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::symbol_added", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let sym = Symbol::from_raw(sym);
+
+        notification.symbol_added(&view, &sym);
+    })
+}
+
+extern "C" fn cb_symbol_removed<N>(ctxt: *mut c_void, view: *mut BNBinaryView, sym: *mut BNSymbol)
+where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::symbol_removed", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let sym = Symbol::from_raw(sym);
+
+        notification.symbol_removed(&view, &sym);
+    })
+}
+
+extern "C" fn cb_symbol_updated<N>(ctxt: *mut c_void, view: *mut BNBinaryView, sym: *mut BNSymbol)
+where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::symbol_updated", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let sym = Symbol::from_raw(sym);
+
+        notification.symbol_updated(&view, &sym);
+    })
+}
+
+extern "C" fn cb_type_defined<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    name: *mut BNQualifiedName,
+    ty: *mut BNType,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::type_defined", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let name = &*(name as *const QualifiedName);
+        let ty = Type { handle: ty };
+
+        notification.type_defined(&view, name, &ty);
+    })
+}
+
+extern "C" fn cb_type_undefined<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    name: *mut BNQualifiedName,
+    ty: *mut BNType,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::type_undefined", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+        let name = &*(name as *const QualifiedName);
+        let ty = Type { handle: ty };
+
+        notification.type_undefined(&view, name, &ty);
+    })
+}
+
+extern "C" fn cb_string_found<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    ty: StringType,
+    offset: u64,
+    len: usize,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::string_found", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+
+        notification.string_found(&view, ty, offset, len);
+    })
+}
+
+extern "C" fn cb_string_removed<N>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    ty: StringType,
+    offset: u64,
+    len: usize,
+) where
+    N: BinaryDataNotification,
+{
+    ffi_wrap!("BinaryDataNotification::string_removed", unsafe {
+        let notification = &*(ctxt as *const N);
+        let view = BinaryView { handle: view };
+
+        notification.string_removed(&view, ty, offset, len);
+    })
+}
+
+extern "C" fn cb_ignore_function(
+    _ctxt: *mut c_void,
+    _view: *mut BNBinaryView,
+    _func: *mut BNFunction,
+) {
+}
+
+extern "C" fn cb_ignore_offset(_ctxt: *mut c_void, _view: *mut BNBinaryView, _offset: u64) {}
+
+extern "C" fn cb_ignore_type(
+    _ctxt: *mut c_void,
+    _view: *mut BNBinaryView,
+    _name: *mut BNQualifiedName,
+    _ty: *mut BNType,
+) {
+}
+
+extern "C" fn cb_ignore_type_field(
+    _ctxt: *mut c_void,
+    _view: *mut BNBinaryView,
+    _name: *mut BNQualifiedName,
+    _offset: u64,
+) {
+}
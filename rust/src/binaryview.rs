@@ -15,7 +15,10 @@
 use binaryninjacore_sys::*;
 
 pub use binaryninjacore_sys::BNModificationStatus as ModificationStatus;
+pub use binaryninjacore_sys::BNStringType as StringType;
 
+use std::iter;
+use std::mem;
 use std::ops;
 use std::ptr;
 use std::result;
@@ -24,17 +27,24 @@ use crate::architecture::Architecture;
 use crate::architecture::CoreArchitecture;
 use crate::basicblock::BasicBlock;
 use crate::databuffer::DataBuffer;
+use crate::datavariable::DataVariable;
 use crate::debuginfo::DebugInfo;
 use crate::fileaccessor::FileAccessor;
 use crate::filemetadata::FileMetadata;
 use crate::flowgraph::FlowGraph;
 use crate::function::{Function, NativeBlock};
+use crate::metadata::{CoreMetadata, Metadata};
 use crate::platform::Platform;
+use crate::references::{
+    addresses_from_raw, ReferenceSource, TypeFieldReference, TypeReferenceSource,
+};
 use crate::section::{Section, SectionBuilder};
 use crate::segment::{Segment, SegmentBuilder};
 use crate::settings::Settings;
 use crate::symbol::{Symbol, SymbolType};
-use crate::types::{DataVariable, QualifiedName, Type};
+use crate::tags::{Tag, TagReference, TagType};
+use crate::typelibrary::TypeLibrary;
+use crate::types::{Conf, QualifiedName, QualifiedNameAndType, Type};
 use crate::Endianness;
 
 use crate::rc::*;
@@ -44,6 +54,73 @@ use crate::string::*;
 
 pub type Result<R> = result::Result<R, ()>;
 
+/// A string found by analysis. `length` is in bytes, not characters.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StringReference {
+    pub ty: StringType,
+    pub start: u64,
+    pub length: usize,
+}
+
+impl StringReference {
+    fn from_raw(raw: &BNStringReference) -> Self {
+        Self {
+            ty: raw.type_,
+            start: raw.start,
+            length: raw.length,
+        }
+    }
+
+    pub fn address_range(&self) -> ops::Range<u64> {
+        self.start..self.start + self.length as u64
+    }
+
+    /// Decodes `data`, the contents of the string, using the string's encoding. Invalid
+    /// characters are replaced with U+FFFD.
+    pub fn decode(&self, data: &[u8], endianness: Endianness) -> String {
+        let word = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .enumerate()
+                .fold(0u32, |value, (i, &b)| match endianness {
+                    Endianness::LittleEndian => value | (b as u32) << (8 * i),
+                    Endianness::BigEndian => value << 8 | b as u32,
+                })
+        };
+
+        match self.ty {
+            StringType::AsciiString | StringType::Utf8String => {
+                String::from_utf8_lossy(data).into_owned()
+            }
+            StringType::Utf16String => {
+                let units: Vec<u16> = data.chunks_exact(2).map(|c| word(c) as u16).collect();
+                String::from_utf16_lossy(&units)
+            }
+            StringType::Utf32String => data
+                .chunks_exact(4)
+                .map(|c| char::from_u32(word(c)).unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect(),
+        }
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for StringReference {
+    type Raw = BNStringReference;
+    type Context = ();
+
+    unsafe fn free(raw: *mut BNStringReference, _count: usize, _context: &()) {
+        BNFreeStringReferenceList(raw);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for StringReference {
+    type Wrapped = StringReference;
+
+    unsafe fn wrap_raw(raw: &'a BNStringReference, _context: &'a ()) -> StringReference {
+        StringReference::from_raw(raw)
+    }
+}
+
 pub trait BinaryViewBase: AsRef<BinaryView> {
     fn read(&self, _buf: &mut [u8], _offset: u64) -> usize {
         0
@@ -429,6 +506,73 @@ pub trait BinaryViewExt: BinaryViewBase {
         }
     }
 
+    fn has_data_variables(&self) -> bool {
+        unsafe { BNHasDataVariables(self.as_ref().handle) }
+    }
+
+    fn data_var_at(&self, addr: u64) -> Option<DataVariable> {
+        unsafe {
+            let mut var = mem::zeroed();
+
+            if BNGetDataVariableAtAddress(self.as_ref().handle, addr, &mut var) {
+                Some(DataVariable::from_raw(var))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns the first data variable starting after `addr`, if any.
+    fn get_next_data_var(&self, addr: u64) -> Option<DataVariable> {
+        let next = unsafe { BNGetNextDataVariableStartAfterAddress(self.as_ref().handle, addr) };
+
+        if next <= addr {
+            return None;
+        }
+
+        self.data_var_at(next)
+    }
+
+    /// Returns the last data variable starting before `addr`, if any.
+    fn get_previous_data_var(&self, addr: u64) -> Option<DataVariable> {
+        let prev =
+            unsafe { BNGetPreviousDataVariableStartBeforeAddress(self.as_ref().handle, addr) };
+
+        if prev >= addr {
+            return None;
+        }
+
+        self.data_var_at(prev)
+    }
+
+    fn define_auto_data_var<'a, T: Into<Conf<&'a Type>>>(&self, addr: u64, ty: T) {
+        let mut ty: BNTypeWithConfidence = ty.into().into();
+
+        unsafe {
+            BNDefineDataVariable(self.as_ref().handle, addr, &mut ty);
+        }
+    }
+
+    fn define_user_data_var<'a, T: Into<Conf<&'a Type>>>(&self, addr: u64, ty: T) {
+        let mut ty: BNTypeWithConfidence = ty.into().into();
+
+        unsafe {
+            BNDefineUserDataVariable(self.as_ref().handle, addr, &mut ty);
+        }
+    }
+
+    fn undefine_auto_data_var(&self, addr: u64) {
+        unsafe {
+            BNUndefineDataVariable(self.as_ref().handle, addr);
+        }
+    }
+
+    fn undefine_user_data_var(&self, addr: u64) {
+        unsafe {
+            BNUndefineUserDataVariable(self.as_ref().handle, addr);
+        }
+    }
+
     fn define_user_type<S: BnStrCompatible>(&self, name: S, type_obj: &Type) {
         unsafe {
             let mut qualified_name = QualifiedName::from(name);
@@ -436,6 +580,684 @@ pub trait BinaryViewExt: BinaryViewBase {
         }
     }
 
+    /// Defines `type_obj` on behalf of analysis or a loader, keyed by `id` so that defining it
+    /// again replaces the old definition. See `Type::generate_auto_type_id`.
+    ///
+    /// Returns the name the type was actually defined as, which differs from `default_name` if
+    /// that name was already taken.
+    fn define_auto_type<N: Into<QualifiedName>, I: BnStrCompatible>(
+        &self,
+        default_name: N,
+        id: I,
+        type_obj: &Type,
+    ) -> QualifiedName {
+        let mut qualified_name = default_name.into();
+        let id = id.as_bytes_with_nul();
+
+        unsafe {
+            QualifiedName(BNDefineAnalysisType(
+                self.as_ref().handle,
+                id.as_ref().as_ptr() as *const _,
+                &mut qualified_name.0,
+                type_obj.handle,
+            ))
+        }
+    }
+
+    fn undefine_auto_type<I: BnStrCompatible>(&self, id: I) {
+        let id = id.as_bytes_with_nul();
+
+        unsafe {
+            BNUndefineAnalysisType(self.as_ref().handle, id.as_ref().as_ptr() as *const _);
+        }
+    }
+
+    fn undefine_user_type<N: Into<QualifiedName>>(&self, name: N) {
+        let mut qualified_name = name.into();
+
+        unsafe {
+            BNUndefineUserAnalysisType(self.as_ref().handle, &mut qualified_name.0);
+        }
+    }
+
+    fn rename_type<N: Into<QualifiedName>, M: Into<QualifiedName>>(
+        &self,
+        old_name: N,
+        new_name: M,
+    ) {
+        let mut old_name = old_name.into();
+        let mut new_name = new_name.into();
+
+        unsafe {
+            BNRenameAnalysisType(self.as_ref().handle, &mut old_name.0, &mut new_name.0);
+        }
+    }
+
+    fn get_type_by_name<N: Into<QualifiedName>>(&self, name: N) -> Option<Ref<Type>> {
+        let mut qualified_name = name.into();
+
+        unsafe {
+            let raw = BNGetAnalysisTypeByName(self.as_ref().handle, &mut qualified_name.0);
+
+            if raw.is_null() {
+                return None;
+            }
+
+            Some(Type::ref_from_raw(raw))
+        }
+    }
+
+    fn get_type_by_id<I: BnStrCompatible>(&self, id: I) -> Option<Ref<Type>> {
+        let id = id.as_bytes_with_nul();
+
+        unsafe {
+            let raw = BNGetAnalysisTypeById(self.as_ref().handle, id.as_ref().as_ptr() as *const _);
+
+            if raw.is_null() {
+                return None;
+            }
+
+            Some(Type::ref_from_raw(raw))
+        }
+    }
+
+    fn get_type_id<N: Into<QualifiedName>>(&self, name: N) -> Option<BnString> {
+        let mut qualified_name = name.into();
+
+        unsafe {
+            let raw = BNGetAnalysisTypeId(self.as_ref().handle, &mut qualified_name.0);
+
+            if raw.is_null() {
+                return None;
+            }
+
+            let id = BnString::from_raw(raw);
+            if id.as_str().is_empty() {
+                return None;
+            }
+
+            Some(id)
+        }
+    }
+
+    fn is_type_auto_defined<N: Into<QualifiedName>>(&self, name: N) -> bool {
+        let mut qualified_name = name.into();
+
+        unsafe { BNIsAnalysisTypeAutoDefined(self.as_ref().handle, &mut qualified_name.0) }
+    }
+
+    fn types(&self) -> Array<QualifiedNameAndType> {
+        unsafe {
+            let mut count = 0;
+            let types = BNGetAnalysisTypeList(self.as_ref().handle, &mut count);
+
+            Array::new(types, count, ())
+        }
+    }
+
+    /// Makes the types and objects in `lib` available for import into this view.
+    fn add_type_library(&self, lib: &TypeLibrary) {
+        unsafe { BNAddBinaryViewTypeLibrary(self.as_ref().handle, lib.handle) }
+    }
+
+    fn type_library_by_name<S: BnStrCompatible>(&self, name: S) -> Option<Ref<TypeLibrary>> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            let raw = BNGetBinaryViewTypeLibrary(self.as_ref().handle, name.as_ref().as_ptr() as _);
+
+            if raw.is_null() {
+                None
+            } else {
+                Some(TypeLibrary::ref_from_raw(raw))
+            }
+        }
+    }
+
+    fn type_libraries(&self) -> Array<TypeLibrary> {
+        unsafe {
+            let mut count = 0;
+            let libs = BNGetBinaryViewTypeLibraries(self.as_ref().handle, &mut count);
+
+            Array::new(libs, count, ())
+        }
+    }
+
+    /// Imports the type `name` from `lib` into this view, searching every added type library if
+    /// `lib` is `None`. The imported type is returned as a named type reference.
+    fn import_library_type<N: Into<QualifiedName>>(
+        &self,
+        name: N,
+        lib: Option<&TypeLibrary>,
+    ) -> Option<Ref<Type>> {
+        let mut name = name.into();
+        let lib = lib.map_or(ptr::null_mut(), |lib| lib.handle);
+
+        unsafe {
+            let raw = BNBinaryViewImportTypeLibraryType(self.as_ref().handle, lib, &mut name.0);
+
+            if raw.is_null() {
+                None
+            } else {
+                Some(Type::ref_from_raw(raw))
+            }
+        }
+    }
+
+    /// Imports the prototype of the function or variable `name` from `lib`, searching every
+    /// added type library if `lib` is `None`. Types it depends on are imported too.
+    fn import_library_object<N: Into<QualifiedName>>(
+        &self,
+        name: N,
+        lib: Option<&TypeLibrary>,
+    ) -> Option<Ref<Type>> {
+        let mut name = name.into();
+        let lib = lib.map_or(ptr::null_mut(), |lib| lib.handle);
+
+        unsafe {
+            let raw = BNBinaryViewImportTypeLibraryObject(self.as_ref().handle, lib, &mut name.0);
+
+            if raw.is_null() {
+                None
+            } else {
+                Some(Type::ref_from_raw(raw))
+            }
+        }
+    }
+
+    /// Adds `type_obj` to `lib` as `name`, along with any types from this view it refers to.
+    fn export_type_to_library<N: Into<QualifiedName>>(
+        &self,
+        lib: &TypeLibrary,
+        name: N,
+        type_obj: &Type,
+    ) {
+        let mut name = name.into();
+
+        unsafe {
+            BNBinaryViewExportTypeToTypeLibrary(
+                self.as_ref().handle,
+                lib.handle,
+                &mut name.0,
+                type_obj.handle,
+            )
+        }
+    }
+
+    /// Adds the object prototype `type_obj` to `lib` as `name`, along with any types from this
+    /// view it refers to.
+    fn export_object_to_library<N: Into<QualifiedName>>(
+        &self,
+        lib: &TypeLibrary,
+        name: N,
+        type_obj: &Type,
+    ) {
+        let mut name = name.into();
+
+        unsafe {
+            BNBinaryViewExportObjectToTypeLibrary(
+                self.as_ref().handle,
+                lib.handle,
+                &mut name.0,
+                type_obj.handle,
+            )
+        }
+    }
+
+    /// Creates a tag type and adds it to this view.
+    fn create_tag_type<N: BnStrCompatible, I: BnStrCompatible>(
+        &self,
+        name: N,
+        icon: I,
+    ) -> Ref<TagType> {
+        unsafe {
+            let tag_type = TagType::ref_from_raw(BNCreateTagType(self.as_ref().handle));
+            tag_type.set_name(name);
+            tag_type.set_icon(icon);
+
+            BNAddTagType(self.as_ref().handle, tag_type.handle);
+
+            tag_type
+        }
+    }
+
+    fn remove_tag_type(&self, tag_type: &TagType) {
+        unsafe { BNRemoveTagType(self.as_ref().handle, tag_type.handle) }
+    }
+
+    fn tag_type_by_name<S: BnStrCompatible>(&self, name: S) -> Option<Ref<TagType>> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            let raw = BNGetTagType(self.as_ref().handle, name.as_ref().as_ptr() as *mut _);
+
+            if raw.is_null() {
+                None
+            } else {
+                Some(TagType::ref_from_raw(raw))
+            }
+        }
+    }
+
+    fn tag_type_by_id<S: BnStrCompatible>(&self, id: S) -> Option<Ref<TagType>> {
+        let id = id.as_bytes_with_nul();
+
+        unsafe {
+            let raw = BNGetTagTypeById(self.as_ref().handle, id.as_ref().as_ptr() as *mut _);
+
+            if raw.is_null() {
+                None
+            } else {
+                Some(TagType::ref_from_raw(raw))
+            }
+        }
+    }
+
+    fn tag_types(&self) -> Array<TagType> {
+        unsafe {
+            let mut count = 0;
+            let tag_types = BNGetTagTypes(self.as_ref().handle, &mut count);
+
+            Array::new(tag_types, count, ())
+        }
+    }
+
+    /// Creates a tag and adds it to this view, ready to be placed on an address, function or data.
+    fn create_tag<S: BnStrCompatible>(&self, tag_type: &TagType, data: S, user: bool) -> Ref<Tag> {
+        let tag = Tag::new(tag_type, data);
+        self.add_tag(&tag, user);
+
+        tag
+    }
+
+    fn add_tag(&self, tag: &Tag, user: bool) {
+        unsafe { BNAddTag(self.as_ref().handle, tag.handle, user) }
+    }
+
+    fn remove_tag(&self, tag: &Tag, user: bool) {
+        unsafe { BNRemoveTag(self.as_ref().handle, tag.handle, user) }
+    }
+
+    fn tag_by_id<S: BnStrCompatible>(&self, id: S) -> Option<Ref<Tag>> {
+        let id = id.as_bytes_with_nul();
+
+        unsafe {
+            let raw = BNGetTag(self.as_ref().handle, id.as_ref().as_ptr() as *mut _);
+
+            if raw.is_null() {
+                None
+            } else {
+                Some(Tag::ref_from_raw(raw))
+            }
+        }
+    }
+
+    fn add_auto_data_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNAddAutoDataTag(self.as_ref().handle, addr, tag.handle) }
+    }
+
+    fn remove_auto_data_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNRemoveAutoDataTag(self.as_ref().handle, addr, tag.handle) }
+    }
+
+    fn remove_auto_data_tags_of_type(&self, addr: u64, tag_type: &TagType) {
+        unsafe { BNRemoveAutoDataTagsOfType(self.as_ref().handle, addr, tag_type.handle) }
+    }
+
+    fn add_user_data_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNAddUserDataTag(self.as_ref().handle, addr, tag.handle) }
+    }
+
+    fn remove_user_data_tag(&self, addr: u64, tag: &Tag) {
+        unsafe { BNRemoveUserDataTag(self.as_ref().handle, addr, tag.handle) }
+    }
+
+    fn remove_user_data_tags_of_type(&self, addr: u64, tag_type: &TagType) {
+        unsafe { BNRemoveUserDataTagsOfType(self.as_ref().handle, addr, tag_type.handle) }
+    }
+
+    fn create_auto_data_tag<S: BnStrCompatible>(
+        &self,
+        addr: u64,
+        tag_type: &TagType,
+        data: S,
+    ) -> Ref<Tag> {
+        let tag = self.create_tag(tag_type, data, false);
+        self.add_auto_data_tag(addr, &tag);
+
+        tag
+    }
+
+    fn create_user_data_tag<S: BnStrCompatible>(
+        &self,
+        addr: u64,
+        tag_type: &TagType,
+        data: S,
+    ) -> Ref<Tag> {
+        let tag = self.create_tag(tag_type, data, true);
+        self.add_user_data_tag(addr, &tag);
+
+        tag
+    }
+
+    /// Tags on the data at `addr`, both auto and user.
+    fn data_tags_at(&self, addr: u64) -> Array<Tag> {
+        unsafe {
+            let mut count = 0;
+            let tags = BNGetDataTags(self.as_ref().handle, addr, &mut count);
+
+            Array::new(tags, count, ())
+        }
+    }
+
+    fn data_tags_in_range(&self, range: ops::Range<u64>) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs =
+                BNGetDataTagsInRange(self.as_ref().handle, range.start, range.end, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    fn data_tag_references(&self) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetDataTagReferences(self.as_ref().handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Every address, function and data tag in the view.
+    fn all_tag_references(&self) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetAllTagReferences(self.as_ref().handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Every address, function and data tag of type `tag_type` in the view.
+    fn tag_references_of_type(&self, tag_type: &TagType) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs =
+                BNGetAllTagReferencesOfType(self.as_ref().handle, tag_type.handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    fn tag_reference_count_of_type(&self, tag_type: &TagType) -> usize {
+        unsafe { BNGetAllTagReferencesOfTypeCount(self.as_ref().handle, tag_type.handle) }
+    }
+
+    /// Stores `value` under `key`, replacing any existing value. Metadata is saved in the
+    /// database along with the view; auto metadata is expected to be regenerated by analysis.
+    ///
+    /// Fails without storing anything if a string in `value` contains a nul byte.
+    fn store_metadata<S: BnStrCompatible, V: Into<Metadata>>(
+        &self,
+        key: S,
+        value: V,
+        auto: bool,
+    ) -> Result<()> {
+        let key = key.as_bytes_with_nul();
+        let value = value.into().to_raw()?;
+
+        unsafe {
+            BNBinaryViewStoreMetadata(
+                self.as_ref().handle,
+                key.as_ref().as_ptr() as *const _,
+                value.0,
+                auto,
+            )
+        }
+
+        Ok(())
+    }
+
+    fn query_metadata<S: BnStrCompatible>(&self, key: S) -> Option<Metadata> {
+        let key = key.as_bytes_with_nul();
+
+        unsafe {
+            let raw =
+                BNBinaryViewQueryMetadata(self.as_ref().handle, key.as_ref().as_ptr() as *const _);
+            CoreMetadata::from_raw(raw).and_then(|m| m.value())
+        }
+    }
+
+    fn remove_metadata<S: BnStrCompatible>(&self, key: S) {
+        let key = key.as_bytes_with_nul();
+
+        unsafe {
+            BNBinaryViewRemoveMetadata(self.as_ref().handle, key.as_ref().as_ptr() as *const _)
+        }
+    }
+
+    /// Instructions that refer to `addr`.
+    fn code_refs_to(&self, addr: u64) -> Array<ReferenceSource> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetCodeReferences(self.as_ref().handle, addr, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Instructions that refer to any address in `range`.
+    fn code_refs_in_range(&self, range: ops::Range<u64>) -> Array<ReferenceSource> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetCodeReferencesInRange(
+                self.as_ref().handle,
+                range.start,
+                range.end.saturating_sub(range.start),
+                &mut count,
+            );
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Addresses referred to by the instruction at `addr`, in every function containing it.
+    fn code_refs_from<A: Architecture>(&self, arch: &A, addr: u64) -> Vec<u64> {
+        let mut result = Vec::new();
+
+        unsafe {
+            for function in self.functions_containing(addr).iter() {
+                let mut source = BNReferenceSource {
+                    func: function.handle,
+                    arch: arch.as_ref().0,
+                    addr,
+                };
+
+                let mut count = 0;
+                let refs = BNGetCodeReferencesFrom(self.as_ref().handle, &mut source, &mut count);
+
+                result.extend(addresses_from_raw(refs, count, BNFreeAddressList));
+            }
+        }
+
+        result.sort_unstable();
+        result.dedup();
+        result
+    }
+
+    /// Data addresses that refer to `addr`, e.g. pointers in a table.
+    fn data_refs_to(&self, addr: u64) -> Vec<u64> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetDataReferences(self.as_ref().handle, addr, &mut count);
+
+            addresses_from_raw(refs, count, BNFreeDataReferences)
+        }
+    }
+
+    /// Addresses referred to by the data at `addr`.
+    fn data_refs_from(&self, addr: u64) -> Vec<u64> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetDataReferencesFrom(self.as_ref().handle, addr, &mut count);
+
+            addresses_from_raw(refs, count, BNFreeDataReferences)
+        }
+    }
+
+    /// Instructions that use the type `name`, e.g. through a variable or a cast.
+    fn code_refs_for_type<N: Into<QualifiedName>>(&self, name: N) -> Array<ReferenceSource> {
+        let mut name = name.into();
+
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetCodeReferencesForType(self.as_ref().handle, &mut name.0, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Data variables of the type `name`.
+    fn data_refs_for_type<N: Into<QualifiedName>>(&self, name: N) -> Vec<u64> {
+        let mut name = name.into();
+
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetDataReferencesForType(self.as_ref().handle, &mut name.0, &mut count);
+
+            addresses_from_raw(refs, count, BNFreeDataReferences)
+        }
+    }
+
+    /// Types with a member of the type `name`, or a pointer to it.
+    fn type_refs_for_type<N: Into<QualifiedName>>(&self, name: N) -> Array<TypeReferenceSource> {
+        let mut name = name.into();
+
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetTypeReferencesForType(self.as_ref().handle, &mut name.0, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Instructions that access the member at `offset` in the structure `name`.
+    fn code_refs_for_type_field<N: Into<QualifiedName>>(
+        &self,
+        name: N,
+        offset: u64,
+    ) -> Array<TypeFieldReference> {
+        let mut name = name.into();
+
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetCodeReferencesForTypeField(
+                self.as_ref().handle,
+                &mut name.0,
+                offset,
+                &mut count,
+            );
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Data addresses that refer to the member at `offset` in the structure `name`.
+    fn data_refs_for_type_field<N: Into<QualifiedName>>(&self, name: N, offset: u64) -> Vec<u64> {
+        let mut name = name.into();
+
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetDataReferencesForTypeField(
+                self.as_ref().handle,
+                &mut name.0,
+                offset,
+                &mut count,
+            );
+
+            addresses_from_raw(refs, count, BNFreeDataReferences)
+        }
+    }
+
+    /// Types with a member of the type of the member at `offset` in the structure `name`.
+    fn type_refs_for_type_field<N: Into<QualifiedName>>(
+        &self,
+        name: N,
+        offset: u64,
+    ) -> Array<TypeReferenceSource> {
+        let mut name = name.into();
+
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetTypeReferencesForTypeField(
+                self.as_ref().handle,
+                &mut name.0,
+                offset,
+                &mut count,
+            );
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Strings found by analysis, in address order.
+    fn strings(&self) -> Array<StringReference> {
+        unsafe {
+            let mut count = 0;
+            let strings = BNGetStrings(self.as_ref().handle, &mut count);
+
+            Array::new(strings, count, ())
+        }
+    }
+
+    /// Strings found by analysis that start in `range`, in address order.
+    fn strings_in_range(&self, range: ops::Range<u64>) -> Array<StringReference> {
+        unsafe {
+            let mut count = 0;
+            let strings = BNGetStringsInRange(
+                self.as_ref().handle,
+                range.start,
+                range.end.saturating_sub(range.start),
+                &mut count,
+            );
+
+            Array::new(strings, count, ())
+        }
+    }
+
+    /// The string found by analysis that contains `addr`.
+    fn string_reference_at(&self, addr: u64) -> Option<StringReference> {
+        let mut raw = BNStringReference {
+            type_: StringType::AsciiString,
+            start: 0,
+            length: 0,
+        };
+
+        if unsafe { BNGetStringAtAddress(self.as_ref().handle, addr, &mut raw) } {
+            Some(StringReference::from_raw(&raw))
+        } else {
+            None
+        }
+    }
+
+    /// The value of the string found by analysis that contains `addr`, decoded with the
+    /// string's encoding and the view's endianness.
+    fn string_at(&self, addr: u64) -> Option<String> {
+        let string = self.string_reference_at(addr)?;
+        let data = self.read_vec(string.start, string.length);
+
+        Some(string.decode(&data, self.default_endianness()))
+    }
+
+    fn add_user_data_ref(&self, from_addr: u64, to_addr: u64) {
+        unsafe { BNAddUserDataReference(self.as_ref().handle, from_addr, to_addr) }
+    }
+
+    fn remove_user_data_ref(&self, from_addr: u64, to_addr: u64) {
+        unsafe { BNRemoveUserDataReference(self.as_ref().handle, from_addr, to_addr) }
+    }
+
     fn segments(&self) -> Array<Segment> {
         unsafe {
             let mut count = 0;
@@ -568,6 +1390,17 @@ pub trait BinaryViewExt: BinaryViewBase {
         }
     }
 
+    /// List of functions with a basic block containing `addr`
+    fn functions_containing(&self, addr: u64) -> Array<Function> {
+        unsafe {
+            let mut count = 0;
+            let functions =
+                BNGetAnalysisFunctionsContainingAddress(self.as_ref().handle, addr, &mut count);
+
+            Array::new(functions, count, ())
+        }
+    }
+
     fn function_at(&self, platform: &Platform, addr: u64) -> Result<Ref<Function>> {
         unsafe {
             let handle = BNGetAnalysisFunction(self.as_ref().handle, platform.handle, addr);
@@ -667,10 +1500,215 @@ pub trait BinaryViewExt: BinaryViewBase {
             )
         };
     }
+
+    fn is_never_branch_patch_available<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        unsafe { BNIsNeverBranchPatchAvailable(self.as_ref().handle, arch.as_ref().0, addr) }
+    }
+
+    fn is_always_branch_patch_available<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        unsafe { BNIsAlwaysBranchPatchAvailable(self.as_ref().handle, arch.as_ref().0, addr) }
+    }
+
+    fn is_invert_branch_patch_available<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        unsafe { BNIsInvertBranchPatchAvailable(self.as_ref().handle, arch.as_ref().0, addr) }
+    }
+
+    fn is_skip_and_return_zero_patch_available<A: Architecture>(
+        &self,
+        arch: &A,
+        addr: u64,
+    ) -> bool {
+        unsafe { BNIsSkipAndReturnZeroPatchAvailable(self.as_ref().handle, arch.as_ref().0, addr) }
+    }
+
+    fn is_skip_and_return_value_patch_available<A: Architecture>(
+        &self,
+        arch: &A,
+        addr: u64,
+    ) -> bool {
+        unsafe { BNIsSkipAndReturnValuePatchAvailable(self.as_ref().handle, arch.as_ref().0, addr) }
+    }
+
+    /// Replaces the instruction at `addr` with no-op instructions, as a single undoable action.
+    fn convert_to_nop<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        with_undo_actions(
+            self,
+            || unsafe { BNConvertToNop(self.as_ref().handle, arch.as_ref().0, addr) },
+            |&patched| patched,
+        )
+    }
+
+    /// Makes the conditional branch at `addr` unconditional, as a single undoable action.
+    fn always_branch<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        with_undo_actions(
+            self,
+            || unsafe { BNAlwaysBranch(self.as_ref().handle, arch.as_ref().0, addr) },
+            |&patched| patched,
+        )
+    }
+
+    /// Inverts the condition of the branch at `addr`, as a single undoable action.
+    fn invert_branch<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        with_undo_actions(
+            self,
+            || unsafe { BNInvertBranch(self.as_ref().handle, arch.as_ref().0, addr) },
+            |&patched| patched,
+        )
+    }
+
+    /// Patches the call at `addr` to be skipped, returning `value` instead, as a single undoable
+    /// action.
+    fn skip_and_return_value<A: Architecture>(&self, arch: &A, addr: u64, value: u64) -> bool {
+        with_undo_actions(
+            self,
+            || unsafe { BNSkipAndReturnValue(self.as_ref().handle, arch.as_ref().0, addr, value) },
+            |&patched| patched,
+        )
+    }
+
+    /// Assembles `code` with `arch` and writes the result at `addr`, as a single undoable action.
+    ///
+    /// Returns the number of bytes written, or the errors reported by the assembler.
+    fn assemble_at<A: Architecture>(
+        &self,
+        arch: &A,
+        addr: u64,
+        code: &str,
+    ) -> result::Result<usize, String> {
+        let bytes = arch.as_ref().assemble(code, addr)?;
+
+        let written = with_undo_actions(
+            self,
+            || unsafe {
+                BNWriteViewData(
+                    self.as_ref().handle,
+                    addr,
+                    bytes.as_ptr() as *const _,
+                    bytes.len(),
+                )
+            },
+            |&written| written == bytes.len(),
+        );
+
+        if written != bytes.len() {
+            return Err(format!(
+                "Only wrote {} of {} bytes at 0x{:x}",
+                written,
+                bytes.len(),
+                addr
+            ));
+        }
+
+        Ok(written)
+    }
+
+    /// Returns the address ranges whose contents differ from the original file, in ascending order.
+    fn modified_ranges(&self) -> Vec<ops::Range<u64>> {
+        const CHUNK_SIZE: usize = 0x1_0000;
+
+        let segments = self.segments();
+        let mut regions: Vec<ops::Range<u64>> = if segments.len() == 0 {
+            iter::once(self.start()..self.end()).collect()
+        } else {
+            segments.iter().map(|s| s.address_range()).collect()
+        };
+        regions.sort_by_key(|r| r.start);
+
+        // segments may overlap; scan each address once, in order
+        let mut merged: Vec<ops::Range<u64>> = Vec::with_capacity(regions.len());
+        for region in regions {
+            match merged.last_mut() {
+                Some(last) if last.end >= region.start => last.end = last.end.max(region.end),
+                _ => merged.push(region),
+            }
+        }
+
+        let mut status = vec![ModificationStatus::Original; CHUNK_SIZE];
+        let mut ranges: Vec<ops::Range<u64>> = Vec::new();
+
+        for region in merged {
+            let mut offset = region.start;
+
+            while offset < region.end {
+                let len = CHUNK_SIZE.min((region.end - offset) as usize);
+                let len = unsafe {
+                    BNGetModificationArray(self.as_ref().handle, offset, status.as_mut_ptr(), len)
+                };
+
+                if len == 0 {
+                    break;
+                }
+
+                for (i, s) in status[..len].iter().enumerate() {
+                    if *s == ModificationStatus::Original {
+                        continue;
+                    }
+
+                    let addr = offset + i as u64;
+                    match ranges.last_mut() {
+                        Some(last) if last.end >= addr => last.end = last.end.max(addr + 1),
+                        _ => ranges.push(addr..addr + 1),
+                    }
+                }
+
+                offset += len as u64;
+            }
+        }
+
+        ranges
+    }
+
+    /// Writes the underlying file, including all modifications, to `filename`.
+    fn save_to_path<S: BnStrCompatible>(&self, filename: S) -> bool {
+        let filename = filename.as_bytes_with_nul();
+
+        unsafe { BNSaveToFilename(self.as_ref().handle, filename.as_ref().as_ptr() as *mut _) }
+    }
+
+    /// Writes the underlying file, including all modifications, to `file`.
+    fn save_to_accessor(&self, file: &mut FileAccessor) -> bool {
+        unsafe { BNSaveToFile(self.as_ref().handle, &mut file.api_object) }
+    }
 }
 
 impl<T: BinaryViewBase> BinaryViewExt for T {}
 
+/// Runs `f` as a single undoable action. If `succeeded` rejects the result, the action is rolled
+/// back, so a failed patch leaves no modifications behind and nothing to undo.
+fn with_undo_actions<V, F, S, R>(view: &V, f: F, succeeded: S) -> R
+where
+    V: ?Sized + BinaryViewExt,
+    F: FnOnce() -> R,
+    S: FnOnce(&R) -> bool,
+{
+    let meta = view.metadata();
+    let entries = undo_entry_count(&meta);
+
+    meta.begin_undo_actions();
+    let res = f();
+
+    // the core can't discard pending undo actions, so close the action and roll it back instead
+    meta.commit_undo_actions();
+    if !succeeded(&res) && undo_entry_count(&meta) > entries {
+        meta.undo();
+    }
+
+    res
+}
+
+fn undo_entry_count(meta: &FileMetadata) -> usize {
+    unsafe {
+        let mut count = 0;
+        let entries = BNGetUndoEntries(meta.handle, &mut count);
+
+        if !entries.is_null() {
+            BNFreeUndoEntries(entries, count);
+        }
+
+        count
+    }
+}
+
 #[derive(PartialEq, Eq, Hash)]
 pub struct BinaryView {
     pub(crate) handle: *mut BNBinaryView,
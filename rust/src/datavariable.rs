@@ -1 +1,114 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
 
+//! Typed data defined at an address in a `BinaryView`.
+//!
+//! Data variables are created and looked up through `BinaryViewExt`; see
+//! `define_user_data_var`, `data_var_at` and friends.
+
+use binaryninjacore_sys::*;
+
+use std::fmt;
+use std::mem;
+
+use crate::binaryview::{self, BinaryViewBase};
+use crate::rc::*;
+use crate::typedvalue::TypedValue;
+use crate::types::{Conf, Type};
+
+#[repr(transparent)]
+pub struct DataVariable(pub(crate) BNDataVariable);
+
+impl DataVariable {
+    /// Takes ownership of the type reference held by `var`.
+    pub(crate) unsafe fn from_raw(var: BNDataVariable) -> Self {
+        DataVariable(var)
+    }
+
+    /// Creates a `DataVariable` holding its own reference to the type in `var`.
+    pub(crate) unsafe fn from_borrowed_raw(var: &BNDataVariable) -> Self {
+        DataVariable(BNDataVariable {
+            type_: BNNewTypeReference(var.type_),
+            ..*var
+        })
+    }
+
+    pub fn address(&self) -> u64 {
+        self.0.address
+    }
+
+    pub fn type_with_confidence(&self) -> Conf<Ref<Type>> {
+        Conf::new(
+            Type {
+                handle: self.0.type_,
+            }
+            .to_owned(),
+            self.0.typeConfidence,
+        )
+    }
+
+    /// Returns `true` if this variable was defined by analysis rather than by the user.
+    pub fn auto_discovered(&self) -> bool {
+        self.0.autoDiscovered
+    }
+
+    /// Reads the current value of this variable out of `view`.
+    pub fn value<V: BinaryViewBase + ?Sized>(&self, view: &V) -> binaryview::Result<TypedValue> {
+        let ty = Type {
+            handle: self.0.type_,
+        };
+
+        TypedValue::read(view, self.address(), &ty)
+    }
+}
+
+impl Clone for DataVariable {
+    fn clone(&self) -> Self {
+        unsafe { Self::from_borrowed_raw(&self.0) }
+    }
+}
+
+impl Drop for DataVariable {
+    fn drop(&mut self) {
+        unsafe {
+            BNFreeType(self.0.type_);
+        }
+    }
+}
+
+impl fmt::Debug for DataVariable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DataVariable")
+            .field("address", &format_args!("{:#x}", self.address()))
+            .field("auto_discovered", &self.auto_discovered())
+            .finish()
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for DataVariable {
+    type Raw = BNDataVariable;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeDataVariables(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for DataVariable {
+    type Wrapped = &'a DataVariable;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped {
+        mem::transmute(raw)
+    }
+}
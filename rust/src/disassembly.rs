@@ -16,7 +16,8 @@
 
 use binaryninjacore_sys::*;
 
-use crate::string::BnString;
+use crate::rc::*;
+use crate::string::{BnStr, BnString};
 use crate::{BN_FULL_CONFIDENCE, BN_INVALID_EXPR};
 
 use std::convert::From;
@@ -26,6 +27,7 @@ use std::ptr;
 pub type InstructionTextTokenType = BNInstructionTextTokenType;
 pub type InstructionTextTokenContext = BNInstructionTextTokenContext;
 
+#[repr(transparent)]
 pub struct InstructionTextToken(pub(crate) BNInstructionTextToken);
 
 // TODO : Consider remodeling this after types::EnumerationMember
@@ -59,6 +61,39 @@ impl InstructionTextToken {
     pub fn set_context(&mut self, context: InstructionTextTokenContext) {
         self.0.context = context;
     }
+
+    pub fn token_type(&self) -> InstructionTextTokenType {
+        self.0.type_
+    }
+
+    pub fn text(&self) -> &BnStr {
+        unsafe { BnStr::from_raw(self.0.text) }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0.value
+    }
+
+    pub fn context(&self) -> InstructionTextTokenContext {
+        self.0.context
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for InstructionTextToken {
+    type Raw = BNInstructionTextToken;
+    type Context = ();
+
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeInstructionText(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for InstructionTextToken {
+    type Wrapped = &'a InstructionTextToken;
+
+    unsafe fn wrap_raw(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped {
+        mem::transmute(raw)
+    }
 }
 
 impl Default for InstructionTextToken {
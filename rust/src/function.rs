@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::fmt;
+use std::ops;
 
 use binaryninjacore_sys::*;
 
@@ -21,9 +22,12 @@ use crate::basicblock::{BasicBlock, BlockContext};
 use crate::binaryview::{BinaryView, BinaryViewExt};
 use crate::platform::Platform;
 use crate::symbol::Symbol;
+use crate::tags::{Tag, TagReference, TagType};
 use crate::types::Type;
 
+use crate::hlil;
 use crate::llil;
+use crate::mlil;
 
 use crate::rc::*;
 use crate::string::*;
@@ -230,11 +234,227 @@ impl Function {
         }
     }
 
+    pub fn medium_level_il(&self) -> Result<Ref<mlil::RegularFunction<CoreArchitecture>>, ()> {
+        unsafe {
+            let mlil = BNGetFunctionMediumLevelIL(self.handle);
+
+            if mlil.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(mlil::RegularFunction::from_raw(self.arch(), mlil)))
+        }
+    }
+
+    pub fn high_level_il(&self) -> Result<Ref<hlil::RegularFunction<CoreArchitecture>>, ()> {
+        unsafe {
+            let hlil = BNGetFunctionHighLevelIL(self.handle);
+
+            if hlil.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(hlil::RegularFunction::from_raw(self.arch(), hlil)))
+        }
+    }
+
     pub fn set_user_type(&self, t: Type) {
         unsafe {
             BNSetFunctionUserType(self.handle, t.handle);
         }
     }
+
+    /// Adds a reference from the instruction at `from_addr` to `to_addr`, which shows up in
+    /// `BinaryViewExt::code_refs_to` like any reference found by analysis.
+    pub fn add_user_code_ref(&self, from_arch: &CoreArchitecture, from_addr: u64, to_addr: u64) {
+        unsafe { BNAddUserCodeReference(self.handle, from_arch.0, from_addr, to_addr) }
+    }
+
+    pub fn remove_user_code_ref(&self, from_arch: &CoreArchitecture, from_addr: u64, to_addr: u64) {
+        unsafe { BNRemoveUserCodeReference(self.handle, from_arch.0, from_addr, to_addr) }
+    }
+
+    pub fn add_auto_address_tag(&self, arch: &CoreArchitecture, addr: u64, tag: &Tag) {
+        unsafe { BNAddAutoAddressTag(self.handle, arch.0, addr, tag.handle) }
+    }
+
+    pub fn remove_auto_address_tag(&self, arch: &CoreArchitecture, addr: u64, tag: &Tag) {
+        unsafe { BNRemoveAutoAddressTag(self.handle, arch.0, addr, tag.handle) }
+    }
+
+    pub fn remove_auto_address_tags_of_type(
+        &self,
+        arch: &CoreArchitecture,
+        addr: u64,
+        tag_type: &TagType,
+    ) {
+        unsafe { BNRemoveAutoAddressTagsOfType(self.handle, arch.0, addr, tag_type.handle) }
+    }
+
+    pub fn add_user_address_tag(&self, arch: &CoreArchitecture, addr: u64, tag: &Tag) {
+        unsafe { BNAddUserAddressTag(self.handle, arch.0, addr, tag.handle) }
+    }
+
+    pub fn remove_user_address_tag(&self, arch: &CoreArchitecture, addr: u64, tag: &Tag) {
+        unsafe { BNRemoveUserAddressTag(self.handle, arch.0, addr, tag.handle) }
+    }
+
+    pub fn remove_user_address_tags_of_type(
+        &self,
+        arch: &CoreArchitecture,
+        addr: u64,
+        tag_type: &TagType,
+    ) {
+        unsafe { BNRemoveUserAddressTagsOfType(self.handle, arch.0, addr, tag_type.handle) }
+    }
+
+    /// Creates a tag in this function's view and places it on `addr`.
+    pub fn create_auto_address_tag<S: BnStrCompatible>(
+        &self,
+        arch: &CoreArchitecture,
+        addr: u64,
+        tag_type: &TagType,
+        data: S,
+    ) -> Ref<Tag> {
+        let tag = self.view().create_tag(tag_type, data, false);
+        self.add_auto_address_tag(arch, addr, &tag);
+
+        tag
+    }
+
+    /// Creates a tag in this function's view and places it on `addr`.
+    pub fn create_user_address_tag<S: BnStrCompatible>(
+        &self,
+        arch: &CoreArchitecture,
+        addr: u64,
+        tag_type: &TagType,
+        data: S,
+    ) -> Ref<Tag> {
+        let tag = self.view().create_tag(tag_type, data, true);
+        self.add_user_address_tag(arch, addr, &tag);
+
+        tag
+    }
+
+    /// Tags on the instruction at `addr`, both auto and user.
+    pub fn address_tags_at(&self, arch: &CoreArchitecture, addr: u64) -> Array<Tag> {
+        unsafe {
+            let mut count = 0;
+            let tags = BNGetAddressTags(self.handle, arch.0, addr, &mut count);
+
+            Array::new(tags, count, ())
+        }
+    }
+
+    pub fn address_tags_in_range(
+        &self,
+        arch: &CoreArchitecture,
+        range: ops::Range<u64>,
+    ) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs =
+                BNGetAddressTagsInRange(self.handle, arch.0, range.start, range.end, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    pub fn address_tag_references(&self) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetAddressTagReferences(self.handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    pub fn add_auto_function_tag(&self, tag: &Tag) {
+        unsafe { BNAddAutoFunctionTag(self.handle, tag.handle) }
+    }
+
+    pub fn remove_auto_function_tag(&self, tag: &Tag) {
+        unsafe { BNRemoveAutoFunctionTag(self.handle, tag.handle) }
+    }
+
+    pub fn remove_auto_function_tags_of_type(&self, tag_type: &TagType) {
+        unsafe { BNRemoveAutoFunctionTagsOfType(self.handle, tag_type.handle) }
+    }
+
+    pub fn add_user_function_tag(&self, tag: &Tag) {
+        unsafe { BNAddUserFunctionTag(self.handle, tag.handle) }
+    }
+
+    pub fn remove_user_function_tag(&self, tag: &Tag) {
+        unsafe { BNRemoveUserFunctionTag(self.handle, tag.handle) }
+    }
+
+    pub fn remove_user_function_tags_of_type(&self, tag_type: &TagType) {
+        unsafe { BNRemoveUserFunctionTagsOfType(self.handle, tag_type.handle) }
+    }
+
+    /// Creates a tag in this function's view and places it on the function.
+    pub fn create_auto_function_tag<S: BnStrCompatible>(
+        &self,
+        tag_type: &TagType,
+        data: S,
+    ) -> Ref<Tag> {
+        let tag = self.view().create_tag(tag_type, data, false);
+        self.add_auto_function_tag(&tag);
+
+        tag
+    }
+
+    /// Creates a tag in this function's view and places it on the function.
+    pub fn create_user_function_tag<S: BnStrCompatible>(
+        &self,
+        tag_type: &TagType,
+        data: S,
+    ) -> Ref<Tag> {
+        let tag = self.view().create_tag(tag_type, data, true);
+        self.add_user_function_tag(&tag);
+
+        tag
+    }
+
+    /// Tags on the function itself, both auto and user.
+    pub fn function_tags(&self) -> Array<Tag> {
+        unsafe {
+            let mut count = 0;
+            let tags = BNGetFunctionTags(self.handle, &mut count);
+
+            Array::new(tags, count, ())
+        }
+    }
+
+    pub fn function_tag_references(&self) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetFunctionTagReferences(self.handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Every address and function tag in this function.
+    pub fn all_tag_references(&self) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetFunctionAllTagReferences(self.handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
+
+    /// Every address and function tag of type `tag_type` in this function.
+    pub fn tag_references_of_type(&self, tag_type: &TagType) -> Array<TagReference> {
+        unsafe {
+            let mut count = 0;
+            let refs = BNGetFunctionTagReferencesOfType(self.handle, tag_type.handle, &mut count);
+
+            Array::new(refs, count, ())
+        }
+    }
 }
 
 impl fmt::Debug for Function {
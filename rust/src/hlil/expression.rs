@@ -0,0 +1,1001 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNGetHighLevelILByIndex;
+use binaryninjacore_sys::BNHighLevelILInstruction;
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::operation;
+use super::operation::Operation;
+use super::*;
+
+use crate::architecture::Architecture;
+use crate::rc::Ref;
+use crate::types::{Conf, Type};
+
+pub use crate::llil::{ExpressionResultType, ValueExpr, VoidExpr};
+
+pub struct Expression<'func, A, M, F, R>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+    R: ExpressionResultType,
+{
+    pub(crate) function: &'func Function<A, M, F>,
+    pub(crate) expr_idx: usize,
+
+    // tag the 'return' type of this expression
+    pub(crate) _ty: PhantomData<R>,
+}
+
+impl<'func, A, M, F, R> Expression<'func, A, M, F, R>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+    R: ExpressionResultType,
+{
+    pub(crate) fn new(function: &'func Function<A, M, F>, expr_idx: usize) -> Self {
+        Self {
+            function,
+            expr_idx,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.expr_idx
+    }
+
+    pub(crate) fn raw_op(&self) -> BNHighLevelILInstruction {
+        unsafe { BNGetHighLevelILByIndex(self.function.handle, self.expr_idx, true) }
+    }
+
+    pub fn address(&self) -> u64 {
+        self.raw_op().address
+    }
+
+    /// Returns the expression this one is an operand of
+    ///
+    /// The root of the function's syntax tree has no parent.
+    pub fn parent(&self) -> Option<Expression<'func, A, M, F, ValueExpr>> {
+        match self.raw_op().parent {
+            usize::MAX => None,
+            parent => Some(Expression::new(self.function, parent)),
+        }
+    }
+}
+
+impl<'func, A, M> fmt::Debug for Expression<'func, A, M, NonSSA, ValueExpr>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op_info = self.info();
+        write!(f, "<expr {}: {:?}>", self.expr_idx, op_info)
+    }
+}
+
+fn common_info<'func, A, M, F>(
+    function: &'func Function<A, M, F>,
+    expr_idx: usize,
+    op: BNHighLevelILInstruction,
+) -> ExprInfo<'func, A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    use binaryninjacore_sys::BNHighLevelILOperation::*;
+
+    match op.operation {
+        HLIL_NOP => ExprInfo::Nop(Operation::new(function, expr_idx, op)),
+        HLIL_BLOCK => ExprInfo::Block(Operation::new(function, expr_idx, op)),
+        HLIL_IF => ExprInfo::If(Operation::new(function, expr_idx, op)),
+        HLIL_SWITCH => ExprInfo::Switch(Operation::new(function, expr_idx, op)),
+        HLIL_CASE => ExprInfo::Case(Operation::new(function, expr_idx, op)),
+        HLIL_BREAK => ExprInfo::Break(Operation::new(function, expr_idx, op)),
+        HLIL_CONTINUE => ExprInfo::Continue(Operation::new(function, expr_idx, op)),
+        HLIL_JUMP => ExprInfo::Jump(Operation::new(function, expr_idx, op)),
+        HLIL_RET => ExprInfo::Ret(Operation::new(function, expr_idx, op)),
+        HLIL_NORET => ExprInfo::NoRet(Operation::new(function, expr_idx, op)),
+        HLIL_GOTO => ExprInfo::Goto(Operation::new(function, expr_idx, op)),
+        HLIL_LABEL => ExprInfo::Label(Operation::new(function, expr_idx, op)),
+
+        HLIL_VAR_DECLARE => ExprInfo::VarDeclare(Operation::new(function, expr_idx, op)),
+
+        HLIL_STRUCT_FIELD => ExprInfo::StructField(Operation::new(function, expr_idx, op)),
+        HLIL_SPLIT => ExprInfo::Split(Operation::new(function, expr_idx, op)),
+        HLIL_ADDRESS_OF => ExprInfo::AddressOf(Operation::new(function, expr_idx, op)),
+
+        HLIL_CONST => ExprInfo::Const(Operation::new(function, expr_idx, op)),
+        HLIL_CONST_PTR => ExprInfo::ConstPtr(Operation::new(function, expr_idx, op)),
+        HLIL_EXTERN_PTR => ExprInfo::ExternPtr(Operation::new(function, expr_idx, op)),
+        HLIL_FLOAT_CONST => ExprInfo::FloatConst(Operation::new(function, expr_idx, op)),
+        HLIL_IMPORT => ExprInfo::Import(Operation::new(function, expr_idx, op)),
+
+        HLIL_ADD => ExprInfo::Add(Operation::new(function, expr_idx, op)),
+        HLIL_ADC => ExprInfo::Adc(Operation::new(function, expr_idx, op)),
+        HLIL_SUB => ExprInfo::Sub(Operation::new(function, expr_idx, op)),
+        HLIL_SBB => ExprInfo::Sbb(Operation::new(function, expr_idx, op)),
+        HLIL_AND => ExprInfo::And(Operation::new(function, expr_idx, op)),
+        HLIL_OR => ExprInfo::Or(Operation::new(function, expr_idx, op)),
+        HLIL_XOR => ExprInfo::Xor(Operation::new(function, expr_idx, op)),
+        HLIL_LSL => ExprInfo::Lsl(Operation::new(function, expr_idx, op)),
+        HLIL_LSR => ExprInfo::Lsr(Operation::new(function, expr_idx, op)),
+        HLIL_ASR => ExprInfo::Asr(Operation::new(function, expr_idx, op)),
+        HLIL_ROL => ExprInfo::Rol(Operation::new(function, expr_idx, op)),
+        HLIL_RLC => ExprInfo::Rlc(Operation::new(function, expr_idx, op)),
+        HLIL_ROR => ExprInfo::Ror(Operation::new(function, expr_idx, op)),
+        HLIL_RRC => ExprInfo::Rrc(Operation::new(function, expr_idx, op)),
+        HLIL_MUL => ExprInfo::Mul(Operation::new(function, expr_idx, op)),
+
+        HLIL_MULU_DP => ExprInfo::MuluDp(Operation::new(function, expr_idx, op)),
+        HLIL_MULS_DP => ExprInfo::MulsDp(Operation::new(function, expr_idx, op)),
+
+        HLIL_DIVU => ExprInfo::Divu(Operation::new(function, expr_idx, op)),
+        HLIL_DIVS => ExprInfo::Divs(Operation::new(function, expr_idx, op)),
+
+        HLIL_DIVU_DP => ExprInfo::DivuDp(Operation::new(function, expr_idx, op)),
+        HLIL_DIVS_DP => ExprInfo::DivsDp(Operation::new(function, expr_idx, op)),
+
+        HLIL_MODU => ExprInfo::Modu(Operation::new(function, expr_idx, op)),
+        HLIL_MODS => ExprInfo::Mods(Operation::new(function, expr_idx, op)),
+
+        HLIL_MODU_DP => ExprInfo::ModuDp(Operation::new(function, expr_idx, op)),
+        HLIL_MODS_DP => ExprInfo::ModsDp(Operation::new(function, expr_idx, op)),
+
+        HLIL_NEG => ExprInfo::Neg(Operation::new(function, expr_idx, op)),
+        HLIL_NOT => ExprInfo::Not(Operation::new(function, expr_idx, op)),
+
+        HLIL_SX => ExprInfo::Sx(Operation::new(function, expr_idx, op)),
+        HLIL_ZX => ExprInfo::Zx(Operation::new(function, expr_idx, op)),
+        HLIL_LOW_PART => ExprInfo::LowPart(Operation::new(function, expr_idx, op)),
+
+        HLIL_CMP_E => ExprInfo::CmpE(Operation::new(function, expr_idx, op)),
+        HLIL_CMP_NE => ExprInfo::CmpNe(Operation::new(function, expr_idx, op)),
+        HLIL_CMP_SLT => ExprInfo::CmpSlt(Operation::new(function, expr_idx, op)),
+        HLIL_CMP_ULT => ExprInfo::CmpUlt(Operation::new(function, expr_idx, op)),
+        HLIL_CMP_SLE => ExprInfo::CmpSle(Operation::new(function, expr_idx, op)),
+        HLIL_CMP_ULE => ExprInfo::CmpUle(Operation::new(function, expr_idx, op)),
+        HLIL_CMP_SGE => ExprInfo::CmpSge(Operation::new(function, expr_idx, op)),
+        HLIL_CMP_UGE => ExprInfo::CmpUge(Operation::new(function, expr_idx, op)),
+        HLIL_CMP_SGT => ExprInfo::CmpSgt(Operation::new(function, expr_idx, op)),
+        HLIL_CMP_UGT => ExprInfo::CmpUgt(Operation::new(function, expr_idx, op)),
+
+        HLIL_TEST_BIT => ExprInfo::TestBit(Operation::new(function, expr_idx, op)),
+        HLIL_BOOL_TO_INT => ExprInfo::BoolToInt(Operation::new(function, expr_idx, op)),
+        HLIL_ADD_OVERFLOW => ExprInfo::AddOverflow(Operation::new(function, expr_idx, op)),
+
+        HLIL_TAILCALL => ExprInfo::Tailcall(Operation::new(function, expr_idx, op)),
+        HLIL_BP => ExprInfo::Bp(Operation::new(function, expr_idx, op)),
+        HLIL_TRAP => ExprInfo::Trap(Operation::new(function, expr_idx, op)),
+
+        HLIL_FADD => ExprInfo::Fadd(Operation::new(function, expr_idx, op)),
+        HLIL_FSUB => ExprInfo::Fsub(Operation::new(function, expr_idx, op)),
+        HLIL_FMUL => ExprInfo::Fmul(Operation::new(function, expr_idx, op)),
+        HLIL_FDIV => ExprInfo::Fdiv(Operation::new(function, expr_idx, op)),
+
+        HLIL_FSQRT => ExprInfo::Fsqrt(Operation::new(function, expr_idx, op)),
+        HLIL_FNEG => ExprInfo::Fneg(Operation::new(function, expr_idx, op)),
+        HLIL_FABS => ExprInfo::Fabs(Operation::new(function, expr_idx, op)),
+        HLIL_FLOAT_TO_INT => ExprInfo::FloatToInt(Operation::new(function, expr_idx, op)),
+        HLIL_INT_TO_FLOAT => ExprInfo::IntToFloat(Operation::new(function, expr_idx, op)),
+        HLIL_FLOAT_CONV => ExprInfo::FloatConv(Operation::new(function, expr_idx, op)),
+        HLIL_ROUND_TO_INT => ExprInfo::RoundToInt(Operation::new(function, expr_idx, op)),
+        HLIL_FLOOR => ExprInfo::Floor(Operation::new(function, expr_idx, op)),
+        HLIL_CEIL => ExprInfo::Ceil(Operation::new(function, expr_idx, op)),
+        HLIL_FTRUNC => ExprInfo::Ftrunc(Operation::new(function, expr_idx, op)),
+
+        HLIL_FCMP_E => ExprInfo::FcmpE(Operation::new(function, expr_idx, op)),
+        HLIL_FCMP_NE => ExprInfo::FcmpNe(Operation::new(function, expr_idx, op)),
+        HLIL_FCMP_LT => ExprInfo::FcmpLt(Operation::new(function, expr_idx, op)),
+        HLIL_FCMP_LE => ExprInfo::FcmpLe(Operation::new(function, expr_idx, op)),
+        HLIL_FCMP_GE => ExprInfo::FcmpGe(Operation::new(function, expr_idx, op)),
+        HLIL_FCMP_GT => ExprInfo::FcmpGt(Operation::new(function, expr_idx, op)),
+        HLIL_FCMP_O => ExprInfo::FcmpO(Operation::new(function, expr_idx, op)),
+        HLIL_FCMP_UO => ExprInfo::FcmpUo(Operation::new(function, expr_idx, op)),
+
+        HLIL_UNIMPL => ExprInfo::Unimpl(Operation::new(function, expr_idx, op)),
+        HLIL_UNIMPL_MEM => ExprInfo::UnimplMem(Operation::new(function, expr_idx, op)),
+        HLIL_UNDEF => ExprInfo::Undef(Operation::new(function, expr_idx, op)),
+
+        _ => {
+            #[cfg(debug_assertions)]
+            {
+                error!(
+                    "Got unexpected operation {:?} in expr at 0x{:x}",
+                    op.operation, op.address
+                );
+            }
+
+            ExprInfo::Undef(Operation::new(function, expr_idx, op))
+        }
+    }
+}
+
+use super::VisitorAction;
+
+macro_rules! visit {
+    ($f:expr, $($e:expr),*) => {
+        if let VisitorAction::Halt = $f($($e,)*) {
+            return VisitorAction::Halt;
+        }
+    }
+}
+
+fn common_visit<'func, A, M, F, CB>(info: &ExprInfo<'func, A, M, F>, f: &mut CB) -> VisitorAction
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+    CB: FnMut(&Expression<'func, A, M, F, ValueExpr>) -> VisitorAction,
+{
+    use self::ExprInfo::*;
+
+    if let Some(op) = info.as_cmp_op() {
+        visit!(f, &op.left());
+        visit!(f, &op.right());
+    } else if let Some(op) = info.as_binary_op() {
+        visit!(f, &op.left());
+        visit!(f, &op.right());
+    } else if let Some(op) = info.as_binary_op_carry() {
+        visit!(f, &op.left());
+        visit!(f, &op.right());
+        visit!(f, &op.carry());
+    } else if let Some(op) = info.as_unary_op() {
+        visit!(f, &op.operand());
+    } else {
+        match *info {
+            Block(ref op) => {
+                for expr in op.body() {
+                    visit!(f, &expr);
+                }
+            }
+            If(ref op) => {
+                visit!(f, &op.condition());
+                visit!(f, &op.true_body());
+                visit!(f, &op.false_body());
+            }
+            Switch(ref op) => {
+                visit!(f, &op.condition());
+                for case in op.cases() {
+                    visit!(f, &case);
+                }
+                visit!(f, &op.default());
+            }
+            Case(ref op) => {
+                for value in op.values() {
+                    visit!(f, &value);
+                }
+                visit!(f, &op.body());
+            }
+            Jump(ref op) => visit!(f, &op.target()),
+            Ret(ref op) => {
+                for expr in op.source_exprs() {
+                    visit!(f, &expr);
+                }
+            }
+            StructField(ref op) => visit!(f, &op.source_expr()),
+            Split(ref op) => {
+                visit!(f, &op.high());
+                visit!(f, &op.low());
+            }
+            Deref(ref op) => visit!(f, &op.source_expr()),
+            Call(ref op) => {
+                visit!(f, &op.target());
+                for param in op.params() {
+                    visit!(f, &param);
+                }
+            }
+            Tailcall(ref op) => {
+                visit!(f, &op.target());
+                for param in op.params() {
+                    visit!(f, &param);
+                }
+            }
+            Syscall(ref op) => {
+                for param in op.params() {
+                    visit!(f, &param);
+                }
+            }
+            Intrinsic(ref op) => {
+                for param in op.params() {
+                    visit!(f, &param);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    VisitorAction::Sibling
+}
+
+impl<'func, A, M> Expression<'func, A, M, NonSSA, ValueExpr>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub(crate) unsafe fn info_from_op(
+        &self,
+        op: BNHighLevelILInstruction,
+    ) -> ExprInfo<'func, A, M, NonSSA> {
+        use binaryninjacore_sys::BNHighLevelILOperation::*;
+
+        let expr_idx = self.expr_idx;
+
+        match op.operation {
+            HLIL_WHILE => ExprInfo::While(Operation::new(self.function, expr_idx, op)),
+            HLIL_DO_WHILE => ExprInfo::DoWhile(Operation::new(self.function, expr_idx, op)),
+            HLIL_FOR => ExprInfo::For(Operation::new(self.function, expr_idx, op)),
+            HLIL_VAR_INIT => ExprInfo::VarInit(Operation::new(self.function, expr_idx, op)),
+            HLIL_ASSIGN => ExprInfo::Assign(Operation::new(self.function, expr_idx, op)),
+            HLIL_ASSIGN_UNPACK => {
+                ExprInfo::AssignUnpack(Operation::new(self.function, expr_idx, op))
+            }
+            HLIL_VAR => ExprInfo::Var(Operation::new(self.function, expr_idx, op)),
+            HLIL_ARRAY_INDEX => ExprInfo::ArrayIndex(Operation::new(self.function, expr_idx, op)),
+            HLIL_DEREF => ExprInfo::Deref(Operation::new(self.function, expr_idx, op)),
+            HLIL_DEREF_FIELD => ExprInfo::DerefField(Operation::new(self.function, expr_idx, op)),
+            HLIL_CALL => ExprInfo::Call(Operation::new(self.function, expr_idx, op)),
+            HLIL_SYSCALL => ExprInfo::Syscall(Operation::new(self.function, expr_idx, op)),
+            HLIL_INTRINSIC => ExprInfo::Intrinsic(Operation::new(self.function, expr_idx, op)),
+            _ => common_info(self.function, expr_idx, op),
+        }
+    }
+
+    pub fn info(&self) -> ExprInfo<'func, A, M, NonSSA> {
+        unsafe { self.info_from_op(self.raw_op()) }
+    }
+
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(&Self, &ExprInfo<'func, A, M, NonSSA>) -> VisitorAction,
+    {
+        use self::ExprInfo::*;
+
+        let info = self.info();
+
+        match f(self, &info) {
+            VisitorAction::Descend => {}
+            action => return action,
+        };
+
+        match info {
+            While(ref op) => {
+                visit!(Self::visit_tree, &op.condition(), f);
+                visit!(Self::visit_tree, &op.body(), f);
+            }
+            DoWhile(ref op) => {
+                visit!(Self::visit_tree, &op.body(), f);
+                visit!(Self::visit_tree, &op.condition(), f);
+            }
+            For(ref op) => {
+                visit!(Self::visit_tree, &op.init(), f);
+                visit!(Self::visit_tree, &op.condition(), f);
+                visit!(Self::visit_tree, &op.update(), f);
+                visit!(Self::visit_tree, &op.body(), f);
+            }
+            VarInit(ref op) => visit!(Self::visit_tree, &op.source_expr(), f),
+            Assign(ref op) => {
+                visit!(Self::visit_tree, &op.dest_expr(), f);
+                visit!(Self::visit_tree, &op.source_expr(), f);
+            }
+            AssignUnpack(ref op) => {
+                for dest in op.dest_exprs() {
+                    visit!(Self::visit_tree, &dest, f);
+                }
+                visit!(Self::visit_tree, &op.source_expr(), f);
+            }
+            ArrayIndex(ref op) => {
+                visit!(Self::visit_tree, &op.source_expr(), f);
+                visit!(Self::visit_tree, &op.index(), f);
+            }
+            DerefField(ref op) => visit!(Self::visit_tree, &op.source_expr(), f),
+            _ => {
+                let mut fb = |e: &Self| e.visit_tree(f);
+                visit!(common_visit, &info, &mut fb);
+            }
+        };
+
+        VisitorAction::Sibling
+    }
+}
+
+impl<'func, A, M> Expression<'func, A, M, SSA, ValueExpr>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub(crate) unsafe fn info_from_op(
+        &self,
+        op: BNHighLevelILInstruction,
+    ) -> ExprInfo<'func, A, M, SSA> {
+        use binaryninjacore_sys::BNHighLevelILOperation::*;
+
+        let expr_idx = self.expr_idx;
+
+        match op.operation {
+            HLIL_WHILE_SSA => ExprInfo::While(Operation::new(self.function, expr_idx, op)),
+            HLIL_DO_WHILE_SSA => ExprInfo::DoWhile(Operation::new(self.function, expr_idx, op)),
+            HLIL_FOR_SSA => ExprInfo::For(Operation::new(self.function, expr_idx, op)),
+            HLIL_VAR_INIT_SSA => ExprInfo::VarInit(Operation::new(self.function, expr_idx, op)),
+            HLIL_ASSIGN => ExprInfo::Assign(Operation::new(self.function, expr_idx, op)),
+            HLIL_ASSIGN_UNPACK => {
+                ExprInfo::AssignUnpack(Operation::new(self.function, expr_idx, op))
+            }
+            HLIL_ASSIGN_MEM_SSA => ExprInfo::AssignMem(Operation::new(self.function, expr_idx, op)),
+            HLIL_ASSIGN_UNPACK_MEM_SSA => {
+                ExprInfo::AssignUnpackMem(Operation::new(self.function, expr_idx, op))
+            }
+            HLIL_VAR_SSA => ExprInfo::Var(Operation::new(self.function, expr_idx, op)),
+            HLIL_ARRAY_INDEX_SSA => {
+                ExprInfo::ArrayIndex(Operation::new(self.function, expr_idx, op))
+            }
+            HLIL_DEREF_SSA => ExprInfo::Deref(Operation::new(self.function, expr_idx, op)),
+            HLIL_DEREF_FIELD_SSA => {
+                ExprInfo::DerefField(Operation::new(self.function, expr_idx, op))
+            }
+            HLIL_CALL_SSA => ExprInfo::Call(Operation::new(self.function, expr_idx, op)),
+            HLIL_SYSCALL_SSA => ExprInfo::Syscall(Operation::new(self.function, expr_idx, op)),
+            HLIL_INTRINSIC_SSA => ExprInfo::Intrinsic(Operation::new(self.function, expr_idx, op)),
+            HLIL_VAR_PHI => ExprInfo::VarPhi(Operation::new(self.function, expr_idx, op)),
+            HLIL_MEM_PHI => ExprInfo::MemPhi(Operation::new(self.function, expr_idx, op)),
+            _ => common_info(self.function, expr_idx, op),
+        }
+    }
+
+    pub fn info(&self) -> ExprInfo<'func, A, M, SSA> {
+        unsafe { self.info_from_op(self.raw_op()) }
+    }
+
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(&Self, &ExprInfo<'func, A, M, SSA>) -> VisitorAction,
+    {
+        use self::ExprInfo::*;
+
+        let info = self.info();
+
+        match f(self, &info) {
+            VisitorAction::Descend => {}
+            action => return action,
+        };
+
+        match info {
+            While(ref op) => {
+                visit!(Self::visit_tree, &op.condition_phi(), f);
+                visit!(Self::visit_tree, &op.condition(), f);
+                visit!(Self::visit_tree, &op.body(), f);
+            }
+            DoWhile(ref op) => {
+                visit!(Self::visit_tree, &op.body(), f);
+                visit!(Self::visit_tree, &op.condition_phi(), f);
+                visit!(Self::visit_tree, &op.condition(), f);
+            }
+            For(ref op) => {
+                visit!(Self::visit_tree, &op.init(), f);
+                visit!(Self::visit_tree, &op.condition_phi(), f);
+                visit!(Self::visit_tree, &op.condition(), f);
+                visit!(Self::visit_tree, &op.update(), f);
+                visit!(Self::visit_tree, &op.body(), f);
+            }
+            VarInit(ref op) => visit!(Self::visit_tree, &op.source_expr(), f),
+            Assign(ref op) => {
+                visit!(Self::visit_tree, &op.dest_expr(), f);
+                visit!(Self::visit_tree, &op.source_expr(), f);
+            }
+            AssignUnpack(ref op) => {
+                for dest in op.dest_exprs() {
+                    visit!(Self::visit_tree, &dest, f);
+                }
+                visit!(Self::visit_tree, &op.source_expr(), f);
+            }
+            AssignMem(ref op) => {
+                visit!(Self::visit_tree, &op.dest_expr(), f);
+                visit!(Self::visit_tree, &op.source_expr(), f);
+            }
+            AssignUnpackMem(ref op) => {
+                for dest in op.dest_exprs() {
+                    visit!(Self::visit_tree, &dest, f);
+                }
+                visit!(Self::visit_tree, &op.source_expr(), f);
+            }
+            ArrayIndex(ref op) => {
+                visit!(Self::visit_tree, &op.source_expr(), f);
+                visit!(Self::visit_tree, &op.index(), f);
+            }
+            DerefField(ref op) => visit!(Self::visit_tree, &op.source_expr(), f),
+            _ => {
+                let mut fb = |e: &Self| e.visit_tree(f);
+                visit!(common_visit, &info, &mut fb);
+            }
+        };
+
+        VisitorAction::Sibling
+    }
+}
+
+impl<'func, A, F> Expression<'func, A, Finalized, F, ValueExpr>
+where
+    A: 'func + Architecture,
+    F: FunctionForm,
+{
+    /// Returns the type analysis assigned to the result of this expression
+    pub fn expr_type(&self) -> Option<Conf<Ref<Type>>> {
+        use binaryninjacore_sys::BNGetHighLevelILExprType;
+
+        let result = unsafe { BNGetHighLevelILExprType(self.function.handle, self.expr_idx) };
+
+        if result.type_.is_null() {
+            None
+        } else {
+            Some(result.into())
+        }
+    }
+}
+
+pub enum ExprInfo<'func, A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    Nop(Operation<'func, A, M, F, operation::NoArgs>),
+    Block(Operation<'func, A, M, F, operation::Block>),
+    If(Operation<'func, A, M, F, operation::If>),
+    While(Operation<'func, A, M, F, operation::While>),
+    DoWhile(Operation<'func, A, M, F, operation::DoWhile>),
+    For(Operation<'func, A, M, F, operation::For>),
+    Switch(Operation<'func, A, M, F, operation::Switch>),
+    Case(Operation<'func, A, M, F, operation::Case>),
+    Break(Operation<'func, A, M, F, operation::NoArgs>),
+    Continue(Operation<'func, A, M, F, operation::NoArgs>),
+    Jump(Operation<'func, A, M, F, operation::Jump>),
+    Ret(Operation<'func, A, M, F, operation::Ret>),
+    NoRet(Operation<'func, A, M, F, operation::NoArgs>),
+    Goto(Operation<'func, A, M, F, operation::Label>),
+    Label(Operation<'func, A, M, F, operation::Label>),
+
+    VarDeclare(Operation<'func, A, M, F, operation::VarDeclare>),
+    VarInit(Operation<'func, A, M, F, operation::VarInit>),
+    Assign(Operation<'func, A, M, F, operation::Assign>),
+    AssignUnpack(Operation<'func, A, M, F, operation::AssignUnpack>),
+    Var(Operation<'func, A, M, F, operation::Var>),
+    StructField(Operation<'func, A, M, F, operation::StructField>),
+    ArrayIndex(Operation<'func, A, M, F, operation::ArrayIndex>),
+    Split(Operation<'func, A, M, F, operation::Split>),
+    Deref(Operation<'func, A, M, F, operation::Deref>),
+    DerefField(Operation<'func, A, M, F, operation::DerefField>),
+    AddressOf(Operation<'func, A, M, F, operation::UnaryOp>),
+
+    Const(Operation<'func, A, M, F, operation::Const>),
+    ConstPtr(Operation<'func, A, M, F, operation::Const>),
+    ExternPtr(Operation<'func, A, M, F, operation::ExternPtr>),
+    FloatConst(Operation<'func, A, M, F, operation::FloatConst>),
+    Import(Operation<'func, A, M, F, operation::Const>),
+
+    Add(Operation<'func, A, M, F, operation::BinaryOp>),
+    Adc(Operation<'func, A, M, F, operation::BinaryOpCarry>),
+    Sub(Operation<'func, A, M, F, operation::BinaryOp>),
+    Sbb(Operation<'func, A, M, F, operation::BinaryOpCarry>),
+    And(Operation<'func, A, M, F, operation::BinaryOp>),
+    Or(Operation<'func, A, M, F, operation::BinaryOp>),
+    Xor(Operation<'func, A, M, F, operation::BinaryOp>),
+    Lsl(Operation<'func, A, M, F, operation::BinaryOp>),
+    Lsr(Operation<'func, A, M, F, operation::BinaryOp>),
+    Asr(Operation<'func, A, M, F, operation::BinaryOp>),
+    Rol(Operation<'func, A, M, F, operation::BinaryOp>),
+    Rlc(Operation<'func, A, M, F, operation::BinaryOpCarry>),
+    Ror(Operation<'func, A, M, F, operation::BinaryOp>),
+    Rrc(Operation<'func, A, M, F, operation::BinaryOpCarry>),
+    Mul(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    MulsDp(Operation<'func, A, M, F, operation::BinaryOp>),
+    MuluDp(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Divu(Operation<'func, A, M, F, operation::BinaryOp>),
+    Divs(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    DivuDp(Operation<'func, A, M, F, operation::BinaryOp>),
+    DivsDp(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Modu(Operation<'func, A, M, F, operation::BinaryOp>),
+    Mods(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    ModuDp(Operation<'func, A, M, F, operation::BinaryOp>),
+    ModsDp(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Neg(Operation<'func, A, M, F, operation::UnaryOp>),
+    Not(Operation<'func, A, M, F, operation::UnaryOp>),
+    Sx(Operation<'func, A, M, F, operation::UnaryOp>),
+    Zx(Operation<'func, A, M, F, operation::UnaryOp>),
+    LowPart(Operation<'func, A, M, F, operation::UnaryOp>),
+
+    Call(Operation<'func, A, M, F, operation::Call>),
+    Tailcall(Operation<'func, A, M, F, operation::Tailcall>),
+    Syscall(Operation<'func, A, M, F, operation::Syscall>),
+    Intrinsic(Operation<'func, A, M, F, operation::Intrinsic>),
+
+    CmpE(Operation<'func, A, M, F, operation::Condition>),
+    CmpNe(Operation<'func, A, M, F, operation::Condition>),
+    CmpSlt(Operation<'func, A, M, F, operation::Condition>),
+    CmpUlt(Operation<'func, A, M, F, operation::Condition>),
+    CmpSle(Operation<'func, A, M, F, operation::Condition>),
+    CmpUle(Operation<'func, A, M, F, operation::Condition>),
+    CmpSge(Operation<'func, A, M, F, operation::Condition>),
+    CmpUge(Operation<'func, A, M, F, operation::Condition>),
+    CmpSgt(Operation<'func, A, M, F, operation::Condition>),
+    CmpUgt(Operation<'func, A, M, F, operation::Condition>),
+
+    TestBit(Operation<'func, A, M, F, operation::Condition>),
+    BoolToInt(Operation<'func, A, M, F, operation::UnaryOp>),
+    AddOverflow(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Bp(Operation<'func, A, M, F, operation::NoArgs>),
+    Trap(Operation<'func, A, M, F, operation::Trap>),
+
+    Fadd(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fsub(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fmul(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fdiv(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Fsqrt(Operation<'func, A, M, F, operation::UnaryOp>),
+    Fneg(Operation<'func, A, M, F, operation::UnaryOp>),
+    Fabs(Operation<'func, A, M, F, operation::UnaryOp>),
+    FloatToInt(Operation<'func, A, M, F, operation::UnaryOp>),
+    IntToFloat(Operation<'func, A, M, F, operation::UnaryOp>),
+    FloatConv(Operation<'func, A, M, F, operation::UnaryOp>),
+    RoundToInt(Operation<'func, A, M, F, operation::UnaryOp>),
+    Floor(Operation<'func, A, M, F, operation::UnaryOp>),
+    Ceil(Operation<'func, A, M, F, operation::UnaryOp>),
+    Ftrunc(Operation<'func, A, M, F, operation::UnaryOp>),
+
+    FcmpE(Operation<'func, A, M, F, operation::Condition>),
+    FcmpNe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpLt(Operation<'func, A, M, F, operation::Condition>),
+    FcmpLe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpGe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpGt(Operation<'func, A, M, F, operation::Condition>),
+    FcmpO(Operation<'func, A, M, F, operation::Condition>),
+    FcmpUo(Operation<'func, A, M, F, operation::Condition>),
+
+    // only present in SSA form
+    AssignMem(Operation<'func, A, M, F, operation::AssignMem>),
+    AssignUnpackMem(Operation<'func, A, M, F, operation::AssignUnpackMem>),
+    VarPhi(Operation<'func, A, M, F, operation::VarPhi>),
+    MemPhi(Operation<'func, A, M, F, operation::MemPhi>),
+
+    Unimpl(Operation<'func, A, M, F, operation::NoArgs>),
+    UnimplMem(Operation<'func, A, M, F, operation::UnaryOp>),
+
+    Undef(Operation<'func, A, M, F, operation::NoArgs>),
+}
+
+impl<'func, A, M, F> ExprInfo<'func, A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    /// Returns the size of the result of this expression
+    ///
+    /// If the expression is malformed or is `Unimpl` there
+    /// is no meaningful size associated with the result.
+    pub fn size(&self) -> Option<usize> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Undef(..) | Unimpl(..) => None,
+
+            _ if self.as_cmp_op().is_some() => Some(0),
+
+            _ => Some(self.raw_struct().size),
+        }
+    }
+
+    pub fn address(&self) -> u64 {
+        self.raw_struct().address
+    }
+
+    /// Determines if the expressions represent the same operation
+    ///
+    /// It does not examine the operands for equality.
+    pub fn is_same_op_as(&self, other: &Self) -> bool {
+        self.raw_struct().operation == other.raw_struct().operation
+    }
+
+    pub fn as_cmp_op(&self) -> Option<&Operation<'func, A, M, F, operation::Condition>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
+            | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op)
+            | CmpUgt(ref op) | TestBit(ref op) | FcmpE(ref op) | FcmpNe(ref op)
+            | FcmpLt(ref op) | FcmpLe(ref op) | FcmpGe(ref op) | FcmpGt(ref op) | FcmpO(ref op)
+            | FcmpUo(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub fn as_binary_op(&self) -> Option<&Operation<'func, A, M, F, operation::BinaryOp>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
+            | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
+            | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | DivuDp(ref op)
+            | DivsDp(ref op) | Modu(ref op) | Mods(ref op) | ModuDp(ref op) | ModsDp(ref op)
+            | AddOverflow(ref op) | Fadd(ref op) | Fsub(ref op) | Fmul(ref op) | Fdiv(ref op) => {
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_binary_op_carry(
+        &self,
+    ) -> Option<&Operation<'func, A, M, F, operation::BinaryOpCarry>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Adc(ref op) | Sbb(ref op) | Rlc(ref op) | Rrc(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub fn as_unary_op(&self) -> Option<&Operation<'func, A, M, F, operation::UnaryOp>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            AddressOf(ref op) | Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op)
+            | LowPart(ref op) | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op)
+            | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op)
+            | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op) | UnimplMem(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn raw_struct(&self) -> &BNHighLevelILInstruction {
+        use self::ExprInfo::*;
+
+        if let Some(op) = self.as_cmp_op() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_binary_op() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_binary_op_carry() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_unary_op() {
+            return &op.op;
+        }
+
+        match *self {
+            Nop(ref op) | Break(ref op) | Continue(ref op) | NoRet(ref op) | Bp(ref op)
+            | Unimpl(ref op) | Undef(ref op) => &op.op,
+            Block(ref op) => &op.op,
+            If(ref op) => &op.op,
+            While(ref op) => &op.op,
+            DoWhile(ref op) => &op.op,
+            For(ref op) => &op.op,
+            Switch(ref op) => &op.op,
+            Case(ref op) => &op.op,
+            Jump(ref op) => &op.op,
+            Ret(ref op) => &op.op,
+            Goto(ref op) | Label(ref op) => &op.op,
+
+            VarDeclare(ref op) => &op.op,
+            VarInit(ref op) => &op.op,
+            Assign(ref op) => &op.op,
+            AssignUnpack(ref op) => &op.op,
+            Var(ref op) => &op.op,
+            StructField(ref op) => &op.op,
+            ArrayIndex(ref op) => &op.op,
+            Split(ref op) => &op.op,
+            Deref(ref op) => &op.op,
+            DerefField(ref op) => &op.op,
+
+            Const(ref op) | ConstPtr(ref op) | Import(ref op) => &op.op,
+            ExternPtr(ref op) => &op.op,
+            FloatConst(ref op) => &op.op,
+
+            Call(ref op) => &op.op,
+            Tailcall(ref op) => &op.op,
+            Syscall(ref op) => &op.op,
+            Intrinsic(ref op) => &op.op,
+            Trap(ref op) => &op.op,
+
+            AssignMem(ref op) => &op.op,
+            AssignUnpackMem(ref op) => &op.op,
+            VarPhi(ref op) => &op.op,
+            MemPhi(ref op) => &op.op,
+
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'func, A, M> fmt::Debug for ExprInfo<'func, A, M, NonSSA>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::ExprInfo::*;
+
+        if let Some(op) = self.as_cmp_op() {
+            return write!(
+                f,
+                "{:?}({}, {:?}, {:?})",
+                op.op.operation,
+                op.size(),
+                op.left(),
+                op.right()
+            );
+        }
+
+        if let Some(op) = self.as_binary_op() {
+            return write!(
+                f,
+                "{:?}({}, {:?}, {:?})",
+                op.op.operation,
+                op.size(),
+                op.left(),
+                op.right()
+            );
+        }
+
+        if let Some(op) = self.as_binary_op_carry() {
+            return write!(
+                f,
+                "{:?}({}, {:?}, {:?}, carry: {:?})",
+                op.op.operation,
+                op.size(),
+                op.left(),
+                op.right(),
+                op.carry()
+            );
+        }
+
+        if let Some(op) = self.as_unary_op() {
+            return write!(
+                f,
+                "{:?}({}, {:?})",
+                op.op.operation,
+                op.size(),
+                op.operand()
+            );
+        }
+
+        match *self {
+            Undef(..) => f.write_str("undefined"),
+
+            Unimpl(..) => f.write_str("unimplemented"),
+
+            Nop(..) => f.write_str("nop"),
+
+            Break(..) => f.write_str("break"),
+
+            Continue(..) => f.write_str("continue"),
+
+            NoRet(..) => f.write_str("noreturn"),
+
+            Bp(..) => f.write_str("breakpoint"),
+
+            Block(ref op) => write!(f, "{{ {:?} }}", op.body()),
+
+            If(ref op) => write!(
+                f,
+                "if ({:?}) {:?} else {:?}",
+                op.condition(),
+                op.true_body(),
+                op.false_body()
+            ),
+
+            While(ref op) => write!(f, "while ({:?}) {:?}", op.condition(), op.body()),
+
+            DoWhile(ref op) => write!(f, "do {:?} while ({:?})", op.body(), op.condition()),
+
+            For(ref op) => write!(
+                f,
+                "for ({:?}; {:?}; {:?}) {:?}",
+                op.init(),
+                op.condition(),
+                op.update(),
+                op.body()
+            ),
+
+            Switch(ref op) => write!(
+                f,
+                "switch ({:?}) {:?} default: {:?}",
+                op.condition(),
+                op.cases(),
+                op.default()
+            ),
+
+            Case(ref op) => write!(f, "case {:?}: {:?}", op.values(), op.body()),
+
+            Jump(ref op) => write!(f, "jump({:?})", op.target()),
+
+            Ret(ref op) => write!(f, "return {:?}", op.source_exprs()),
+
+            Goto(ref op) => write!(f, "goto label_{}", op.label_id()),
+
+            Label(ref op) => write!(f, "label_{}:", op.label_id()),
+
+            VarDeclare(ref op) => write!(f, "declare {:?}", op.var()),
+
+            VarInit(ref op) => write!(f, "{:?} = {:?}", op.dest_var(), op.source_expr()),
+
+            Assign(ref op) => write!(f, "{:?} = {:?}", op.dest_expr(), op.source_expr()),
+
+            AssignUnpack(ref op) => {
+                write!(f, "{:?} = {:?}", op.dest_exprs(), op.source_expr())
+            }
+
+            Var(ref op) => write!(f, "{:?}", op.var()),
+
+            StructField(ref op) => write!(
+                f,
+                "{:?}:0x{:x}.{}",
+                op.source_expr(),
+                op.offset(),
+                op.size()
+            ),
+
+            ArrayIndex(ref op) => write!(f, "{:?}[{:?}]", op.source_expr(), op.index()),
+
+            Split(ref op) => write!(f, "{:?}:{:?}", op.high(), op.low()),
+
+            Deref(ref op) => write!(f, "[{:?}].{}", op.source_expr(), op.size()),
+
+            DerefField(ref op) => write!(
+                f,
+                "[{:?} + 0x{:x}].{}",
+                op.source_expr(),
+                op.offset(),
+                op.size()
+            ),
+
+            Const(ref op) | ConstPtr(ref op) | Import(ref op) => write!(f, "0x{:x}", op.value()),
+
+            ExternPtr(ref op) => write!(f, "extern(0x{:x}) + 0x{:x}", op.value(), op.offset()),
+
+            FloatConst(ref op) => write!(f, "{}", op.value()),
+
+            Call(ref op) => write!(f, "call({:?}, {:?})", op.target(), op.params()),
+
+            Tailcall(ref op) => write!(f, "tailcall({:?}, {:?})", op.target(), op.params()),
+
+            Syscall(ref op) => write!(f, "syscall({:?})", op.params()),
+
+            Intrinsic(ref op) => {
+                write!(f, "intrinsic({}, {:?})", op.intrinsic_id(), op.params())
+            }
+
+            Trap(ref op) => write!(f, "trap({})", op.vector()),
+
+            _ => unreachable!(),
+        }
+    }
+}
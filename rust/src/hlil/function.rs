@@ -0,0 +1,378 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNFreeHighLevelILFunction;
+use binaryninjacore_sys::BNHighLevelILFunction;
+use binaryninjacore_sys::BNNewHighLevelILFunctionReference;
+
+use std::marker::PhantomData;
+use std::slice;
+
+use crate::basicblock::BasicBlock;
+use crate::function::Function as CoreFunction;
+use crate::mlil;
+use crate::rc::*;
+
+use super::*;
+
+pub struct Function<A: Architecture, M: FunctionMutability, F: FunctionForm> {
+    pub(crate) borrower: A::Handle,
+    pub(crate) handle: *mut BNHighLevelILFunction,
+    _arch: PhantomData<*mut A>,
+    _mutability: PhantomData<M>,
+    _form: PhantomData<F>,
+}
+
+unsafe impl<A: Architecture, M: FunctionMutability, F: FunctionForm> Send for Function<A, M, F> {}
+unsafe impl<A: Architecture, M: FunctionMutability, F: FunctionForm> Sync for Function<A, M, F> {}
+
+impl<A: Architecture, M: FunctionMutability, F: FunctionForm> Eq for Function<A, M, F> {}
+impl<A: Architecture, M: FunctionMutability, F: FunctionForm> PartialEq for Function<A, M, F> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.handle == rhs.handle
+    }
+}
+
+use std::hash::{Hash, Hasher};
+impl<A: Architecture, M: FunctionMutability, F: FunctionForm> Hash for Function<A, M, F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handle.hash(state);
+    }
+}
+
+impl<'func, A, M, F> Function<A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub(crate) unsafe fn from_raw(borrower: A::Handle, handle: *mut BNHighLevelILFunction) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self {
+            borrower,
+            handle,
+            _arch: PhantomData,
+            _mutability: PhantomData,
+            _form: PhantomData,
+        }
+    }
+
+    pub fn function(&self) -> Ref<CoreFunction> {
+        use binaryninjacore_sys::BNGetHighLevelILOwnerFunction;
+
+        unsafe { CoreFunction::from_raw(BNGetHighLevelILOwnerFunction(self.handle)) }
+    }
+
+    /// Returns the root of the abstract syntax tree for this function
+    pub fn root(&self) -> Expression<A, M, F, ValueExpr> {
+        use binaryninjacore_sys::BNGetHighLevelILRootExpr;
+
+        let expr_idx = unsafe { BNGetHighLevelILRootExpr(self.handle) };
+
+        Expression::new(self, expr_idx)
+    }
+
+    pub fn instruction_from_idx(&self, instr_idx: usize) -> Instruction<A, M, F> {
+        unsafe {
+            use binaryninjacore_sys::BNGetHighLevelILInstructionCount;
+            if instr_idx >= BNGetHighLevelILInstructionCount(self.handle) {
+                panic!("instruction index {} out of bounds", instr_idx);
+            }
+
+            Instruction {
+                function: self,
+                instr_idx,
+            }
+        }
+    }
+
+    pub fn instruction_count(&self) -> usize {
+        unsafe {
+            use binaryninjacore_sys::BNGetHighLevelILInstructionCount;
+            BNGetHighLevelILInstructionCount(self.handle)
+        }
+    }
+
+    /// Returns every variable referenced by this function
+    pub fn variables(&self) -> Vec<Variable> {
+        use binaryninjacore_sys::BNFreeVariableList;
+        use binaryninjacore_sys::BNGetHighLevelILVariables;
+
+        unsafe {
+            let mut count = 0;
+            let vars = BNGetHighLevelILVariables(self.handle, &mut count);
+
+            let result = slice::from_raw_parts(vars, count)
+                .iter()
+                .map(|&v| Variable::from_raw(v))
+                .collect();
+
+            BNFreeVariableList(vars);
+
+            result
+        }
+    }
+
+    pub fn expression_count(&self) -> usize {
+        unsafe {
+            use binaryninjacore_sys::BNGetHighLevelILExprCount;
+            BNGetHighLevelILExprCount(self.handle)
+        }
+    }
+
+    pub(crate) fn expression_list(
+        &self,
+        list: *mut usize,
+        count: usize,
+    ) -> Vec<Expression<A, M, F, ValueExpr>> {
+        use binaryninjacore_sys::BNFreeILInstructionList;
+
+        unsafe {
+            let result = slice::from_raw_parts(list, count)
+                .iter()
+                .map(|&expr_idx| Expression::new(self, expr_idx))
+                .collect();
+
+            BNFreeILInstructionList(list);
+
+            result
+        }
+    }
+}
+
+// HLIL basic blocks are only available once the function
+// has been finalized
+impl<'func, A, F> Function<A, Finalized, F>
+where
+    A: 'func + Architecture,
+    F: FunctionForm,
+{
+    pub fn basic_blocks(&self) -> Array<BasicBlock<HighLevelBlock<A, Finalized, F>>> {
+        use binaryninjacore_sys::BNGetHighLevelILBasicBlockList;
+
+        unsafe {
+            let mut count = 0;
+            let blocks = BNGetHighLevelILBasicBlockList(self.handle, &mut count);
+            let context = HighLevelBlock { function: self };
+
+            Array::new(blocks, count, context)
+        }
+    }
+}
+
+impl<'func, A> Function<A, Finalized, NonSSA>
+where
+    A: 'func + Architecture,
+{
+    pub fn ssa_form(&self) -> Result<Ref<Function<A, Finalized, SSA>>, ()> {
+        use binaryninjacore_sys::BNGetHighLevelILSSAForm;
+
+        unsafe {
+            let ssa = BNGetHighLevelILSSAForm(self.handle);
+
+            if ssa.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(Function::from_raw(self.borrower.clone(), ssa)))
+        }
+    }
+
+    /// Returns the medium level IL this function was generated from
+    pub fn medium_level_il(&self) -> Result<Ref<mlil::RegularFunction<A>>, ()> {
+        use binaryninjacore_sys::BNGetMediumLevelILForHighLevelILFunction;
+
+        unsafe {
+            let mlil = BNGetMediumLevelILForHighLevelILFunction(self.handle);
+
+            if mlil.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(mlil::RegularFunction::from_raw(
+                self.borrower.clone(),
+                mlil,
+            )))
+        }
+    }
+
+    /// Returns the expressions that write to `var`
+    pub fn variable_definitions(
+        &self,
+        var: &Variable,
+    ) -> Vec<Expression<A, Finalized, NonSSA, ValueExpr>> {
+        use binaryninjacore_sys::BNGetHighLevelILVariableDefinitions;
+
+        let raw_var = var.into_raw();
+
+        unsafe {
+            let mut count = 0;
+            let exprs = BNGetHighLevelILVariableDefinitions(self.handle, &raw_var, &mut count);
+
+            self.expression_list(exprs, count)
+        }
+    }
+
+    /// Returns the expressions that read from `var`
+    pub fn variable_uses(
+        &self,
+        var: &Variable,
+    ) -> Vec<Expression<A, Finalized, NonSSA, ValueExpr>> {
+        use binaryninjacore_sys::BNGetHighLevelILVariableUses;
+
+        let raw_var = var.into_raw();
+
+        unsafe {
+            let mut count = 0;
+            let exprs = BNGetHighLevelILVariableUses(self.handle, &raw_var, &mut count);
+
+            self.expression_list(exprs, count)
+        }
+    }
+}
+
+impl<'func, A> Function<A, Finalized, SSA>
+where
+    A: 'func + Architecture,
+{
+    pub fn non_ssa_form(&self) -> Result<Ref<Function<A, Finalized, NonSSA>>, ()> {
+        use binaryninjacore_sys::BNGetHighLevelILNonSSAForm;
+
+        unsafe {
+            let non_ssa = BNGetHighLevelILNonSSAForm(self.handle);
+
+            if non_ssa.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(Function::from_raw(self.borrower.clone(), non_ssa)))
+        }
+    }
+
+    /// Returns the expression that defines the given version of a variable
+    ///
+    /// Variables that are never written within the function (such as
+    /// incoming parameters at version 0) have no definition.
+    pub fn get_ssa_var_definition(
+        &self,
+        var: &SSAVariable,
+    ) -> Option<Expression<A, Finalized, SSA, ValueExpr>> {
+        use binaryninjacore_sys::BNGetHighLevelILSSAVarDefinition;
+
+        let raw_var = var.variable.into_raw();
+
+        unsafe {
+            let expr_idx = BNGetHighLevelILSSAVarDefinition(self.handle, &raw_var, var.version);
+
+            if expr_idx >= self.expression_count() {
+                None
+            } else {
+                Some(Expression::new(self, expr_idx))
+            }
+        }
+    }
+
+    pub fn get_ssa_var_uses(
+        &self,
+        var: &SSAVariable,
+    ) -> Vec<Expression<A, Finalized, SSA, ValueExpr>> {
+        use binaryninjacore_sys::BNGetHighLevelILSSAVarUses;
+
+        let raw_var = var.variable.into_raw();
+
+        unsafe {
+            let mut count = 0;
+            let exprs = BNGetHighLevelILSSAVarUses(self.handle, &raw_var, var.version, &mut count);
+
+            self.expression_list(exprs, count)
+        }
+    }
+
+    pub fn get_ssa_memory_definition(
+        &self,
+        version: usize,
+    ) -> Option<Expression<A, Finalized, SSA, ValueExpr>> {
+        use binaryninjacore_sys::BNGetHighLevelILSSAMemoryDefinition;
+
+        unsafe {
+            let expr_idx = BNGetHighLevelILSSAMemoryDefinition(self.handle, version);
+
+            if expr_idx >= self.expression_count() {
+                None
+            } else {
+                Some(Expression::new(self, expr_idx))
+            }
+        }
+    }
+
+    pub fn get_ssa_memory_uses(
+        &self,
+        version: usize,
+    ) -> Vec<Expression<A, Finalized, SSA, ValueExpr>> {
+        use binaryninjacore_sys::BNGetHighLevelILSSAMemoryUses;
+
+        unsafe {
+            let mut count = 0;
+            let exprs = BNGetHighLevelILSSAMemoryUses(self.handle, version, &mut count);
+
+            self.expression_list(exprs, count)
+        }
+    }
+}
+
+impl<'func, A, M, F> ToOwned for Function<A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl<'func, A, M, F> RefCountable for Function<A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            borrower: handle.borrower.clone(),
+            handle: BNNewHighLevelILFunctionReference(handle.handle),
+            _arch: PhantomData,
+            _mutability: PhantomData,
+            _form: PhantomData,
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeHighLevelILFunction(handle.handle);
+    }
+}
+
+impl<'func, A, M, F> fmt::Debug for Function<A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<hlil func handle {:p}>", self.handle)
+    }
+}
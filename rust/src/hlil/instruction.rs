@@ -0,0 +1,100 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNGetHighLevelILIndexForInstruction;
+
+use super::*;
+
+use crate::architecture::Architecture;
+
+pub struct Instruction<'func, A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub(crate) function: &'func Function<A, M, F>,
+    pub(crate) instr_idx: usize,
+}
+
+impl<'func, A, M, F> Instruction<'func, A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn index(&self) -> usize {
+        self.instr_idx
+    }
+
+    /// Returns the expression at the root of this instruction
+    pub fn expr(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        let expr_idx =
+            unsafe { BNGetHighLevelILIndexForInstruction(self.function.handle, self.instr_idx) };
+
+        Expression::new(self.function, expr_idx)
+    }
+
+    pub fn address(&self) -> u64 {
+        self.expr().address()
+    }
+}
+
+impl<'func, A, M> Instruction<'func, A, M, NonSSA>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn info(&self) -> ExprInfo<'func, A, M, NonSSA> {
+        self.expr().info()
+    }
+
+    /// Visits the expression tree rooted at this instruction
+    ///
+    /// Control flow instructions own their bodies, so visiting an `If`
+    /// or a loop also visits the instructions of the blocks it contains.
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(
+            &Expression<'func, A, M, NonSSA, ValueExpr>,
+            &ExprInfo<'func, A, M, NonSSA>,
+        ) -> VisitorAction,
+    {
+        self.expr().visit_tree(f)
+    }
+}
+
+impl<'func, A, M> Instruction<'func, A, M, SSA>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn info(&self) -> ExprInfo<'func, A, M, SSA> {
+        self.expr().info()
+    }
+
+    /// Visits the expression tree rooted at this instruction
+    ///
+    /// Control flow instructions own their bodies, so visiting an `If`
+    /// or a loop also visits the instructions of the blocks it contains.
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(
+            &Expression<'func, A, M, SSA, ValueExpr>,
+            &ExprInfo<'func, A, M, SSA>,
+        ) -> VisitorAction,
+    {
+        self.expr().visit_tree(f)
+    }
+}
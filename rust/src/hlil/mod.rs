@@ -0,0 +1,42 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// HLIL is a tree: statements and values are both expressions, and control
+// flow constructs (if/while/for/switch) own their bodies. The basic blocks
+// are still available for dominator queries, but an instruction in a block
+// is the root of a subtree that may span several blocks.
+
+use std::fmt;
+
+use crate::architecture::Architecture;
+use crate::types::Variable;
+
+mod block;
+mod expression;
+mod function;
+mod instruction;
+pub mod operation;
+
+pub use self::expression::*;
+pub use self::function::*;
+pub use self::instruction::*;
+
+pub use self::block::Block as HighLevelBlock;
+pub use self::block::BlockIter as HighLevelBlockIter;
+
+pub use crate::llil::{Finalized, FunctionMutability, Mutable, VisitorAction};
+pub use crate::mlil::{FunctionForm, NonSSA, SSAVariable, SSA};
+
+pub type RegularFunction<Arch> = Function<Arch, Finalized, NonSSA>;
+pub type SSAFunction<Arch> = Function<Arch, Finalized, SSA>;
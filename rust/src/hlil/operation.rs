@@ -0,0 +1,1101 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNHighLevelILInstruction;
+
+use std::marker::PhantomData;
+use std::mem;
+use std::slice;
+
+use crate::string::BnString;
+
+use super::*;
+
+pub struct Operation<'func, A, M, F, O>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+    O: OperationArguments,
+{
+    pub(crate) function: &'func Function<A, M, F>,
+    pub(crate) expr_idx: usize,
+    pub(crate) op: BNHighLevelILInstruction,
+    _args: PhantomData<O>,
+}
+
+impl<'func, A, M, F, O> Operation<'func, A, M, F, O>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+    O: OperationArguments,
+{
+    pub(crate) fn new(
+        function: &'func Function<A, M, F>,
+        expr_idx: usize,
+        op: BNHighLevelILInstruction,
+    ) -> Self {
+        Self {
+            function,
+            expr_idx,
+            op,
+            _args: PhantomData,
+        }
+    }
+
+    pub fn address(&self) -> u64 {
+        self.op.address
+    }
+
+    fn expr_operand(&self, operand: usize) -> Expression<'func, A, M, F, ValueExpr> {
+        Expression::new(self.function, self.op.operands[operand] as usize)
+    }
+
+    fn var_operand(&self, operand: usize) -> Variable {
+        Variable::from_identifier(self.op.operands[operand])
+    }
+
+    fn ssa_var_operand(&self, var_operand: usize, version_operand: usize) -> SSAVariable {
+        SSAVariable::new(
+            self.var_operand(var_operand),
+            self.op.operands[version_operand] as usize,
+        )
+    }
+
+    // struct member indices use the top bit to mark "no member"
+    fn member_index_operand(&self, operand: usize) -> Option<usize> {
+        match self.op.operands[operand] {
+            idx if idx & (1 << 63) != 0 => None,
+            idx => Some(idx as usize),
+        }
+    }
+
+    fn operand_list(&self, operand: usize) -> Vec<u64> {
+        use binaryninjacore_sys::BNHighLevelILFreeOperandList;
+        use binaryninjacore_sys::BNHighLevelILGetOperandList;
+
+        unsafe {
+            let mut count = 0;
+            let list = BNHighLevelILGetOperandList(
+                self.function.handle,
+                self.expr_idx,
+                operand,
+                &mut count,
+            );
+
+            let result = slice::from_raw_parts(list, count).to_vec();
+
+            BNHighLevelILFreeOperandList(list);
+
+            result
+        }
+    }
+
+    fn expr_list(&self, operand: usize) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.operand_list(operand)
+            .into_iter()
+            .map(|idx| Expression::new(self.function, idx as usize))
+            .collect()
+    }
+}
+
+// HLIL_NOP, HLIL_BREAK, HLIL_CONTINUE, HLIL_NORET,
+// HLIL_BP, HLIL_UNDEF, HLIL_UNIMPL
+pub struct NoArgs;
+
+// HLIL_BLOCK
+pub struct Block;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Block>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn body(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.expr_list(0)
+    }
+}
+
+// HLIL_IF
+pub struct If;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, If>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn condition(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn true_body(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(1)
+    }
+
+    /// The `else` branch, which is `Nop` when the statement has none
+    pub fn false_body(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+// HLIL_WHILE, HLIL_WHILE_SSA
+pub struct While;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, While>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn condition(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn body(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, While>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn condition_phi(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn condition(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+
+    pub fn body(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+// HLIL_DO_WHILE, HLIL_DO_WHILE_SSA
+pub struct DoWhile;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, DoWhile>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn body(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn condition(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, DoWhile>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn body(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn condition_phi(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+
+    pub fn condition(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+// HLIL_FOR, HLIL_FOR_SSA
+pub struct For;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, For>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn init(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn condition(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+
+    pub fn update(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+
+    pub fn body(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(3)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, For>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn init(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn condition_phi(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+
+    pub fn condition(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+
+    pub fn update(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(3)
+    }
+
+    pub fn body(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(4)
+    }
+}
+
+// HLIL_SWITCH
+pub struct Switch;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Switch>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn condition(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn default(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(1)
+    }
+
+    /// Returns the `Case` expressions of this switch
+    pub fn cases(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.expr_list(2)
+    }
+}
+
+// HLIL_CASE
+pub struct Case;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Case>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn values(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.expr_list(0)
+    }
+
+    pub fn body(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+// HLIL_JUMP
+pub struct Jump;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Jump>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn target(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+}
+
+// HLIL_RET
+pub struct Ret;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Ret>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn source_exprs(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.expr_list(0)
+    }
+}
+
+// HLIL_GOTO, HLIL_LABEL
+pub struct Label;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Label>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn label_id(&self) -> u64 {
+        self.op.operands[0]
+    }
+
+    pub fn name(&self) -> BnString {
+        use binaryninjacore_sys::BNGetGotoLabelName;
+
+        let func = self.function.function();
+
+        unsafe { BnString::from_raw(BNGetGotoLabelName(func.handle, self.label_id())) }
+    }
+
+    /// Returns the `Label` expression this label id refers to
+    pub fn target(&self) -> Option<Expression<'func, A, M, F, ValueExpr>> {
+        use binaryninjacore_sys::BNGetHighLevelILExprIndexForLabel;
+
+        let expr_idx =
+            unsafe { BNGetHighLevelILExprIndexForLabel(self.function.handle, self.label_id()) };
+
+        if expr_idx == usize::MAX {
+            None
+        } else {
+            Some(Expression::new(self.function, expr_idx))
+        }
+    }
+}
+
+// HLIL_VAR_DECLARE
+pub struct VarDeclare;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, VarDeclare>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn var(&self) -> Variable {
+        self.var_operand(0)
+    }
+}
+
+// HLIL_VAR_INIT, HLIL_VAR_INIT_SSA
+pub struct VarInit;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, VarInit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_var(&self) -> Variable {
+        self.var_operand(0)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, VarInit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+// HLIL_ASSIGN
+pub struct Assign;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Assign>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_expr(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+// HLIL_ASSIGN_MEM_SSA
+pub struct AssignMem;
+
+impl<'func, A, M> Operation<'func, A, M, SSA, AssignMem>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[3] as usize
+    }
+}
+
+// HLIL_ASSIGN_UNPACK
+pub struct AssignUnpack;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, AssignUnpack>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn dest_exprs(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.expr_list(0)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+// HLIL_ASSIGN_UNPACK_MEM_SSA
+pub struct AssignUnpackMem;
+
+impl<'func, A, M> Operation<'func, A, M, SSA, AssignUnpackMem>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_exprs(&self) -> Vec<Expression<'func, A, M, SSA, ValueExpr>> {
+        self.expr_list(0)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(3)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[4] as usize
+    }
+}
+
+// HLIL_VAR, HLIL_VAR_SSA
+pub struct Var;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, Var>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn var(&self) -> Variable {
+        self.var_operand(0)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Var>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+}
+
+// HLIL_STRUCT_FIELD
+pub struct StructField;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, StructField>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+
+    pub fn member_index(&self) -> Option<usize> {
+        self.member_index_operand(2)
+    }
+}
+
+// HLIL_ARRAY_INDEX, HLIL_ARRAY_INDEX_SSA
+pub struct ArrayIndex;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, ArrayIndex>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn index(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, ArrayIndex>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+
+    pub fn index(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+// HLIL_SPLIT
+pub struct Split;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Split>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn high(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn low(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+// HLIL_DEREF, HLIL_DEREF_SSA
+pub struct Deref;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Deref>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Deref>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+}
+
+// HLIL_DEREF_FIELD, HLIL_DEREF_FIELD_SSA
+pub struct DerefField;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, DerefField>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+
+    pub fn member_index(&self) -> Option<usize> {
+        self.member_index_operand(2)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, DerefField>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[2]
+    }
+
+    pub fn member_index(&self) -> Option<usize> {
+        self.member_index_operand(3)
+    }
+}
+
+// HLIL_CALL, HLIL_CALL_SSA
+pub struct Call;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Call>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn target(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.expr_list(1)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Call>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[3] as usize
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[4] as usize
+    }
+}
+
+// HLIL_TAILCALL
+pub struct Tailcall;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Tailcall>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn target(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.expr_list(1)
+    }
+}
+
+// HLIL_SYSCALL, HLIL_SYSCALL_SSA
+pub struct Syscall;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Syscall>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn params(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.expr_list(0)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Syscall>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[3] as usize
+    }
+}
+
+// HLIL_INTRINSIC, HLIL_INTRINSIC_SSA
+pub struct Intrinsic;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Intrinsic>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn intrinsic_id(&self) -> u32 {
+        self.op.operands[0] as u32
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.expr_list(1)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Intrinsic>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[3] as usize
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[4] as usize
+    }
+}
+
+// HLIL_TRAP
+pub struct Trap;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Trap>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn vector(&self) -> u64 {
+        self.op.operands[0]
+    }
+}
+
+// HLIL_VAR_PHI
+pub struct VarPhi;
+
+impl<'func, A, M> Operation<'func, A, M, SSA, VarPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+
+    pub fn sources(&self) -> Vec<SSAVariable> {
+        self.operand_list(2)
+            .chunks(2)
+            .map(|pair| SSAVariable::new(Variable::from_identifier(pair[0]), pair[1] as usize))
+            .collect()
+    }
+}
+
+// HLIL_MEM_PHI
+pub struct MemPhi;
+
+impl<'func, A, M> Operation<'func, A, M, SSA, MemPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[0] as usize
+    }
+
+    pub fn source_memory_versions(&self) -> Vec<usize> {
+        self.operand_list(1)
+            .into_iter()
+            .map(|v| v as usize)
+            .collect()
+    }
+}
+
+// HLIL_CONST, HLIL_CONST_PTR, HLIL_IMPORT
+pub struct Const;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Const>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn value(&self) -> u64 {
+        let mut mask = -1i64 as u64;
+
+        if self.op.size > 0 && self.op.size < mem::size_of::<u64>() {
+            mask <<= self.op.size * 8;
+            mask = !mask;
+        }
+
+        self.op.operands[0] & mask
+    }
+}
+
+// HLIL_EXTERN_PTR
+pub struct ExternPtr;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, ExternPtr>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn value(&self) -> u64 {
+        self.op.operands[0]
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+// HLIL_FLOAT_CONST
+pub struct FloatConst;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, FloatConst>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn value(&self) -> f64 {
+        match self.op.size {
+            4 => f32::from_bits(self.op.operands[0] as u32) as f64,
+            _ => f64::from_bits(self.op.operands[0]),
+        }
+    }
+}
+
+// HLIL_ADD, HLIL_SUB, HLIL_AND, HLIL_OR
+// HLIL_XOR, HLIL_LSL, HLIL_LSR, HLIL_ASR
+// HLIL_ROL, HLIL_ROR, HLIL_MUL, HLIL_MULU_DP,
+// HLIL_MULS_DP, HLIL_DIVU, HLIL_DIVU_DP, HLIL_DIVS,
+// HLIL_DIVS_DP, HLIL_MODU, HLIL_MODU_DP, HLIL_MODS,
+// HLIL_MODS_DP, HLIL_ADD_OVERFLOW, HLIL_FADD, HLIL_FSUB,
+// HLIL_FMUL, HLIL_FDIV
+pub struct BinaryOp;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, BinaryOp>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn right(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+// HLIL_ADC, HLIL_SBB, HLIL_RLC, HLIL_RRC
+pub struct BinaryOpCarry;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, BinaryOpCarry>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn right(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(1)
+    }
+
+    pub fn carry(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+// HLIL_ADDRESS_OF, HLIL_NEG, HLIL_NOT, HLIL_SX, HLIL_ZX,
+// HLIL_LOW_PART, HLIL_BOOL_TO_INT, HLIL_UNIMPL_MEM, HLIL_FSQRT,
+// HLIL_FNEG, HLIL_FABS, HLIL_FLOAT_TO_INT, HLIL_INT_TO_FLOAT,
+// HLIL_FLOAT_CONV, HLIL_ROUND_TO_INT, HLIL_FLOOR, HLIL_CEIL,
+// HLIL_FTRUNC
+pub struct UnaryOp;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, UnaryOp>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn operand(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+}
+
+// HLIL_CMP_X, HLIL_FCMP_X, HLIL_TEST_BIT
+pub struct Condition;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Condition>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn right(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+pub trait OperationArguments: 'static {}
+
+impl OperationArguments for NoArgs {}
+impl OperationArguments for Block {}
+impl OperationArguments for If {}
+impl OperationArguments for While {}
+impl OperationArguments for DoWhile {}
+impl OperationArguments for For {}
+impl OperationArguments for Switch {}
+impl OperationArguments for Case {}
+impl OperationArguments for Jump {}
+impl OperationArguments for Ret {}
+impl OperationArguments for Label {}
+impl OperationArguments for VarDeclare {}
+impl OperationArguments for VarInit {}
+impl OperationArguments for Assign {}
+impl OperationArguments for AssignUnpack {}
+impl OperationArguments for AssignMem {}
+impl OperationArguments for AssignUnpackMem {}
+impl OperationArguments for Var {}
+impl OperationArguments for StructField {}
+impl OperationArguments for ArrayIndex {}
+impl OperationArguments for Split {}
+impl OperationArguments for Deref {}
+impl OperationArguments for DerefField {}
+impl OperationArguments for Call {}
+impl OperationArguments for Tailcall {}
+impl OperationArguments for Syscall {}
+impl OperationArguments for Intrinsic {}
+impl OperationArguments for Trap {}
+impl OperationArguments for VarPhi {}
+impl OperationArguments for MemPhi {}
+impl OperationArguments for Const {}
+impl OperationArguments for ExternPtr {}
+impl OperationArguments for FloatConst {}
+impl OperationArguments for BinaryOp {}
+impl OperationArguments for BinaryOpCarry {}
+impl OperationArguments for UnaryOp {}
+impl OperationArguments for Condition {}
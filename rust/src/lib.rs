@@ -25,7 +25,6 @@ extern crate rayon;
 // TODO
 // move some options to results
 // replace `fn handle` with `AsRef` bounds
-// possible values
 // arch rework
 // cc possible values
 // bv reorg
@@ -39,6 +38,7 @@ mod ffi;
 pub mod architecture;
 pub mod backgroundtask;
 pub mod basicblock;
+pub mod binarydatanotification;
 pub mod binaryview;
 pub mod callingconvention;
 pub mod command;
@@ -52,14 +52,23 @@ pub mod filemetadata;
 pub mod flowgraph;
 pub mod function;
 pub mod headless;
+pub mod hlil;
 pub mod llil;
+pub mod metadata;
+pub mod mlil;
 pub mod platform;
 pub mod rc;
+pub mod references;
+pub mod rusttypeprinter;
 pub mod section;
 pub mod segment;
 pub mod settings;
 pub mod string;
 pub mod symbol;
+pub mod tags;
+pub mod typedvalue;
+pub mod typelibrary;
+pub mod typeprinter;
 pub mod types;
 
 use std::collections::HashMap;
@@ -17,6 +17,7 @@ use binaryninjacore_sys::BNLowLevelILInstruction;
 
 use std::fmt;
 use std::marker::PhantomData;
+use std::ptr;
 
 use super::operation;
 use super::operation::Operation;
@@ -24,6 +25,7 @@ use super::*;
 
 use crate::architecture::Architecture;
 use crate::architecture::RegisterInfo;
+use crate::types::{PossibleValueSet, RegisterValue};
 
 // used as a marker for Expressions that can produce a value
 #[derive(Copy, Clone, Debug)]
@@ -77,6 +79,7 @@ where
 
 fn common_info<'func, A, M, F>(
     function: &'func Function<A, M, F>,
+    expr_idx: usize,
     op: BNLowLevelILInstruction,
 ) -> ExprInfo<'func, A, M, F>
 where
@@ -87,64 +90,92 @@ where
     use binaryninjacore_sys::BNLowLevelILOperation::*;
 
     match op.operation {
-        LLIL_CONST => ExprInfo::Const(Operation::new(function, op)),
-        LLIL_CONST_PTR => ExprInfo::ConstPtr(Operation::new(function, op)),
-
-        LLIL_ADD => ExprInfo::Add(Operation::new(function, op)),
-        LLIL_ADC => ExprInfo::Adc(Operation::new(function, op)),
-        LLIL_SUB => ExprInfo::Sub(Operation::new(function, op)),
-        LLIL_SBB => ExprInfo::Sbb(Operation::new(function, op)),
-        LLIL_AND => ExprInfo::And(Operation::new(function, op)),
-        LLIL_OR => ExprInfo::Or(Operation::new(function, op)),
-        LLIL_XOR => ExprInfo::Xor(Operation::new(function, op)),
-        LLIL_LSL => ExprInfo::Lsl(Operation::new(function, op)),
-        LLIL_LSR => ExprInfo::Lsr(Operation::new(function, op)),
-        LLIL_ASR => ExprInfo::Asr(Operation::new(function, op)),
-        LLIL_ROL => ExprInfo::Rol(Operation::new(function, op)),
-        LLIL_RLC => ExprInfo::Rlc(Operation::new(function, op)),
-        LLIL_ROR => ExprInfo::Ror(Operation::new(function, op)),
-        LLIL_RRC => ExprInfo::Rrc(Operation::new(function, op)),
-        LLIL_MUL => ExprInfo::Mul(Operation::new(function, op)),
-
-        LLIL_MULU_DP => ExprInfo::MuluDp(Operation::new(function, op)),
-        LLIL_MULS_DP => ExprInfo::MulsDp(Operation::new(function, op)),
-
-        LLIL_DIVU => ExprInfo::Divu(Operation::new(function, op)),
-        LLIL_DIVS => ExprInfo::Divs(Operation::new(function, op)),
-
-        LLIL_DIVU_DP => ExprInfo::DivuDp(Operation::new(function, op)),
-        LLIL_DIVS_DP => ExprInfo::DivsDp(Operation::new(function, op)),
-
-        LLIL_MODU => ExprInfo::Modu(Operation::new(function, op)),
-        LLIL_MODS => ExprInfo::Mods(Operation::new(function, op)),
-
-        LLIL_MODU_DP => ExprInfo::ModuDp(Operation::new(function, op)),
-        LLIL_MODS_DP => ExprInfo::ModsDp(Operation::new(function, op)),
-
-        LLIL_NEG => ExprInfo::Neg(Operation::new(function, op)),
-        LLIL_NOT => ExprInfo::Not(Operation::new(function, op)),
-
-        LLIL_SX => ExprInfo::Sx(Operation::new(function, op)),
-        LLIL_ZX => ExprInfo::Zx(Operation::new(function, op)),
-        LLIL_LOW_PART => ExprInfo::LowPart(Operation::new(function, op)),
-
-        LLIL_CMP_E => ExprInfo::CmpE(Operation::new(function, op)),
-        LLIL_CMP_NE => ExprInfo::CmpNe(Operation::new(function, op)),
-        LLIL_CMP_SLT => ExprInfo::CmpSlt(Operation::new(function, op)),
-        LLIL_CMP_ULT => ExprInfo::CmpUlt(Operation::new(function, op)),
-        LLIL_CMP_SLE => ExprInfo::CmpSle(Operation::new(function, op)),
-        LLIL_CMP_ULE => ExprInfo::CmpUle(Operation::new(function, op)),
-        LLIL_CMP_SGE => ExprInfo::CmpSge(Operation::new(function, op)),
-        LLIL_CMP_UGE => ExprInfo::CmpUge(Operation::new(function, op)),
-        LLIL_CMP_SGT => ExprInfo::CmpSgt(Operation::new(function, op)),
-        LLIL_CMP_UGT => ExprInfo::CmpUgt(Operation::new(function, op)),
-
-        LLIL_BOOL_TO_INT => ExprInfo::BoolToInt(Operation::new(function, op)),
-
-        LLIL_UNIMPL => ExprInfo::Unimpl(Operation::new(function, op)),
-        LLIL_UNIMPL_MEM => ExprInfo::UnimplMem(Operation::new(function, op)),
-
-        // TODO TEST_BIT ADD_OVERFLOW
+        LLIL_CONST => ExprInfo::Const(Operation::new(function, expr_idx, op)),
+        LLIL_CONST_PTR => ExprInfo::ConstPtr(Operation::new(function, expr_idx, op)),
+        LLIL_EXTERN_PTR => ExprInfo::ExternPtr(Operation::new(function, expr_idx, op)),
+        LLIL_FLOAT_CONST => ExprInfo::FloatConst(Operation::new(function, expr_idx, op)),
+
+        LLIL_ADD => ExprInfo::Add(Operation::new(function, expr_idx, op)),
+        LLIL_ADC => ExprInfo::Adc(Operation::new(function, expr_idx, op)),
+        LLIL_SUB => ExprInfo::Sub(Operation::new(function, expr_idx, op)),
+        LLIL_SBB => ExprInfo::Sbb(Operation::new(function, expr_idx, op)),
+        LLIL_AND => ExprInfo::And(Operation::new(function, expr_idx, op)),
+        LLIL_OR => ExprInfo::Or(Operation::new(function, expr_idx, op)),
+        LLIL_XOR => ExprInfo::Xor(Operation::new(function, expr_idx, op)),
+        LLIL_LSL => ExprInfo::Lsl(Operation::new(function, expr_idx, op)),
+        LLIL_LSR => ExprInfo::Lsr(Operation::new(function, expr_idx, op)),
+        LLIL_ASR => ExprInfo::Asr(Operation::new(function, expr_idx, op)),
+        LLIL_ROL => ExprInfo::Rol(Operation::new(function, expr_idx, op)),
+        LLIL_RLC => ExprInfo::Rlc(Operation::new(function, expr_idx, op)),
+        LLIL_ROR => ExprInfo::Ror(Operation::new(function, expr_idx, op)),
+        LLIL_RRC => ExprInfo::Rrc(Operation::new(function, expr_idx, op)),
+        LLIL_MUL => ExprInfo::Mul(Operation::new(function, expr_idx, op)),
+
+        LLIL_MULU_DP => ExprInfo::MuluDp(Operation::new(function, expr_idx, op)),
+        LLIL_MULS_DP => ExprInfo::MulsDp(Operation::new(function, expr_idx, op)),
+
+        LLIL_DIVU => ExprInfo::Divu(Operation::new(function, expr_idx, op)),
+        LLIL_DIVS => ExprInfo::Divs(Operation::new(function, expr_idx, op)),
+
+        LLIL_DIVU_DP => ExprInfo::DivuDp(Operation::new(function, expr_idx, op)),
+        LLIL_DIVS_DP => ExprInfo::DivsDp(Operation::new(function, expr_idx, op)),
+
+        LLIL_MODU => ExprInfo::Modu(Operation::new(function, expr_idx, op)),
+        LLIL_MODS => ExprInfo::Mods(Operation::new(function, expr_idx, op)),
+
+        LLIL_MODU_DP => ExprInfo::ModuDp(Operation::new(function, expr_idx, op)),
+        LLIL_MODS_DP => ExprInfo::ModsDp(Operation::new(function, expr_idx, op)),
+
+        LLIL_NEG => ExprInfo::Neg(Operation::new(function, expr_idx, op)),
+        LLIL_NOT => ExprInfo::Not(Operation::new(function, expr_idx, op)),
+
+        LLIL_SX => ExprInfo::Sx(Operation::new(function, expr_idx, op)),
+        LLIL_ZX => ExprInfo::Zx(Operation::new(function, expr_idx, op)),
+        LLIL_LOW_PART => ExprInfo::LowPart(Operation::new(function, expr_idx, op)),
+
+        LLIL_CMP_E => ExprInfo::CmpE(Operation::new(function, expr_idx, op)),
+        LLIL_CMP_NE => ExprInfo::CmpNe(Operation::new(function, expr_idx, op)),
+        LLIL_CMP_SLT => ExprInfo::CmpSlt(Operation::new(function, expr_idx, op)),
+        LLIL_CMP_ULT => ExprInfo::CmpUlt(Operation::new(function, expr_idx, op)),
+        LLIL_CMP_SLE => ExprInfo::CmpSle(Operation::new(function, expr_idx, op)),
+        LLIL_CMP_ULE => ExprInfo::CmpUle(Operation::new(function, expr_idx, op)),
+        LLIL_CMP_SGE => ExprInfo::CmpSge(Operation::new(function, expr_idx, op)),
+        LLIL_CMP_UGE => ExprInfo::CmpUge(Operation::new(function, expr_idx, op)),
+        LLIL_CMP_SGT => ExprInfo::CmpSgt(Operation::new(function, expr_idx, op)),
+        LLIL_CMP_UGT => ExprInfo::CmpUgt(Operation::new(function, expr_idx, op)),
+
+        LLIL_TEST_BIT => ExprInfo::TestBit(Operation::new(function, expr_idx, op)),
+        LLIL_BOOL_TO_INT => ExprInfo::BoolToInt(Operation::new(function, expr_idx, op)),
+        LLIL_ADD_OVERFLOW => ExprInfo::AddOverflow(Operation::new(function, expr_idx, op)),
+
+        LLIL_FADD => ExprInfo::Fadd(Operation::new(function, expr_idx, op)),
+        LLIL_FSUB => ExprInfo::Fsub(Operation::new(function, expr_idx, op)),
+        LLIL_FMUL => ExprInfo::Fmul(Operation::new(function, expr_idx, op)),
+        LLIL_FDIV => ExprInfo::Fdiv(Operation::new(function, expr_idx, op)),
+
+        LLIL_FSQRT => ExprInfo::Fsqrt(Operation::new(function, expr_idx, op)),
+        LLIL_FNEG => ExprInfo::Fneg(Operation::new(function, expr_idx, op)),
+        LLIL_FABS => ExprInfo::Fabs(Operation::new(function, expr_idx, op)),
+        LLIL_FLOAT_TO_INT => ExprInfo::FloatToInt(Operation::new(function, expr_idx, op)),
+        LLIL_INT_TO_FLOAT => ExprInfo::IntToFloat(Operation::new(function, expr_idx, op)),
+        LLIL_FLOAT_CONV => ExprInfo::FloatConv(Operation::new(function, expr_idx, op)),
+        LLIL_ROUND_TO_INT => ExprInfo::RoundToInt(Operation::new(function, expr_idx, op)),
+        LLIL_FLOOR => ExprInfo::Floor(Operation::new(function, expr_idx, op)),
+        LLIL_CEIL => ExprInfo::Ceil(Operation::new(function, expr_idx, op)),
+        LLIL_FTRUNC => ExprInfo::Ftrunc(Operation::new(function, expr_idx, op)),
+
+        LLIL_FCMP_E => ExprInfo::FcmpE(Operation::new(function, expr_idx, op)),
+        LLIL_FCMP_NE => ExprInfo::FcmpNe(Operation::new(function, expr_idx, op)),
+        LLIL_FCMP_LT => ExprInfo::FcmpLt(Operation::new(function, expr_idx, op)),
+        LLIL_FCMP_LE => ExprInfo::FcmpLe(Operation::new(function, expr_idx, op)),
+        LLIL_FCMP_GE => ExprInfo::FcmpGe(Operation::new(function, expr_idx, op)),
+        LLIL_FCMP_GT => ExprInfo::FcmpGt(Operation::new(function, expr_idx, op)),
+        LLIL_FCMP_O => ExprInfo::FcmpO(Operation::new(function, expr_idx, op)),
+        LLIL_FCMP_UO => ExprInfo::FcmpUo(Operation::new(function, expr_idx, op)),
+
+        LLIL_UNIMPL => ExprInfo::Unimpl(Operation::new(function, expr_idx, op)),
+        LLIL_UNIMPL_MEM => ExprInfo::UnimplMem(Operation::new(function, expr_idx, op)),
+
         _ => {
             #[cfg(debug_assertions)]
             {
@@ -154,7 +185,7 @@ where
                 );
             }
 
-            ExprInfo::Undef(Operation::new(function, op))
+            ExprInfo::Undef(Operation::new(function, expr_idx, op))
         }
     }
 }
@@ -178,43 +209,21 @@ where
 {
     use self::ExprInfo::*;
 
-    match *info {
-        CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
-        | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op) | CmpUgt(ref op) => {
-            visit!(f, &op.left());
-            visit!(f, &op.right());
-        }
-
-        Adc(ref op) | Sbb(ref op) | Rlc(ref op) | Rrc(ref op) => {
-            visit!(f, &op.left());
-            visit!(f, &op.right());
-            visit!(f, &op.carry());
-        }
-
-        Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
-        | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op) | MulsDp(ref op)
-        | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | Modu(ref op) | Mods(ref op) => {
-            visit!(f, &op.left());
-            visit!(f, &op.right());
-        }
-
-        DivuDp(ref op) | DivsDp(ref op) | ModuDp(ref op) | ModsDp(ref op) => {
-            visit!(f, &op.high());
-            visit!(f, &op.low());
-            visit!(f, &op.right());
-        }
-
-        Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
-        | BoolToInt(ref op) => {
-            visit!(f, &op.operand());
-        }
-
-        UnimplMem(ref op) => {
-            visit!(f, &op.mem_expr());
-        }
-
-        _ => {}
-    };
+    if let Some(op) = info.as_cmp_op() {
+        visit!(f, &op.left());
+        visit!(f, &op.right());
+    } else if let Some(op) = info.as_binary_op() {
+        visit!(f, &op.left());
+        visit!(f, &op.right());
+    } else if let Some(op) = info.as_binary_op_carry() {
+        visit!(f, &op.left());
+        visit!(f, &op.right());
+        visit!(f, &op.carry());
+    } else if let Some(op) = info.as_unary_op() {
+        visit!(f, &op.operand());
+    } else if let UnimplMem(ref op) = *info {
+        visit!(f, &op.mem_expr());
+    }
 
     VisitorAction::Sibling
 }
@@ -231,15 +240,24 @@ where
     ) -> ExprInfo<'func, A, M, NonSSA<V>> {
         use binaryninjacore_sys::BNLowLevelILOperation::*;
 
+        let expr_idx = self.expr_idx;
+
         match op.operation {
-            LLIL_LOAD => ExprInfo::Load(Operation::new(self.function, op)),
-            LLIL_POP => ExprInfo::Pop(Operation::new(self.function, op)),
-            LLIL_REG => ExprInfo::Reg(Operation::new(self.function, op)),
-            LLIL_FLAG => ExprInfo::Flag(Operation::new(self.function, op)),
-            LLIL_FLAG_BIT => ExprInfo::FlagBit(Operation::new(self.function, op)),
-            LLIL_FLAG_COND => ExprInfo::FlagCond(Operation::new(self.function, op)), // TODO lifted only
-            LLIL_FLAG_GROUP => ExprInfo::FlagGroup(Operation::new(self.function, op)), // TODO lifted only
-            _ => common_info(self.function, op),
+            LLIL_LOAD => ExprInfo::Load(Operation::new(self.function, expr_idx, op)),
+            LLIL_POP => ExprInfo::Pop(Operation::new(self.function, expr_idx, op)),
+            LLIL_REG => ExprInfo::Reg(Operation::new(self.function, expr_idx, op)),
+            LLIL_REG_SPLIT => ExprInfo::RegSplit(Operation::new(self.function, expr_idx, op)),
+            LLIL_REG_STACK_REL => {
+                ExprInfo::RegStackRel(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_REG_STACK_POP => {
+                ExprInfo::RegStackPop(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_FLAG => ExprInfo::Flag(Operation::new(self.function, expr_idx, op)),
+            LLIL_FLAG_BIT => ExprInfo::FlagBit(Operation::new(self.function, expr_idx, op)),
+            LLIL_FLAG_COND => ExprInfo::FlagCond(Operation::new(self.function, expr_idx, op)), // TODO lifted only
+            LLIL_FLAG_GROUP => ExprInfo::FlagGroup(Operation::new(self.function, expr_idx, op)), // TODO lifted only
+            _ => common_info(self.function, expr_idx, op),
         }
     }
 
@@ -265,6 +283,7 @@ where
 
         match info {
             Load(ref op) => visit!(Self::visit_tree, &op.source_mem_expr(), f),
+            RegStackRel(ref op) => visit!(Self::visit_tree, &op.source_entry(), f),
             _ => {
                 let mut fb = |e: &Self| e.visit_tree(f);
                 visit!(common_visit, &info, &mut fb);
@@ -286,12 +305,23 @@ where
     ) -> ExprInfo<'func, A, M, SSA> {
         use binaryninjacore_sys::BNLowLevelILOperation::*;
 
+        let expr_idx = self.expr_idx;
+
         match op.operation {
-            LLIL_LOAD_SSA => ExprInfo::Load(Operation::new(self.function, op)),
-            LLIL_REG_SSA | LLIL_REG_SSA_PARTIAL => ExprInfo::Reg(Operation::new(self.function, op)),
-            LLIL_FLAG_SSA => ExprInfo::Flag(Operation::new(self.function, op)),
-            LLIL_FLAG_BIT_SSA => ExprInfo::FlagBit(Operation::new(self.function, op)),
-            _ => common_info(self.function, op),
+            LLIL_LOAD_SSA => ExprInfo::Load(Operation::new(self.function, expr_idx, op)),
+            LLIL_REG_SSA | LLIL_REG_SSA_PARTIAL => {
+                ExprInfo::Reg(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_REG_SPLIT_SSA => ExprInfo::RegSplit(Operation::new(self.function, expr_idx, op)),
+            LLIL_REG_STACK_REL_SSA => {
+                ExprInfo::RegStackRel(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_REG_STACK_ABS_SSA => {
+                ExprInfo::RegStackAbs(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_FLAG_SSA => ExprInfo::Flag(Operation::new(self.function, expr_idx, op)),
+            LLIL_FLAG_BIT_SSA => ExprInfo::FlagBit(Operation::new(self.function, expr_idx, op)),
+            _ => common_info(self.function, expr_idx, op),
         }
     }
 
@@ -316,8 +346,8 @@ where
         };
 
         match info {
-            // TODO ssa
-            Load(ref _op) => {} //visit!(Self::visit_tree, &op.source_mem_expr(), f),
+            Load(ref op) => visit!(Self::visit_tree, &op.source_mem_expr(), f),
+            RegStackRel(ref op) => visit!(Self::visit_tree, &op.source_entry(), f),
             _ => {
                 let mut fb = |e: &Self| e.visit_tree(f);
                 visit!(common_visit, &info, &mut fb);
@@ -333,7 +363,31 @@ where
     A: 'func + Architecture,
     F: FunctionForm,
 {
-    // TODO possible values
+    /// Returns the value of this expression, if dataflow analysis could
+    /// determine one
+    pub fn value(&self) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILExprValue;
+
+        let value = unsafe { BNGetLowLevelILExprValue(self.function.handle, self.expr_idx) };
+
+        RegisterValue::from_raw(value)
+    }
+
+    /// Returns every value dataflow analysis determined this expression may take
+    pub fn possible_values(&self) -> PossibleValueSet {
+        use binaryninjacore_sys::BNGetLowLevelILPossibleExprValues;
+
+        unsafe {
+            let values = BNGetLowLevelILPossibleExprValues(
+                self.function.handle,
+                self.expr_idx,
+                ptr::null_mut(),
+                0,
+            );
+
+            PossibleValueSet::from_raw(values)
+        }
+    }
 }
 
 pub enum ExprInfo<'func, A, M, F>
@@ -345,8 +399,14 @@ where
     Load(Operation<'func, A, M, F, operation::Load>),
     Pop(Operation<'func, A, M, F, operation::Pop>),
     Reg(Operation<'func, A, M, F, operation::Reg>),
+    RegSplit(Operation<'func, A, M, F, operation::RegSplit>),
+    RegStackRel(Operation<'func, A, M, F, operation::RegStackRel>),
+    RegStackPop(Operation<'func, A, M, F, operation::RegStackPop>), // not present in SSA form
+    RegStackAbs(Operation<'func, A, M, F, operation::RegStackAbs>), // only present in SSA form
     Const(Operation<'func, A, M, F, operation::Const>),
     ConstPtr(Operation<'func, A, M, F, operation::Const>),
+    ExternPtr(Operation<'func, A, M, F, operation::ExternPtr>),
+    FloatConst(Operation<'func, A, M, F, operation::FloatConst>),
     Flag(Operation<'func, A, M, F, operation::Flag>),
     FlagBit(Operation<'func, A, M, F, operation::FlagBit>),
 
@@ -372,14 +432,14 @@ where
     Divu(Operation<'func, A, M, F, operation::BinaryOp>),
     Divs(Operation<'func, A, M, F, operation::BinaryOp>),
 
-    DivuDp(Operation<'func, A, M, F, operation::DoublePrecDivOp>),
-    DivsDp(Operation<'func, A, M, F, operation::DoublePrecDivOp>),
+    DivuDp(Operation<'func, A, M, F, operation::BinaryOp>),
+    DivsDp(Operation<'func, A, M, F, operation::BinaryOp>),
 
     Modu(Operation<'func, A, M, F, operation::BinaryOp>),
     Mods(Operation<'func, A, M, F, operation::BinaryOp>),
 
-    ModuDp(Operation<'func, A, M, F, operation::DoublePrecDivOp>),
-    ModsDp(Operation<'func, A, M, F, operation::DoublePrecDivOp>),
+    ModuDp(Operation<'func, A, M, F, operation::BinaryOp>),
+    ModsDp(Operation<'func, A, M, F, operation::BinaryOp>),
 
     Neg(Operation<'func, A, M, F, operation::UnaryOp>),
     Not(Operation<'func, A, M, F, operation::UnaryOp>),
@@ -401,10 +461,35 @@ where
     CmpSgt(Operation<'func, A, M, F, operation::Condition>),
     CmpUgt(Operation<'func, A, M, F, operation::Condition>),
 
-    //TestBit(Operation<'func, A, M, F, operation::TestBit>), // TODO
+    TestBit(Operation<'func, A, M, F, operation::Condition>),
     BoolToInt(Operation<'func, A, M, F, operation::UnaryOp>),
+    AddOverflow(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Fadd(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fsub(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fmul(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fdiv(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Fsqrt(Operation<'func, A, M, F, operation::UnaryOp>),
+    Fneg(Operation<'func, A, M, F, operation::UnaryOp>),
+    Fabs(Operation<'func, A, M, F, operation::UnaryOp>),
+    FloatToInt(Operation<'func, A, M, F, operation::UnaryOp>),
+    IntToFloat(Operation<'func, A, M, F, operation::UnaryOp>),
+    FloatConv(Operation<'func, A, M, F, operation::UnaryOp>),
+    RoundToInt(Operation<'func, A, M, F, operation::UnaryOp>),
+    Floor(Operation<'func, A, M, F, operation::UnaryOp>),
+    Ceil(Operation<'func, A, M, F, operation::UnaryOp>),
+    Ftrunc(Operation<'func, A, M, F, operation::UnaryOp>),
+
+    FcmpE(Operation<'func, A, M, F, operation::Condition>),
+    FcmpNe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpLt(Operation<'func, A, M, F, operation::Condition>),
+    FcmpLe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpGe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpGt(Operation<'func, A, M, F, operation::Condition>),
+    FcmpO(Operation<'func, A, M, F, operation::Condition>),
+    FcmpUo(Operation<'func, A, M, F, operation::Condition>),
 
-    // TODO ADD_OVERFLOW
     Unimpl(Operation<'func, A, M, F, operation::NoArgs>),
     UnimplMem(Operation<'func, A, M, F, operation::UnimplMem>),
 
@@ -427,13 +512,11 @@ where
         match *self {
             Undef(..) | Unimpl(..) => None,
 
-            FlagCond(..) | FlagGroup(..) | CmpE(..) | CmpNe(..) | CmpSlt(..) | CmpUlt(..)
-            | CmpSle(..) | CmpUle(..) | CmpSge(..) | CmpUge(..) | CmpSgt(..) | CmpUgt(..) => {
-                Some(0)
-            }
+            FlagCond(..) | FlagGroup(..) => Some(0),
+
+            _ if self.as_cmp_op().is_some() => Some(0),
 
             _ => Some(self.raw_struct().size),
-            //TestBit(Operation<'func, A, M, F, operation::TestBit>), // TODO
         }
     }
 
@@ -459,7 +542,9 @@ where
         match *self {
             CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
             | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op)
-            | CmpUgt(ref op) => Some(op),
+            | CmpUgt(ref op) | TestBit(ref op) | FcmpE(ref op) | FcmpNe(ref op)
+            | FcmpLt(ref op) | FcmpLe(ref op) | FcmpGe(ref op) | FcmpGt(ref op) | FcmpO(ref op)
+            | FcmpUo(ref op) => Some(op),
             _ => None,
         }
     }
@@ -470,8 +555,11 @@ where
         match *self {
             Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
             | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
-            | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | Modu(ref op)
-            | Mods(ref op) => Some(op),
+            | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | DivuDp(ref op)
+            | DivsDp(ref op) | Modu(ref op) | Mods(ref op) | ModuDp(ref op) | ModsDp(ref op)
+            | AddOverflow(ref op) | Fadd(ref op) | Fsub(ref op) | Fmul(ref op) | Fdiv(ref op) => {
+                Some(op)
+            }
             _ => None,
         }
     }
@@ -487,23 +575,14 @@ where
         }
     }
 
-    pub fn as_double_prec_div_op(
-        &self,
-    ) -> Option<&Operation<'func, A, M, F, operation::DoublePrecDivOp>> {
-        use self::ExprInfo::*;
-
-        match *self {
-            DivuDp(ref op) | DivsDp(ref op) | ModuDp(ref op) | ModsDp(ref op) => Some(op),
-            _ => None,
-        }
-    }
-
     pub fn as_unary_op(&self) -> Option<&Operation<'func, A, M, F, operation::UnaryOp>> {
         use self::ExprInfo::*;
 
         match *self {
             Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
-            | BoolToInt(ref op) => Some(op),
+            | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op)
+            | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op)
+            | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op) => Some(op),
             _ => None,
         }
     }
@@ -519,36 +598,46 @@ where
             FlagCond(ref op) => &op.op,
             FlagGroup(ref op) => &op.op,
 
-            CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
-            | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op)
-            | CmpUgt(ref op) => &op.op,
-
             Load(ref op) => &op.op,
 
             Pop(ref op) => &op.op,
 
             Reg(ref op) => &op.op,
+            RegSplit(ref op) => &op.op,
+            RegStackRel(ref op) => &op.op,
+            RegStackPop(ref op) => &op.op,
+            RegStackAbs(ref op) => &op.op,
 
             Flag(ref op) => &op.op,
 
             FlagBit(ref op) => &op.op,
 
             Const(ref op) | ConstPtr(ref op) => &op.op,
+            ExternPtr(ref op) => &op.op,
+            FloatConst(ref op) => &op.op,
+
+            CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
+            | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op)
+            | CmpUgt(ref op) | TestBit(ref op) | FcmpE(ref op) | FcmpNe(ref op)
+            | FcmpLt(ref op) | FcmpLe(ref op) | FcmpGe(ref op) | FcmpGt(ref op) | FcmpO(ref op)
+            | FcmpUo(ref op) => &op.op,
 
             Adc(ref op) | Sbb(ref op) | Rlc(ref op) | Rrc(ref op) => &op.op,
 
             Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
             | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
-            | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | Modu(ref op)
-            | Mods(ref op) => &op.op,
-
-            DivuDp(ref op) | DivsDp(ref op) | ModuDp(ref op) | ModsDp(ref op) => &op.op,
+            | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | DivuDp(ref op)
+            | DivsDp(ref op) | Modu(ref op) | Mods(ref op) | ModuDp(ref op) | ModsDp(ref op)
+            | AddOverflow(ref op) | Fadd(ref op) | Fsub(ref op) | Fmul(ref op) | Fdiv(ref op) => {
+                &op.op
+            }
 
             Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
-            | BoolToInt(ref op) => &op.op,
+            | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op)
+            | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op)
+            | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op) => &op.op,
 
             UnimplMem(ref op) => &op.op,
-            //TestBit(Operation<'func, A, M, F, operation::TestBit>), // TODO
         }
     }
 }
@@ -568,36 +657,46 @@ where
             FlagCond(ref _op) => None,
             FlagGroup(ref _op) => None,
 
-            CmpE(ref _op) | CmpNe(ref _op) | CmpSlt(ref _op) | CmpUlt(ref _op)
-            | CmpSle(ref _op) | CmpUle(ref _op) | CmpSge(ref _op) | CmpUge(ref _op)
-            | CmpSgt(ref _op) | CmpUgt(ref _op) => None,
-
             Load(ref op) => op.flag_write(),
 
             Pop(ref op) => op.flag_write(),
 
             Reg(ref op) => op.flag_write(),
+            RegSplit(ref op) => op.flag_write(),
+            RegStackRel(ref op) => op.flag_write(),
+            RegStackPop(ref op) => op.flag_write(),
+            RegStackAbs(ref op) => op.flag_write(),
 
             Flag(ref op) => op.flag_write(),
 
             FlagBit(ref op) => op.flag_write(),
 
             Const(ref op) | ConstPtr(ref op) => op.flag_write(),
+            ExternPtr(ref op) => op.flag_write(),
+            FloatConst(ref op) => op.flag_write(),
+
+            CmpE(ref _op) | CmpNe(ref _op) | CmpSlt(ref _op) | CmpUlt(ref _op)
+            | CmpSle(ref _op) | CmpUle(ref _op) | CmpSge(ref _op) | CmpUge(ref _op)
+            | CmpSgt(ref _op) | CmpUgt(ref _op) | TestBit(ref _op) | FcmpE(ref _op)
+            | FcmpNe(ref _op) | FcmpLt(ref _op) | FcmpLe(ref _op) | FcmpGe(ref _op)
+            | FcmpGt(ref _op) | FcmpO(ref _op) | FcmpUo(ref _op) => None,
 
             Adc(ref op) | Sbb(ref op) | Rlc(ref op) | Rrc(ref op) => op.flag_write(),
 
             Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
             | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
-            | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | Modu(ref op)
-            | Mods(ref op) => op.flag_write(),
-
-            DivuDp(ref op) | DivsDp(ref op) | ModuDp(ref op) | ModsDp(ref op) => op.flag_write(),
+            | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | DivuDp(ref op)
+            | DivsDp(ref op) | Modu(ref op) | Mods(ref op) | ModuDp(ref op) | ModsDp(ref op)
+            | AddOverflow(ref op) | Fadd(ref op) | Fsub(ref op) | Fmul(ref op) | Fdiv(ref op) => {
+                op.flag_write()
+            }
 
             Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
-            | BoolToInt(ref op) => op.flag_write(),
+            | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op)
+            | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op)
+            | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op) => op.flag_write(),
 
             UnimplMem(ref op) => op.flag_write(),
-            //TestBit(Operation<'func, A, M, F, operation::TestBit>), // TODO
         }
     }
 }
@@ -621,7 +720,9 @@ where
 
             CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
             | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op)
-            | CmpUgt(ref op) => {
+            | CmpUgt(ref op) | TestBit(ref op) | FcmpE(ref op) | FcmpNe(ref op)
+            | FcmpLt(ref op) | FcmpLe(ref op) | FcmpGe(ref op) | FcmpGt(ref op) | FcmpO(ref op)
+            | FcmpUo(ref op) => {
                 let left = op.left();
                 let right = op.right();
 
@@ -660,12 +761,36 @@ where
                 }
             }
 
+            RegSplit(ref op) => write!(f, "{:?}:{:?}.{}", op.high_reg(), op.low_reg(), op.size()),
+
+            RegStackRel(ref op) => write!(
+                f,
+                "reg_stack{}[{:?}].{}",
+                op.source_reg_stack_id(),
+                op.source_entry(),
+                op.size()
+            ),
+
+            RegStackPop(ref op) => write!(
+                f,
+                "reg_stack_pop(reg_stack{}).{}",
+                op.source_reg_stack_id(),
+                op.size()
+            ),
+
+            // only present in SSA form
+            RegStackAbs(..) => f.write_str("reg_stack_abs"),
+
             Flag(ref _op) => write!(f, "flag"), // TODO
 
             FlagBit(ref _op) => write!(f, "flag_bit"), // TODO
 
             Const(ref op) | ConstPtr(ref op) => write!(f, "0x{:x}", op.value()),
 
+            ExternPtr(ref op) => write!(f, "extern(0x{:x} + 0x{:x})", op.value(), op.offset()),
+
+            FloatConst(ref op) => write!(f, "{}", op.value()),
+
             Adc(ref op) | Sbb(ref op) | Rlc(ref op) | Rrc(ref op) => {
                 let left = op.left();
                 let right = op.right();
@@ -684,8 +809,9 @@ where
 
             Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
             | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
-            | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | Modu(ref op)
-            | Mods(ref op) => {
+            | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | DivuDp(ref op)
+            | DivsDp(ref op) | Modu(ref op) | Mods(ref op) | ModuDp(ref op) | ModsDp(ref op)
+            | AddOverflow(ref op) | Fadd(ref op) | Fsub(ref op) | Fmul(ref op) | Fdiv(ref op) => {
                 let left = op.left();
                 let right = op.right();
 
@@ -699,24 +825,10 @@ where
                 )
             }
 
-            DivuDp(ref op) | DivsDp(ref op) | ModuDp(ref op) | ModsDp(ref op) => {
-                let high = op.high();
-                let low = op.low();
-                let right = op.right();
-
-                write!(
-                    f,
-                    "{:?}({}, {:?}:{:?},{:?})",
-                    op.op.operation,
-                    op.size(),
-                    high,
-                    low,
-                    right
-                )
-            }
-
             Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
-            | BoolToInt(ref op) => write!(
+            | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op)
+            | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op)
+            | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op) => write!(
                 f,
                 "{:?}({}, {:?})",
                 op.op.operation,
@@ -725,7 +837,6 @@ where
             ),
 
             UnimplMem(ref op) => write!(f, "unimplemented_mem({:?})", op.mem_expr()),
-            //TestBit(Operation<'func, A, M, F, operation::TestBit>), // TODO
         }
     }
 }
@@ -18,6 +18,7 @@ use binaryninjacore_sys::BNNewLowLevelILFunctionReference;
 
 use std::borrow::Borrow;
 use std::marker::PhantomData;
+use std::slice;
 
 use crate::basicblock::BasicBlock;
 use crate::rc::*;
@@ -139,6 +140,40 @@ where
             BNGetLowLevelILInstructionCount(self.handle)
         }
     }
+
+    pub(crate) fn instruction_list(
+        &self,
+        list: *mut usize,
+        count: usize,
+    ) -> Vec<Instruction<A, M, F>> {
+        use binaryninjacore_sys::BNFreeILInstructionList;
+
+        unsafe {
+            let result = slice::from_raw_parts(list, count)
+                .iter()
+                .map(|&instr_idx| Instruction {
+                    function: self,
+                    instr_idx,
+                })
+                .collect();
+
+            BNFreeILInstructionList(list);
+
+            result
+        }
+    }
+
+    // the core reports a missing definition as an out of range index
+    fn instruction_if_valid(&self, instr_idx: usize) -> Option<Instruction<A, M, F>> {
+        if instr_idx >= self.instruction_count() {
+            None
+        } else {
+            Some(Instruction {
+                function: self,
+                instr_idx,
+            })
+        }
+    }
 }
 
 // LLIL basic blocks are not available until the function object
@@ -162,6 +197,135 @@ where
     }
 }
 
+impl<'func, A> Function<A, Finalized, NonSSA<RegularNonSSA>>
+where
+    A: 'func + Architecture,
+{
+    pub fn ssa_form(&self) -> Result<Ref<Function<A, Finalized, SSA>>, ()> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAForm;
+
+        unsafe {
+            let ssa = BNGetLowLevelILSSAForm(self.handle);
+
+            if ssa.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(Function::from_raw(self.borrower.clone(), ssa)))
+        }
+    }
+}
+
+impl<'func, A> Function<A, Finalized, SSA>
+where
+    A: 'func + Architecture,
+{
+    pub fn non_ssa_form(&self) -> Result<Ref<Function<A, Finalized, NonSSA<RegularNonSSA>>>, ()> {
+        use binaryninjacore_sys::BNGetLowLevelILNonSSAForm;
+
+        unsafe {
+            let non_ssa = BNGetLowLevelILNonSSAForm(self.handle);
+
+            if non_ssa.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(Function::from_raw(self.borrower.clone(), non_ssa)))
+        }
+    }
+
+    /// Returns the instruction that defines the given version of a register
+    ///
+    /// Registers that are never written within the function (such as
+    /// incoming arguments at version 0) have no definition. Partial
+    /// registers are looked up through their full width register.
+    pub fn get_ssa_reg_definition(
+        &self,
+        reg: &SSARegister<A::Register>,
+    ) -> Option<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSARegisterDefinition;
+
+        let instr_idx = unsafe {
+            BNGetLowLevelILSSARegisterDefinition(self.handle, reg.id(), reg.version() as usize)
+        };
+
+        self.instruction_if_valid(instr_idx)
+    }
+
+    pub fn get_ssa_reg_uses(
+        &self,
+        reg: &SSARegister<A::Register>,
+    ) -> Vec<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSARegisterUses;
+
+        unsafe {
+            let mut count = 0;
+            let instrs = BNGetLowLevelILSSARegisterUses(
+                self.handle,
+                reg.id(),
+                reg.version() as usize,
+                &mut count,
+            );
+
+            self.instruction_list(instrs, count)
+        }
+    }
+
+    pub fn get_ssa_flag_definition(
+        &self,
+        flag: &SSAFlag<A::Flag>,
+    ) -> Option<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAFlagDefinition;
+
+        let instr_idx = unsafe {
+            BNGetLowLevelILSSAFlagDefinition(self.handle, flag.flag.id(), flag.version as usize)
+        };
+
+        self.instruction_if_valid(instr_idx)
+    }
+
+    pub fn get_ssa_flag_uses(
+        &self,
+        flag: &SSAFlag<A::Flag>,
+    ) -> Vec<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAFlagUses;
+
+        unsafe {
+            let mut count = 0;
+            let instrs = BNGetLowLevelILSSAFlagUses(
+                self.handle,
+                flag.flag.id(),
+                flag.version as usize,
+                &mut count,
+            );
+
+            self.instruction_list(instrs, count)
+        }
+    }
+
+    pub fn get_ssa_memory_definition(
+        &self,
+        version: usize,
+    ) -> Option<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAMemoryDefinition;
+
+        let instr_idx = unsafe { BNGetLowLevelILSSAMemoryDefinition(self.handle, version) };
+
+        self.instruction_if_valid(instr_idx)
+    }
+
+    pub fn get_ssa_memory_uses(&self, version: usize) -> Vec<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAMemoryUses;
+
+        unsafe {
+            let mut count = 0;
+            let instrs = BNGetLowLevelILSSAMemoryUses(self.handle, version, &mut count);
+
+            self.instruction_list(instrs, count)
+        }
+    }
+}
+
 impl<'func, A, M, F> ToOwned for Function<A, M, F>
 where
     A: 'func + Architecture,
@@ -17,12 +17,14 @@ use binaryninjacore_sys::BNGetLowLevelILIndexForInstruction;
 use binaryninjacore_sys::BNLowLevelILInstruction;
 
 use std::marker::PhantomData;
+use std::ptr;
 
 use super::operation;
 use super::operation::Operation;
 use super::*;
 
 use crate::architecture::Architecture;
+use crate::types::{PossibleValueSet, RegisterValue};
 
 pub struct Instruction<'func, A, M, F>
 where
@@ -36,6 +38,7 @@ where
 
 fn common_info<'func, A, M, F>(
     function: &'func Function<A, M, F>,
+    expr_idx: usize,
     op: BNLowLevelILInstruction,
 ) -> Option<InstrInfo<'func, A, M, F>>
 where
@@ -46,16 +49,16 @@ where
     use binaryninjacore_sys::BNLowLevelILOperation::*;
 
     match op.operation {
-        LLIL_NOP => InstrInfo::Nop(Operation::new(function, op)).into(),
-        LLIL_JUMP => InstrInfo::Jump(Operation::new(function, op)).into(),
-        LLIL_JUMP_TO => InstrInfo::JumpTo(Operation::new(function, op)).into(),
-        LLIL_RET => InstrInfo::Ret(Operation::new(function, op)).into(),
-        LLIL_NORET => InstrInfo::NoRet(Operation::new(function, op)).into(),
-        LLIL_IF => InstrInfo::If(Operation::new(function, op)).into(),
-        LLIL_GOTO => InstrInfo::Goto(Operation::new(function, op)).into(),
-        LLIL_BP => InstrInfo::Bp(Operation::new(function, op)).into(),
-        LLIL_TRAP => InstrInfo::Trap(Operation::new(function, op)).into(),
-        LLIL_UNDEF => InstrInfo::Undef(Operation::new(function, op)).into(),
+        LLIL_NOP => InstrInfo::Nop(Operation::new(function, expr_idx, op)).into(),
+        LLIL_JUMP => InstrInfo::Jump(Operation::new(function, expr_idx, op)).into(),
+        LLIL_JUMP_TO => InstrInfo::JumpTo(Operation::new(function, expr_idx, op)).into(),
+        LLIL_RET => InstrInfo::Ret(Operation::new(function, expr_idx, op)).into(),
+        LLIL_NORET => InstrInfo::NoRet(Operation::new(function, expr_idx, op)).into(),
+        LLIL_IF => InstrInfo::If(Operation::new(function, expr_idx, op)).into(),
+        LLIL_GOTO => InstrInfo::Goto(Operation::new(function, expr_idx, op)).into(),
+        LLIL_BP => InstrInfo::Bp(Operation::new(function, expr_idx, op)).into(),
+        LLIL_TRAP => InstrInfo::Trap(Operation::new(function, expr_idx, op)).into(),
+        LLIL_UNDEF => InstrInfo::Undef(Operation::new(function, expr_idx, op)).into(),
         _ => None,
     }
 }
@@ -105,17 +108,33 @@ where
         let op = unsafe { BNGetLowLevelILByIndex(self.function.handle, expr_idx) };
 
         match op.operation {
-            LLIL_SET_REG => InstrInfo::SetReg(Operation::new(self.function, op)),
-            LLIL_SET_REG_SPLIT => InstrInfo::SetRegSplit(Operation::new(self.function, op)),
-            LLIL_SET_FLAG => InstrInfo::SetFlag(Operation::new(self.function, op)),
-            LLIL_STORE => InstrInfo::Store(Operation::new(self.function, op)),
-            LLIL_PUSH => InstrInfo::Push(Operation::new(self.function, op)),
+            LLIL_SET_REG => InstrInfo::SetReg(Operation::new(self.function, expr_idx, op)),
+            LLIL_SET_REG_SPLIT => {
+                InstrInfo::SetRegSplit(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_SET_FLAG => InstrInfo::SetFlag(Operation::new(self.function, expr_idx, op)),
+            LLIL_SET_REG_STACK_REL => {
+                InstrInfo::SetRegStackRel(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_REG_STACK_PUSH => {
+                InstrInfo::RegStackPush(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_REG_STACK_FREE_REL => {
+                InstrInfo::RegStackFreeRel(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_REG_STACK_FREE_REG => {
+                InstrInfo::RegStackFreeReg(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_STORE => InstrInfo::Store(Operation::new(self.function, expr_idx, op)),
+            LLIL_PUSH => InstrInfo::Push(Operation::new(self.function, expr_idx, op)),
             LLIL_CALL | LLIL_CALL_STACK_ADJUST => {
-                InstrInfo::Call(Operation::new(self.function, op))
+                InstrInfo::Call(Operation::new(self.function, expr_idx, op))
             }
-            LLIL_SYSCALL => InstrInfo::Syscall(Operation::new(self.function, op)),
+            LLIL_TAILCALL => InstrInfo::Tailcall(Operation::new(self.function, expr_idx, op)),
+            LLIL_SYSCALL => InstrInfo::Syscall(Operation::new(self.function, expr_idx, op)),
+            LLIL_INTRINSIC => InstrInfo::Intrinsic(Operation::new(self.function, expr_idx, op)),
             _ => {
-                common_info(self.function, op).unwrap_or_else(|| {
+                common_info(self.function, expr_idx, op).unwrap_or_else(|| {
                     // Hopefully this is a bare value. If it isn't (expression
                     // from wrong function form or similar) it won't really cause
                     // any problems as it'll come back as undefined when queried.
@@ -149,12 +168,129 @@ where
             SetReg(ref op) => visit!(fb, &op.source_expr()),
             SetRegSplit(ref op) => visit!(fb, &op.source_expr()),
             SetFlag(ref op) => visit!(fb, &op.source_expr()),
+            SetRegStackRel(ref op) => {
+                visit!(fb, &op.dest_entry());
+                visit!(fb, &op.source_expr());
+            }
+            RegStackPush(ref op) => visit!(fb, &op.source_expr()),
+            RegStackFreeRel(ref op) => visit!(fb, &op.dest_entry()),
             Store(ref op) => {
                 visit!(fb, &op.dest_mem_expr());
                 visit!(fb, &op.source_expr());
             }
             Push(ref op) => visit!(fb, &op.operand()),
-            Call(ref op) => visit!(fb, &op.target()),
+            Call(ref op) | Tailcall(ref op) => visit!(fb, &op.target()),
+            Intrinsic(ref op) => {
+                for param in op.params() {
+                    visit!(fb, &param);
+                }
+            }
+            _ => visit!(common_visit, &info, fb),
+        }
+
+        VisitorAction::Sibling
+    }
+}
+
+impl<'func, A, M> Instruction<'func, A, M, SSA>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn info(&self) -> InstrInfo<'func, A, M, SSA> {
+        use binaryninjacore_sys::BNLowLevelILOperation::*;
+
+        let expr_idx =
+            unsafe { BNGetLowLevelILIndexForInstruction(self.function.handle, self.instr_idx) };
+        let op = unsafe { BNGetLowLevelILByIndex(self.function.handle, expr_idx) };
+
+        match op.operation {
+            LLIL_SET_REG_SSA | LLIL_SET_REG_SSA_PARTIAL => {
+                InstrInfo::SetReg(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_SET_REG_SPLIT_SSA => {
+                InstrInfo::SetRegSplit(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_SET_FLAG_SSA => InstrInfo::SetFlag(Operation::new(self.function, expr_idx, op)),
+            LLIL_SET_REG_STACK_REL_SSA => {
+                InstrInfo::SetRegStackRel(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_SET_REG_STACK_ABS_SSA => {
+                InstrInfo::SetRegStackAbs(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_REG_STACK_FREE_REL_SSA => {
+                InstrInfo::RegStackFreeRel(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_REG_STACK_FREE_ABS_SSA => {
+                InstrInfo::RegStackFreeReg(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_STORE_SSA => InstrInfo::Store(Operation::new(self.function, expr_idx, op)),
+            LLIL_CALL_SSA => InstrInfo::Call(Operation::new(self.function, expr_idx, op)),
+            LLIL_TAILCALL_SSA => InstrInfo::Tailcall(Operation::new(self.function, expr_idx, op)),
+            LLIL_SYSCALL_SSA => InstrInfo::Syscall(Operation::new(self.function, expr_idx, op)),
+            LLIL_INTRINSIC_SSA => InstrInfo::Intrinsic(Operation::new(self.function, expr_idx, op)),
+            LLIL_REG_PHI => InstrInfo::RegPhi(Operation::new(self.function, expr_idx, op)),
+            LLIL_REG_STACK_PHI => {
+                InstrInfo::RegStackPhi(Operation::new(self.function, expr_idx, op))
+            }
+            LLIL_FLAG_PHI => InstrInfo::FlagPhi(Operation::new(self.function, expr_idx, op)),
+            LLIL_MEM_PHI => InstrInfo::MemPhi(Operation::new(self.function, expr_idx, op)),
+            _ => common_info(self.function, expr_idx, op).unwrap_or_else(|| {
+                let expr = Expression {
+                    function: self.function,
+                    expr_idx,
+                    _ty: PhantomData,
+                };
+
+                let info = unsafe { expr.info_from_op(op) };
+
+                InstrInfo::Value(expr, info)
+            }),
+        }
+    }
+
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(
+            &Expression<'func, A, M, SSA, ValueExpr>,
+            &ExprInfo<'func, A, M, SSA>,
+        ) -> VisitorAction,
+    {
+        use self::InstrInfo::*;
+        let info = self.info();
+
+        let fb = &mut |e: &Expression<'func, A, M, SSA, ValueExpr>| e.visit_tree(f);
+
+        match info {
+            SetReg(ref op) => visit!(fb, &op.source_expr()),
+            SetRegSplit(ref op) => visit!(fb, &op.source_expr()),
+            SetFlag(ref op) => visit!(fb, &op.source_expr()),
+            SetRegStackRel(ref op) => {
+                visit!(fb, &op.dest_entry());
+                visit!(fb, &op.source_expr());
+            }
+            SetRegStackAbs(ref op) => visit!(fb, &op.source_expr()),
+            RegStackFreeRel(ref op) => visit!(fb, &op.dest_entry()),
+            Store(ref op) => {
+                visit!(fb, &op.dest_mem_expr());
+                visit!(fb, &op.source_expr());
+            }
+            Call(ref op) | Tailcall(ref op) => {
+                visit!(fb, &op.target());
+                for param in op.params() {
+                    visit!(fb, &param);
+                }
+            }
+            Syscall(ref op) => {
+                for param in op.params() {
+                    visit!(fb, &param);
+                }
+            }
+            Intrinsic(ref op) => {
+                for param in op.params() {
+                    visit!(fb, &param);
+                }
+            }
             _ => visit!(common_visit, &info, fb),
         }
 
@@ -162,6 +298,97 @@ where
     }
 }
 
+impl<'func, A, F> Instruction<'func, A, Finalized, F>
+where
+    A: 'func + Architecture,
+    F: FunctionForm,
+{
+    pub fn register_value_before(&self, reg: A::Register) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILRegisterValueAtInstruction;
+
+        let value = unsafe {
+            BNGetLowLevelILRegisterValueAtInstruction(
+                self.function.handle,
+                reg.id(),
+                self.instr_idx,
+            )
+        };
+
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn register_value_after(&self, reg: A::Register) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILRegisterValueAfterInstruction;
+
+        let value = unsafe {
+            BNGetLowLevelILRegisterValueAfterInstruction(
+                self.function.handle,
+                reg.id(),
+                self.instr_idx,
+            )
+        };
+
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn possible_register_values_before(&self, reg: A::Register) -> PossibleValueSet {
+        use binaryninjacore_sys::BNGetLowLevelILPossibleRegisterValuesAtInstruction;
+
+        unsafe {
+            let values = BNGetLowLevelILPossibleRegisterValuesAtInstruction(
+                self.function.handle,
+                reg.id(),
+                self.instr_idx,
+                ptr::null_mut(),
+                0,
+            );
+
+            PossibleValueSet::from_raw(values)
+        }
+    }
+
+    pub fn possible_register_values_after(&self, reg: A::Register) -> PossibleValueSet {
+        use binaryninjacore_sys::BNGetLowLevelILPossibleRegisterValuesAfterInstruction;
+
+        unsafe {
+            let values = BNGetLowLevelILPossibleRegisterValuesAfterInstruction(
+                self.function.handle,
+                reg.id(),
+                self.instr_idx,
+                ptr::null_mut(),
+                0,
+            );
+
+            PossibleValueSet::from_raw(values)
+        }
+    }
+
+    /// Returns the value of `flag` before this instruction executes
+    pub fn flag_value_at(&self, flag: A::Flag) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILFlagValueAtInstruction;
+
+        let value = unsafe {
+            BNGetLowLevelILFlagValueAtInstruction(self.function.handle, flag.id(), self.instr_idx)
+        };
+
+        RegisterValue::from_raw(value)
+    }
+
+    pub fn flag_value_after(&self, flag: A::Flag) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILFlagValueAfterInstruction;
+
+        let value = unsafe {
+            BNGetLowLevelILFlagValueAfterInstruction(
+                self.function.handle,
+                flag.id(),
+                self.instr_idx,
+            )
+        };
+
+        RegisterValue::from_raw(value)
+    }
+}
+
 pub enum InstrInfo<'func, A, M, F>
 where
     A: 'func + Architecture,
@@ -172,6 +399,10 @@ where
     SetReg(Operation<'func, A, M, F, operation::SetReg>),
     SetRegSplit(Operation<'func, A, M, F, operation::SetRegSplit>),
     SetFlag(Operation<'func, A, M, F, operation::SetFlag>),
+    SetRegStackRel(Operation<'func, A, M, F, operation::SetRegStackRel>),
+    RegStackPush(Operation<'func, A, M, F, operation::RegStackPush>), // not present in SSA form
+    RegStackFreeRel(Operation<'func, A, M, F, operation::RegStackFreeRel>),
+    RegStackFreeReg(Operation<'func, A, M, F, operation::RegStackFreeReg>),
     Store(Operation<'func, A, M, F, operation::Store>),
     Push(Operation<'func, A, M, F, operation::UnaryOp>), // TODO needs a real op
 
@@ -179,6 +410,7 @@ where
     JumpTo(Operation<'func, A, M, F, operation::JumpTo>),
 
     Call(Operation<'func, A, M, F, operation::Call>),
+    Tailcall(Operation<'func, A, M, F, operation::Call>),
 
     Ret(Operation<'func, A, M, F, operation::Ret>),
     NoRet(Operation<'func, A, M, F, operation::NoArgs>),
@@ -189,8 +421,16 @@ where
     Syscall(Operation<'func, A, M, F, operation::Syscall>),
     Bp(Operation<'func, A, M, F, operation::NoArgs>),
     Trap(Operation<'func, A, M, F, operation::Trap>),
+    Intrinsic(Operation<'func, A, M, F, operation::Intrinsic>),
     Undef(Operation<'func, A, M, F, operation::NoArgs>),
 
+    // only present in SSA form
+    SetRegStackAbs(Operation<'func, A, M, F, operation::SetRegStackAbs>),
+    RegPhi(Operation<'func, A, M, F, operation::RegPhi>),
+    RegStackPhi(Operation<'func, A, M, F, operation::RegStackPhi>),
+    FlagPhi(Operation<'func, A, M, F, operation::FlagPhi>),
+    MemPhi(Operation<'func, A, M, F, operation::MemPhi>),
+
     Value(
         Expression<'func, A, M, F, ValueExpr>,
         ExprInfo<'func, A, M, F>,
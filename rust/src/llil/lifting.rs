@@ -16,7 +16,9 @@ use std::marker::PhantomData;
 use std::mem;
 
 use crate::architecture::Architecture;
+use crate::architecture::Intrinsic as ArchIntrinsic;
 use crate::architecture::Register as ArchReg;
+use crate::architecture::RegisterStack as ArchRegStack;
 use crate::architecture::{Flag, FlagClass, FlagCondition, FlagGroup, FlagRole, FlagWrite};
 
 use super::*;
@@ -84,6 +86,15 @@ pub enum FlagWriteOp<R: ArchReg> {
     LowPart(usize, RegisterOrConstant<R>),
     BoolToInt(usize, RegisterOrConstant<R>),
     FloatToInt(usize, RegisterOrConstant<R>),
+    Fsqrt(usize, RegisterOrConstant<R>),
+    Fneg(usize, RegisterOrConstant<R>),
+    Fabs(usize, RegisterOrConstant<R>),
+    IntToFloat(usize, RegisterOrConstant<R>),
+    FloatConv(usize, RegisterOrConstant<R>),
+    RoundToInt(usize, RegisterOrConstant<R>),
+    Floor(usize, RegisterOrConstant<R>),
+    Ceil(usize, RegisterOrConstant<R>),
+    Ftrunc(usize, RegisterOrConstant<R>),
 
     Store(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
 
@@ -110,6 +121,31 @@ pub enum FlagWriteOp<R: ArchReg> {
     TestBit(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
     AddOverflow(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
 
+    Fadd(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    Fsub(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    Fmul(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    Fdiv(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+
+    CmpE(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    CmpNe(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    CmpSlt(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    CmpUlt(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    CmpSle(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    CmpUle(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    CmpSge(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    CmpUge(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    CmpSgt(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    CmpUgt(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+
+    FcmpE(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    FcmpNe(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    FcmpLt(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    FcmpLe(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    FcmpGe(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    FcmpGt(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    FcmpO(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+    FcmpUo(usize, RegisterOrConstant<R>, RegisterOrConstant<R>),
+
     Adc(
         usize,
         RegisterOrConstant<R>,
@@ -136,7 +172,6 @@ pub enum FlagWriteOp<R: ArchReg> {
     ),
 
     Pop(usize),
-    // TODO: floating point stuff, llil comparison ops that set flags, intrinsics
 }
 
 impl<R: ArchReg> FlagWriteOp<R> {
@@ -198,6 +233,15 @@ impl<R: ArchReg> FlagWriteOp<R> {
             (1, LLIL_LOW_PART) => op!(LowPart, 0),
             (1, LLIL_BOOL_TO_INT) => op!(BoolToInt, 0),
             (1, LLIL_FLOAT_TO_INT) => op!(FloatToInt, 0),
+            (1, LLIL_FSQRT) => op!(Fsqrt, 0),
+            (1, LLIL_FNEG) => op!(Fneg, 0),
+            (1, LLIL_FABS) => op!(Fabs, 0),
+            (1, LLIL_INT_TO_FLOAT) => op!(IntToFloat, 0),
+            (1, LLIL_FLOAT_CONV) => op!(FloatConv, 0),
+            (1, LLIL_ROUND_TO_INT) => op!(RoundToInt, 0),
+            (1, LLIL_FLOOR) => op!(Floor, 0),
+            (1, LLIL_CEIL) => op!(Ceil, 0),
+            (1, LLIL_FTRUNC) => op!(Ftrunc, 0),
 
             (2, LLIL_STORE) => op!(Store, 0, 1),
 
@@ -224,6 +268,31 @@ impl<R: ArchReg> FlagWriteOp<R> {
             (2, LLIL_TEST_BIT) => op!(TestBit, 0, 1),
             (2, LLIL_ADD_OVERFLOW) => op!(AddOverflow, 0, 1),
 
+            (2, LLIL_FADD) => op!(Fadd, 0, 1),
+            (2, LLIL_FSUB) => op!(Fsub, 0, 1),
+            (2, LLIL_FMUL) => op!(Fmul, 0, 1),
+            (2, LLIL_FDIV) => op!(Fdiv, 0, 1),
+
+            (2, LLIL_CMP_E) => op!(CmpE, 0, 1),
+            (2, LLIL_CMP_NE) => op!(CmpNe, 0, 1),
+            (2, LLIL_CMP_SLT) => op!(CmpSlt, 0, 1),
+            (2, LLIL_CMP_ULT) => op!(CmpUlt, 0, 1),
+            (2, LLIL_CMP_SLE) => op!(CmpSle, 0, 1),
+            (2, LLIL_CMP_ULE) => op!(CmpUle, 0, 1),
+            (2, LLIL_CMP_SGE) => op!(CmpSge, 0, 1),
+            (2, LLIL_CMP_UGE) => op!(CmpUge, 0, 1),
+            (2, LLIL_CMP_SGT) => op!(CmpSgt, 0, 1),
+            (2, LLIL_CMP_UGT) => op!(CmpUgt, 0, 1),
+
+            (2, LLIL_FCMP_E) => op!(FcmpE, 0, 1),
+            (2, LLIL_FCMP_NE) => op!(FcmpNe, 0, 1),
+            (2, LLIL_FCMP_LT) => op!(FcmpLt, 0, 1),
+            (2, LLIL_FCMP_LE) => op!(FcmpLe, 0, 1),
+            (2, LLIL_FCMP_GE) => op!(FcmpGe, 0, 1),
+            (2, LLIL_FCMP_GT) => op!(FcmpGt, 0, 1),
+            (2, LLIL_FCMP_O) => op!(FcmpO, 0, 1),
+            (2, LLIL_FCMP_UO) => op!(FcmpUo, 0, 1),
+
             (3, LLIL_ADC) => op!(Adc, 0, 1, 2),
             (3, LLIL_SBB) => op!(Sbb, 0, 1, 2),
             (3, LLIL_RLC) => op!(Rlc, 0, 1, 2),
@@ -256,6 +325,15 @@ impl<R: ArchReg> FlagWriteOp<R> {
             LowPart(size, ..) => (size, LLIL_LOW_PART),
             BoolToInt(size, ..) => (size, LLIL_BOOL_TO_INT),
             FloatToInt(size, ..) => (size, LLIL_FLOAT_TO_INT),
+            Fsqrt(size, ..) => (size, LLIL_FSQRT),
+            Fneg(size, ..) => (size, LLIL_FNEG),
+            Fabs(size, ..) => (size, LLIL_FABS),
+            IntToFloat(size, ..) => (size, LLIL_INT_TO_FLOAT),
+            FloatConv(size, ..) => (size, LLIL_FLOAT_CONV),
+            RoundToInt(size, ..) => (size, LLIL_ROUND_TO_INT),
+            Floor(size, ..) => (size, LLIL_FLOOR),
+            Ceil(size, ..) => (size, LLIL_CEIL),
+            Ftrunc(size, ..) => (size, LLIL_FTRUNC),
 
             Store(size, ..) => (size, LLIL_STORE),
 
@@ -282,6 +360,31 @@ impl<R: ArchReg> FlagWriteOp<R> {
             TestBit(size, ..) => (size, LLIL_TEST_BIT),
             AddOverflow(size, ..) => (size, LLIL_ADD_OVERFLOW),
 
+            Fadd(size, ..) => (size, LLIL_FADD),
+            Fsub(size, ..) => (size, LLIL_FSUB),
+            Fmul(size, ..) => (size, LLIL_FMUL),
+            Fdiv(size, ..) => (size, LLIL_FDIV),
+
+            CmpE(size, ..) => (size, LLIL_CMP_E),
+            CmpNe(size, ..) => (size, LLIL_CMP_NE),
+            CmpSlt(size, ..) => (size, LLIL_CMP_SLT),
+            CmpUlt(size, ..) => (size, LLIL_CMP_ULT),
+            CmpSle(size, ..) => (size, LLIL_CMP_SLE),
+            CmpUle(size, ..) => (size, LLIL_CMP_ULE),
+            CmpSge(size, ..) => (size, LLIL_CMP_SGE),
+            CmpUge(size, ..) => (size, LLIL_CMP_UGE),
+            CmpSgt(size, ..) => (size, LLIL_CMP_SGT),
+            CmpUgt(size, ..) => (size, LLIL_CMP_UGT),
+
+            FcmpE(size, ..) => (size, LLIL_FCMP_E),
+            FcmpNe(size, ..) => (size, LLIL_FCMP_NE),
+            FcmpLt(size, ..) => (size, LLIL_FCMP_LT),
+            FcmpLe(size, ..) => (size, LLIL_FCMP_LE),
+            FcmpGe(size, ..) => (size, LLIL_FCMP_GE),
+            FcmpGt(size, ..) => (size, LLIL_FCMP_GT),
+            FcmpO(size, ..) => (size, LLIL_FCMP_O),
+            FcmpUo(size, ..) => (size, LLIL_FCMP_UO),
+
             Adc(size, ..) => (size, LLIL_ADC),
             Sbb(size, ..) => (size, LLIL_SBB),
             Rlc(size, ..) => (size, LLIL_RLC),
@@ -308,7 +411,16 @@ impl<R: ArchReg> FlagWriteOp<R> {
             | Zx(_, op0)
             | LowPart(_, op0)
             | BoolToInt(_, op0)
-            | FloatToInt(_, op0) => {
+            | FloatToInt(_, op0)
+            | Fsqrt(_, op0)
+            | Fneg(_, op0)
+            | Fabs(_, op0)
+            | IntToFloat(_, op0)
+            | FloatConv(_, op0)
+            | RoundToInt(_, op0)
+            | Floor(_, op0)
+            | Ceil(_, op0)
+            | Ftrunc(_, op0) => {
                 operands[0] = op0.into_api();
                 1
             }
@@ -337,7 +449,29 @@ impl<R: ArchReg> FlagWriteOp<R> {
             | ModuDp(_, op0, op1)
             | ModsDp(_, op0, op1)
             | TestBit(_, op0, op1)
-            | AddOverflow(_, op0, op1) => {
+            | AddOverflow(_, op0, op1)
+            | Fadd(_, op0, op1)
+            | Fsub(_, op0, op1)
+            | Fmul(_, op0, op1)
+            | Fdiv(_, op0, op1)
+            | CmpE(_, op0, op1)
+            | CmpNe(_, op0, op1)
+            | CmpSlt(_, op0, op1)
+            | CmpUlt(_, op0, op1)
+            | CmpSle(_, op0, op1)
+            | CmpUle(_, op0, op1)
+            | CmpSge(_, op0, op1)
+            | CmpUge(_, op0, op1)
+            | CmpSgt(_, op0, op1)
+            | CmpUgt(_, op0, op1)
+            | FcmpE(_, op0, op1)
+            | FcmpNe(_, op0, op1)
+            | FcmpLt(_, op0, op1)
+            | FcmpLe(_, op0, op1)
+            | FcmpGe(_, op0, op1)
+            | FcmpGt(_, op0, op1)
+            | FcmpO(_, op0, op1)
+            | FcmpUo(_, op0, op1) => {
                 operands[0] = op0.into_api();
                 operands[1] = op1.into_api();
                 2
@@ -614,8 +748,14 @@ where
     R: ExpressionResultType,
 {
     pub fn with_flag_write(mut self, flag_write: A::FlagWrite) -> Self {
-        // TODO verify valid id
-        self.flags = flag_write.id();
+        let id = flag_write.id();
+        debug_assert!(
+            self.function.arch().flag_write_from_id(id).is_some(),
+            "flag write {} isn't known to the architecture",
+            id
+        );
+
+        self.flags = id;
         self
     }
 
@@ -973,6 +1113,59 @@ where
         self.const_ptr_sized(self.arch().address_size(), val)
     }
 
+    pub fn extern_ptr(
+        &self,
+        size: usize,
+        val: u64,
+        offset: u64,
+    ) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr> {
+        use binaryninjacore_sys::BNLowLevelILAddExpr;
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_EXTERN_PTR;
+
+        let expr_idx = unsafe {
+            BNLowLevelILAddExpr(self.handle, LLIL_EXTERN_PTR, size, 0, val, offset, 0, 0)
+        };
+
+        Expression {
+            function: self,
+            expr_idx: expr_idx,
+            _ty: PhantomData,
+        }
+    }
+
+    /// Lifts a floating point constant from its raw bit pattern
+    pub fn float_const_raw(
+        &self,
+        size: usize,
+        val: u64,
+    ) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr> {
+        use binaryninjacore_sys::BNLowLevelILAddExpr;
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_FLOAT_CONST;
+
+        let expr_idx =
+            unsafe { BNLowLevelILAddExpr(self.handle, LLIL_FLOAT_CONST, size, 0, val, 0, 0, 0) };
+
+        Expression {
+            function: self,
+            expr_idx: expr_idx,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn float_const_single(
+        &self,
+        val: f32,
+    ) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr> {
+        self.float_const_raw(4, val.to_bits() as u64)
+    }
+
+    pub fn float_const_double(
+        &self,
+        val: f64,
+    ) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr> {
+        self.float_const_raw(8, val.to_bits())
+    }
+
     pub fn trap(&self, val: u64) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, VoidExpr> {
         use binaryninjacore_sys::BNLowLevelILAddExpr;
         use binaryninjacore_sys::BNLowLevelILOperation::LLIL_TRAP;
@@ -997,7 +1190,58 @@ where
     unsized_unary_op_lifter!(call, LLIL_CALL, VoidExpr);
     unsized_unary_op_lifter!(ret, LLIL_RET, VoidExpr);
     unsized_unary_op_lifter!(jump, LLIL_JUMP, VoidExpr);
-    // JumpTo TODO
+    unsized_unary_op_lifter!(tailcall, LLIL_TAILCALL, VoidExpr);
+
+    /// Lifts an indirect jump whose possible destinations are known
+    ///
+    /// Each entry of `targets` maps a destination address to the label
+    /// control is transferred to when `dest` evaluates to that address.
+    pub fn jump_to<'a: 'b, 'b, E>(
+        &'a self,
+        dest: E,
+        targets: &[(u64, &'b Label)],
+    ) -> Expression<'a, A, Mutable, NonSSA<LiftedNonSSA>, VoidExpr>
+    where
+        E: Liftable<'b, A, Result = ValueExpr>,
+    {
+        use binaryninjacore_sys::BNLowLevelILAddExpr;
+        use binaryninjacore_sys::BNLowLevelILAddLabelMap;
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_JUMP_TO;
+
+        let dest = E::lift(self, dest);
+
+        let mut values: Vec<u64> = targets.iter().map(|&(addr, _)| addr).collect();
+        let mut labels: Vec<*mut BNLowLevelILLabel> = targets
+            .iter()
+            .map(|&(_, label)| &label.0 as *const _ as *mut _)
+            .collect();
+
+        let expr_idx = unsafe {
+            let target_list = BNLowLevelILAddLabelMap(
+                self.handle,
+                values.as_mut_ptr(),
+                labels.as_mut_ptr(),
+                targets.len(),
+            );
+
+            BNLowLevelILAddExpr(
+                self.handle,
+                LLIL_JUMP_TO,
+                0,
+                0,
+                dest.expr_idx as u64,
+                targets.len() as u64 * 2,
+                target_list as u64,
+                0,
+            )
+        };
+
+        Expression {
+            function: self,
+            expr_idx: expr_idx,
+            _ty: PhantomData,
+        }
+    }
 
     pub fn if_expr<'a: 'b, 'b, C>(
         &'a self,
@@ -1043,6 +1287,39 @@ where
         }
     }
 
+    // registers and flags handed to us are expected to come from the architecture being lifted;
+    // temp registers share the id space, tagged with the top bit
+    fn raw_reg_id(&self, reg: Register<A::Register>) -> u32 {
+        match reg {
+            Register::ArchReg(r) => {
+                let id = r.id();
+                debug_assert!(
+                    self.arch().register_from_id(id).is_some(),
+                    "register {} isn't known to the architecture",
+                    id
+                );
+
+                id
+            }
+            Register::Temp(r) => {
+                assert!(r < 0x8000_0000, "temp register {} out of range", r);
+
+                0x8000_0000 | r
+            }
+        }
+    }
+
+    fn raw_flag_id(&self, flag: &A::Flag) -> u32 {
+        let id = flag.id();
+        debug_assert!(
+            self.arch().flag_from_id(id).is_some(),
+            "flag {} isn't known to the architecture",
+            id
+        );
+
+        id
+    }
+
     pub fn reg<R: Into<Register<A::Register>>>(
         &self,
         size: usize,
@@ -1051,11 +1328,7 @@ where
         use binaryninjacore_sys::BNLowLevelILAddExpr;
         use binaryninjacore_sys::BNLowLevelILOperation::LLIL_REG;
 
-        // TODO verify valid id
-        let reg = match reg.into() {
-            Register::ArchReg(r) => r.id(),
-            Register::Temp(r) => 0x8000_0000 | r,
-        };
+        let reg = self.raw_reg_id(reg.into());
 
         let expr_idx =
             unsafe { BNLowLevelILAddExpr(self.handle, LLIL_REG, size, 0, reg as u64, 0, 0, 0) };
@@ -1067,6 +1340,39 @@ where
         }
     }
 
+    pub fn reg_split<H: Into<Register<A::Register>>, L: Into<Register<A::Register>>>(
+        &self,
+        size: usize,
+        hi_reg: H,
+        lo_reg: L,
+    ) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr> {
+        use binaryninjacore_sys::BNLowLevelILAddExpr;
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_REG_SPLIT;
+
+        let hi_reg = self.raw_reg_id(hi_reg.into());
+
+        let lo_reg = self.raw_reg_id(lo_reg.into());
+
+        let expr_idx = unsafe {
+            BNLowLevelILAddExpr(
+                self.handle,
+                LLIL_REG_SPLIT,
+                size,
+                0,
+                hi_reg as u64,
+                lo_reg as u64,
+                0,
+                0,
+            )
+        };
+
+        Expression {
+            function: self,
+            expr_idx: expr_idx,
+            _ty: PhantomData,
+        }
+    }
+
     pub fn set_reg<'a, R, E>(
         &'a self,
         size: usize,
@@ -1079,11 +1385,7 @@ where
     {
         use binaryninjacore_sys::BNLowLevelILOperation::LLIL_SET_REG;
 
-        // TODO verify valid id
-        let dest_reg = match dest_reg.into() {
-            Register::ArchReg(r) => r.id(),
-            Register::Temp(r) => 0x8000_0000 | r,
-        };
+        let dest_reg = self.raw_reg_id(dest_reg.into());
 
         let expr = E::lift_with_size(self, expr, size);
 
@@ -1114,17 +1416,9 @@ where
     {
         use binaryninjacore_sys::BNLowLevelILOperation::LLIL_SET_REG_SPLIT;
 
-        // TODO verify valid id
-        let hi_reg = match hi_reg.into() {
-            Register::ArchReg(r) => r.id(),
-            Register::Temp(r) => 0x8000_0000 | r,
-        };
+        let hi_reg = self.raw_reg_id(hi_reg.into());
 
-        // TODO verify valid id
-        let lo_reg = match lo_reg.into() {
-            Register::ArchReg(r) => r.id(),
-            Register::Temp(r) => 0x8000_0000 | r,
-        };
+        let lo_reg = self.raw_reg_id(lo_reg.into());
 
         let expr = E::lift_with_size(self, expr, size);
 
@@ -1141,13 +1435,194 @@ where
         }
     }
 
+    /// Lifts a call to `intrinsic`, with the results written to `outputs`
+    pub fn intrinsic<'a, O, P>(
+        &'a self,
+        outputs: O,
+        intrinsic: A::Intrinsic,
+        inputs: P,
+    ) -> ExpressionBuilder<'a, A, VoidExpr>
+    where
+        O: IntoIterator<Item = RegisterOrFlag<A::Register, A::Flag>>,
+        P: IntoIterator<Item = Expression<'a, A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr>>,
+    {
+        use binaryninjacore_sys::BNLowLevelILAddExpr;
+        use binaryninjacore_sys::BNLowLevelILAddOperandList;
+        use binaryninjacore_sys::BNLowLevelILOperation::{LLIL_CALL_PARAM, LLIL_INTRINSIC};
+
+        let mut outputs: Vec<u64> = outputs.into_iter().map(|o| o.raw_id()).collect();
+        let mut inputs: Vec<u64> = inputs.into_iter().map(|i| i.expr_idx as u64).collect();
+
+        let (output_list, params) = unsafe {
+            let output_list =
+                BNLowLevelILAddOperandList(self.handle, outputs.as_mut_ptr(), outputs.len());
+            let input_list =
+                BNLowLevelILAddOperandList(self.handle, inputs.as_mut_ptr(), inputs.len());
+
+            let params = BNLowLevelILAddExpr(
+                self.handle,
+                LLIL_CALL_PARAM,
+                0,
+                0,
+                inputs.len() as u64,
+                input_list as u64,
+                0,
+                0,
+            );
+
+            (output_list, params)
+        };
+
+        ExpressionBuilder {
+            function: self,
+            op: LLIL_INTRINSIC,
+            size: 0,
+            flags: 0,
+            op1: outputs.len() as u64,
+            op2: output_list as u64,
+            op3: intrinsic.id() as u64,
+            op4: params as u64,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn reg_stack_push<'a, E>(
+        &'a self,
+        size: usize,
+        dest_stack: A::RegisterStack,
+        expr: E,
+    ) -> ExpressionBuilder<'a, A, VoidExpr>
+    where
+        E: LiftableWithSize<'a, A>,
+    {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_REG_STACK_PUSH;
+
+        let expr = E::lift_with_size(self, expr, size);
+
+        ExpressionBuilder {
+            function: self,
+            op: LLIL_REG_STACK_PUSH,
+            size,
+            flags: 0,
+            op1: dest_stack.id() as u64,
+            op2: expr.expr_idx as u64,
+            op3: 0,
+            op4: 0,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn reg_stack_pop(
+        &self,
+        size: usize,
+        source_stack: A::RegisterStack,
+    ) -> ExpressionBuilder<A, ValueExpr> {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_REG_STACK_POP;
+
+        ExpressionBuilder {
+            function: self,
+            op: LLIL_REG_STACK_POP,
+            size,
+            flags: 0,
+            op1: source_stack.id() as u64,
+            op2: 0,
+            op3: 0,
+            op4: 0,
+            _ty: PhantomData,
+        }
+    }
+
+    /// Reads the stack entry at index `entry`, relative to the current top of `source_stack`
+    pub fn reg_stack_top_relative<'a, E>(
+        &'a self,
+        size: usize,
+        source_stack: A::RegisterStack,
+        entry: E,
+    ) -> ExpressionBuilder<'a, A, ValueExpr>
+    where
+        E: Liftable<'a, A, Result = ValueExpr>,
+    {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_REG_STACK_REL;
+
+        let entry = E::lift(self, entry);
+
+        ExpressionBuilder {
+            function: self,
+            op: LLIL_REG_STACK_REL,
+            size,
+            flags: 0,
+            op1: source_stack.id() as u64,
+            op2: entry.expr_idx as u64,
+            op3: 0,
+            op4: 0,
+            _ty: PhantomData,
+        }
+    }
+
+    /// Writes the stack entry at index `entry`, relative to the current top of `dest_stack`
+    pub fn set_reg_stack_top_relative<'a, I, E>(
+        &'a self,
+        size: usize,
+        dest_stack: A::RegisterStack,
+        entry: I,
+        expr: E,
+    ) -> ExpressionBuilder<'a, A, VoidExpr>
+    where
+        I: Liftable<'a, A, Result = ValueExpr>,
+        E: LiftableWithSize<'a, A>,
+    {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_SET_REG_STACK_REL;
+
+        let entry = I::lift(self, entry);
+        let expr = E::lift_with_size(self, expr, size);
+
+        ExpressionBuilder {
+            function: self,
+            op: LLIL_SET_REG_STACK_REL,
+            size,
+            flags: 0,
+            op1: dest_stack.id() as u64,
+            op2: entry.expr_idx as u64,
+            op3: expr.expr_idx as u64,
+            op4: 0,
+            _ty: PhantomData,
+        }
+    }
+
+    /// Marks the stack entry at index `entry`, relative to the current top of `dest_stack`,
+    /// as no longer in use
+    pub fn reg_stack_free_top_relative<'a, I>(
+        &'a self,
+        dest_stack: A::RegisterStack,
+        entry: I,
+    ) -> ExpressionBuilder<'a, A, VoidExpr>
+    where
+        I: Liftable<'a, A, Result = ValueExpr>,
+    {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_REG_STACK_FREE_REL;
+
+        let entry = I::lift(self, entry);
+
+        ExpressionBuilder {
+            function: self,
+            op: LLIL_REG_STACK_FREE_REL,
+            size: 0,
+            flags: 0,
+            op1: dest_stack.id() as u64,
+            op2: entry.expr_idx as u64,
+            op3: 0,
+            op4: 0,
+            _ty: PhantomData,
+        }
+    }
+
     pub fn flag(&self, flag: A::Flag) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr> {
         use binaryninjacore_sys::BNLowLevelILAddExpr;
         use binaryninjacore_sys::BNLowLevelILOperation::LLIL_FLAG;
 
-        // TODO verify valid id
+        let flag = self.raw_flag_id(&flag);
         let expr_idx =
-            unsafe { BNLowLevelILAddExpr(self.handle, LLIL_FLAG, 0, 0, flag.id() as u64, 0, 0, 0) };
+            unsafe { BNLowLevelILAddExpr(self.handle, LLIL_FLAG, 0, 0, flag as u64, 0, 0, 0) };
 
         Expression {
             function: self,
@@ -1163,7 +1638,6 @@ where
         use binaryninjacore_sys::BNLowLevelILAddExpr;
         use binaryninjacore_sys::BNLowLevelILOperation::LLIL_FLAG_COND;
 
-        // TODO verify valid id
         let expr_idx =
             unsafe { BNLowLevelILAddExpr(self.handle, LLIL_FLAG_COND, 0, 0, cond as u64, 0, 0, 0) };
 
@@ -1181,18 +1655,15 @@ where
         use binaryninjacore_sys::BNLowLevelILAddExpr;
         use binaryninjacore_sys::BNLowLevelILOperation::LLIL_FLAG_GROUP;
 
-        // TODO verify valid id
+        let group_id = group.id();
+        debug_assert!(
+            self.arch().flag_group_from_id(group_id).is_some(),
+            "flag group {} isn't known to the architecture",
+            group_id
+        );
+
         let expr_idx = unsafe {
-            BNLowLevelILAddExpr(
-                self.handle,
-                LLIL_FLAG_GROUP,
-                0,
-                0,
-                group.id() as u64,
-                0,
-                0,
-                0,
-            )
+            BNLowLevelILAddExpr(self.handle, LLIL_FLAG_GROUP, 0, 0, group_id as u64, 0, 0, 0)
         };
 
         Expression {
@@ -1212,8 +1683,7 @@ where
     {
         use binaryninjacore_sys::BNLowLevelILOperation::LLIL_SET_FLAG;
 
-        // TODO verify valid id
-
+        let dest_flag = self.raw_flag_id(&dest_flag);
         let expr = E::lift_with_size(self, expr, 0);
 
         ExpressionBuilder {
@@ -1221,7 +1691,7 @@ where
             op: LLIL_SET_FLAG,
             size: 0,
             flags: 0,
-            op1: dest_flag.id() as u64,
+            op1: dest_flag as u64,
             op2: expr.expr_idx as u64,
             op3: 0,
             op4: 0,
@@ -1229,10 +1699,35 @@ where
         }
     }
 
-    /*
-     * TODO
-    FlagBit(usize, Flag<A>, u64),
-    */
+    pub fn flag_bit(
+        &self,
+        size: usize,
+        flag: A::Flag,
+        bit_index: u64,
+    ) -> Expression<A, Mutable, NonSSA<LiftedNonSSA>, ValueExpr> {
+        use binaryninjacore_sys::BNLowLevelILAddExpr;
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_FLAG_BIT;
+
+        let flag = self.raw_flag_id(&flag);
+        let expr_idx = unsafe {
+            BNLowLevelILAddExpr(
+                self.handle,
+                LLIL_FLAG_BIT,
+                size,
+                0,
+                flag as u64,
+                bit_index,
+                0,
+                0,
+            )
+        };
+
+        Expression {
+            function: self,
+            expr_idx: expr_idx,
+            _ty: PhantomData,
+        }
+    }
 
     pub fn load<'a, E>(&'a self, size: usize, source_mem: E) -> ExpressionBuilder<'a, A, ValueExpr>
     where
@@ -1306,9 +1801,9 @@ where
     binary_op_lifter!(asr, LLIL_ASR);
 
     binary_op_lifter!(rol, LLIL_ROL);
-    binary_op_lifter!(rlc, LLIL_RLC);
+    binary_op_carry_lifter!(rlc, LLIL_RLC);
     binary_op_lifter!(ror, LLIL_ROR);
-    binary_op_lifter!(rrc, LLIL_RRC);
+    binary_op_carry_lifter!(rrc, LLIL_RRC);
     binary_op_lifter!(mul, LLIL_MUL);
     binary_op_lifter!(muls_dp, LLIL_MULS_DP);
     binary_op_lifter!(mulu_dp, LLIL_MULU_DP);
@@ -1320,14 +1815,10 @@ where
     binary_op_carry_lifter!(adc, LLIL_ADC);
     binary_op_carry_lifter!(sbb, LLIL_SBB);
 
-    /*
-    DivsDp(usize, Expr, Expr, Expr, Option<A::FlagWrite>),
-    DivuDp(usize, Expr, Expr, Expr, Option<A::FlagWrite>),
-    ModsDp(usize, Expr, Expr, Expr, Option<A::FlagWrite>),
-    ModuDp(usize, Expr, Expr, Expr, Option<A::FlagWrite>),
-    */
-
-    // FlagCond(u32), // TODO
+    binary_op_lifter!(divs_dp, LLIL_DIVS_DP);
+    binary_op_lifter!(divu_dp, LLIL_DIVU_DP);
+    binary_op_lifter!(mods_dp, LLIL_MODS_DP);
+    binary_op_lifter!(modu_dp, LLIL_MODU_DP);
 
     binary_op_lifter!(cmp_e, LLIL_CMP_E);
     binary_op_lifter!(cmp_ne, LLIL_CMP_NE);
@@ -1344,6 +1835,32 @@ where
     // TODO no flags
     size_changing_unary_op_lifter!(bool_to_int, LLIL_BOOL_TO_INT, ValueExpr);
 
+    binary_op_lifter!(fadd, LLIL_FADD);
+    binary_op_lifter!(fsub, LLIL_FSUB);
+    binary_op_lifter!(fmul, LLIL_FMUL);
+    binary_op_lifter!(fdiv, LLIL_FDIV);
+
+    sized_unary_op_lifter!(fsqrt, LLIL_FSQRT, ValueExpr);
+    sized_unary_op_lifter!(fneg, LLIL_FNEG, ValueExpr);
+    sized_unary_op_lifter!(fabs, LLIL_FABS, ValueExpr);
+    sized_unary_op_lifter!(round_to_int, LLIL_ROUND_TO_INT, ValueExpr);
+    sized_unary_op_lifter!(floor, LLIL_FLOOR, ValueExpr);
+    sized_unary_op_lifter!(ceil, LLIL_CEIL, ValueExpr);
+    sized_unary_op_lifter!(ftrunc, LLIL_FTRUNC, ValueExpr);
+
+    size_changing_unary_op_lifter!(float_to_int, LLIL_FLOAT_TO_INT, ValueExpr);
+    size_changing_unary_op_lifter!(int_to_float, LLIL_INT_TO_FLOAT, ValueExpr);
+    size_changing_unary_op_lifter!(float_conv, LLIL_FLOAT_CONV, ValueExpr);
+
+    binary_op_lifter!(fcmp_e, LLIL_FCMP_E);
+    binary_op_lifter!(fcmp_ne, LLIL_FCMP_NE);
+    binary_op_lifter!(fcmp_lt, LLIL_FCMP_LT);
+    binary_op_lifter!(fcmp_le, LLIL_FCMP_LE);
+    binary_op_lifter!(fcmp_ge, LLIL_FCMP_GE);
+    binary_op_lifter!(fcmp_gt, LLIL_FCMP_GT);
+    binary_op_lifter!(fcmp_o, LLIL_FCMP_O);
+    binary_op_lifter!(fcmp_uo, LLIL_FCMP_UO);
+
     pub fn current_address(&self) -> u64 {
         use binaryninjacore_sys::BNLowLevelILGetCurrentAddress;
         unsafe { BNLowLevelILGetCurrentAddress(self.handle) }
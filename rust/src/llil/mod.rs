@@ -21,6 +21,7 @@ use std::fmt;
 // can reg/set_reg be used with sizes that differ from what is in BNRegisterInfo?
 
 use crate::architecture::Architecture;
+use crate::architecture::Flag as ArchFlag;
 use crate::architecture::Register as ArchReg;
 use crate::function::Location;
 
@@ -85,6 +86,54 @@ impl<R: ArchReg> SSARegister<R> {
             SSARegister::Full(_, ver) | SSARegister::Partial(_, ver, _) => ver,
         }
     }
+
+    // SSA versions are tracked per full-width register
+    fn id(&self) -> u32 {
+        match *self {
+            SSARegister::Full(ref r, _) => r.id(),
+            SSARegister::Partial(ref r, _, _) => r.id(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct SSAFlag<F: ArchFlag> {
+    pub flag: F,
+    pub version: u32,
+}
+
+impl<F: ArchFlag> SSAFlag<F> {
+    pub fn new(flag: F, version: u32) -> Self {
+        Self { flag, version }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum RegisterOrFlag<R: ArchReg, F: ArchFlag> {
+    Register(Register<R>),
+    Flag(F),
+}
+
+impl<R: ArchReg, F: ArchFlag> RegisterOrFlag<R, F> {
+    // flags are distinguished from registers by bit 32 in register/flag lists
+    fn raw_id(&self) -> u64 {
+        match *self {
+            RegisterOrFlag::Register(ref r) => r.id() as u64,
+            RegisterOrFlag::Flag(ref f) => (1 << 32) | f.id() as u64,
+        }
+    }
+}
+
+impl<R: ArchReg, F: ArchFlag> From<Register<R>> for RegisterOrFlag<R, F> {
+    fn from(reg: Register<R>) -> Self {
+        RegisterOrFlag::Register(reg)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum SSARegisterOrFlag<R: ArchReg, F: ArchFlag> {
+    Register(SSARegister<R>),
+    Flag(SSAFlag<F>),
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
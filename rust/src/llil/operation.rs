@@ -16,6 +16,7 @@ use binaryninjacore_sys::BNLowLevelILInstruction;
 
 use std::marker::PhantomData;
 use std::mem;
+use std::slice;
 
 use super::*;
 
@@ -27,6 +28,7 @@ where
     O: OperationArguments,
 {
     pub(crate) function: &'func Function<A, M, F>,
+    pub(crate) expr_idx: usize,
     pub(crate) op: BNLowLevelILInstruction,
     _args: PhantomData<O>,
 }
@@ -38,9 +40,14 @@ where
     F: FunctionForm,
     O: OperationArguments,
 {
-    pub(crate) fn new(function: &'func Function<A, M, F>, op: BNLowLevelILInstruction) -> Self {
+    pub(crate) fn new(
+        function: &'func Function<A, M, F>,
+        expr_idx: usize,
+        op: BNLowLevelILInstruction,
+    ) -> Self {
         Self {
             function: function,
+            expr_idx: expr_idx,
             op: op,
             _args: PhantomData,
         }
@@ -49,6 +56,120 @@ where
     pub fn address(&self) -> u64 {
         self.op.address
     }
+
+    fn reg_from_raw(&self, raw_id: u32) -> Register<A::Register> {
+        if raw_id >= 0x8000_0000 {
+            Register::Temp(raw_id & 0x7fff_ffff)
+        } else {
+            self.function
+                .arch()
+                .register_from_id(raw_id)
+                .map(Register::ArchReg)
+                .unwrap_or_else(|| {
+                    error!(
+                        "got garbage register from {:?} @ 0x{:x}",
+                        self.op.operation, self.op.address
+                    );
+
+                    Register::Temp(0)
+                })
+        }
+    }
+
+    fn reg_stack_from_raw(&self, raw_id: u32) -> Option<A::RegisterStack> {
+        let stack = self.function.arch().register_stack_from_id(raw_id);
+
+        if stack.is_none() {
+            error!(
+                "got garbage register stack from {:?} @ 0x{:x}",
+                self.op.operation, self.op.address
+            );
+        }
+
+        stack
+    }
+
+    fn ssa_reg_from_raw(&self, raw_id: u64, version: u64) -> SSARegister<A::Register> {
+        SSARegister::Full(self.reg_from_raw(raw_id as u32), version as u32)
+    }
+
+    fn ssa_partial_reg_from_raw(
+        &self,
+        full_id: u64,
+        version: u64,
+        partial_id: u64,
+    ) -> SSARegister<A::Register> {
+        let full = self.reg_from_raw(full_id as u32);
+        let partial = self.reg_from_raw(partial_id as u32);
+
+        match (full, partial) {
+            (Register::ArchReg(full), Register::ArchReg(partial)) => {
+                SSARegister::Partial(full, version as u32, partial)
+            }
+            (full, _) => SSARegister::Full(full, version as u32),
+        }
+    }
+
+    fn ssa_flag_from_raw(&self, raw_id: u64, version: u64) -> Option<SSAFlag<A::Flag>> {
+        let flag = self.function.arch().flag_from_id(raw_id as u32);
+
+        if flag.is_none() {
+            error!(
+                "got garbage flag from {:?} @ 0x{:x}",
+                self.op.operation, self.op.address
+            );
+        }
+
+        flag.map(|flag| SSAFlag::new(flag, version as u32))
+    }
+
+    // intrinsic outputs tag flags with a bit above the 32 bit id
+    fn reg_or_flag_from_raw(&self, raw: u64) -> Option<RegisterOrFlag<A::Register, A::Flag>> {
+        if raw & (1 << 32) != 0 {
+            let flag = self.function.arch().flag_from_id(raw as u32);
+
+            if flag.is_none() {
+                error!(
+                    "got garbage flag from {:?} @ 0x{:x}",
+                    self.op.operation, self.op.address
+                );
+            }
+
+            flag.map(RegisterOrFlag::Flag)
+        } else {
+            Some(RegisterOrFlag::Register(self.reg_from_raw(raw as u32)))
+        }
+    }
+
+    fn sub_expr_op(&self, operand: usize) -> BNLowLevelILInstruction {
+        use binaryninjacore_sys::BNGetLowLevelILByIndex;
+
+        unsafe { BNGetLowLevelILByIndex(self.function.handle, self.op.operands[operand] as usize) }
+    }
+
+    fn operand_list(&self, expr_idx: usize, operand: usize) -> Vec<u64> {
+        use binaryninjacore_sys::BNLowLevelILFreeOperandList;
+        use binaryninjacore_sys::BNLowLevelILGetOperandList;
+
+        unsafe {
+            let mut count = 0;
+            let list =
+                BNLowLevelILGetOperandList(self.function.handle, expr_idx, operand, &mut count);
+
+            let result = slice::from_raw_parts(list, count).to_vec();
+
+            BNLowLevelILFreeOperandList(list);
+
+            result
+        }
+    }
+
+    fn ssa_reg_list(&self, expr_idx: usize, operand: usize) -> Vec<SSARegister<A::Register>> {
+        self.operand_list(expr_idx, operand)
+            .chunks(2)
+            .map(|pair| self.ssa_reg_from_raw(pair[0], pair[1]))
+            .collect()
+    }
 }
 
 impl<'func, A, M, O> Operation<'func, A, M, NonSSA<LiftedNonSSA>, O>
@@ -85,7 +206,42 @@ where
 // LLIL_SYSCALL, LLIL_SYSCALL_SSA
 pub struct Syscall;
 
-// LLIL_SET_REG, LLIL_SET_REG_SSA, LLIL_SET_REG_PARTIAL_SSA
+impl<'func, A, M> Operation<'func, A, M, SSA, Syscall>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    /// Returns the registers written by the system call
+    pub fn output_regs(&self) -> Vec<SSARegister<A::Register>> {
+        self.ssa_reg_list(self.op.operands[0] as usize, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        self.sub_expr_op(0).operands[0] as usize
+    }
+
+    pub fn stack_reg(&self) -> SSARegister<A::Register> {
+        let stack = self.sub_expr_op(1);
+        self.ssa_reg_from_raw(stack.operands[0], stack.operands[1])
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.sub_expr_op(1).operands[2] as usize
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, SSA, ValueExpr>> {
+        self.operand_list(self.op.operands[2] as usize, 0)
+            .into_iter()
+            .map(|expr_idx| Expression {
+                function: self.function,
+                expr_idx: expr_idx as usize,
+                _ty: PhantomData,
+            })
+            .collect()
+    }
+}
+
+// LLIL_SET_REG, LLIL_SET_REG_SSA, LLIL_SET_REG_SSA_PARTIAL
 pub struct SetReg;
 
 impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, SetReg>
@@ -128,6 +284,44 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, SetReg>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_reg(&self) -> SSARegister<A::Register> {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_SET_REG_SSA_PARTIAL;
+
+        let operands = &self.op.operands;
+
+        if self.op.operation == LLIL_SET_REG_SSA_PARTIAL {
+            self.ssa_partial_reg_from_raw(operands[0], operands[1], operands[2])
+        } else {
+            self.ssa_reg_from_raw(operands[0], operands[1])
+        }
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_SET_REG_SSA_PARTIAL;
+
+        let operand = if self.op.operation == LLIL_SET_REG_SSA_PARTIAL {
+            3
+        } else {
+            2
+        };
+
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[operand] as usize,
+            _ty: PhantomData,
+        }
+    }
+}
+
 // LLIL_SET_REG_SPLIT, LLIL_SET_REG_SPLIT_SSA
 pub struct SetRegSplit;
 
@@ -192,6 +386,34 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, SetRegSplit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_reg_high(&self) -> SSARegister<A::Register> {
+        let dest = self.sub_expr_op(0);
+        self.ssa_reg_from_raw(dest.operands[0], dest.operands[1])
+    }
+
+    pub fn dest_reg_low(&self) -> SSARegister<A::Register> {
+        let dest = self.sub_expr_op(1);
+        self.ssa_reg_from_raw(dest.operands[0], dest.operands[1])
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[2] as usize,
+            _ty: PhantomData,
+        }
+    }
+}
+
 // LLIL_SET_FLAG, LLIL_SET_FLAG_SSA
 pub struct SetFlag;
 
@@ -210,6 +432,24 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, SetFlag>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_flag(&self) -> Option<SSAFlag<A::Flag>> {
+        self.ssa_flag_from_raw(self.op.operands[0], self.op.operands[1])
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[2] as usize,
+            _ty: PhantomData,
+        }
+    }
+}
+
 // LLIL_LOAD, LLIL_LOAD_SSA
 pub struct Load;
 
@@ -232,6 +472,28 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Load>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_mem_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[0] as usize,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+}
+
 // LLIL_STORE, LLIL_STORE_SSA
 pub struct Store;
 
@@ -262,6 +524,40 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Store>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_mem_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[0] as usize,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[3] as usize,
+            _ty: PhantomData,
+        }
+    }
+}
+
 // LLIL_REG, LLIL_REG_SSA, LLIL_REG_SSA_PARTIAL
 pub struct Reg;
 
@@ -297,12 +593,58 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Reg>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_reg(&self) -> SSARegister<A::Register> {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_REG_SSA_PARTIAL;
+
+        let operands = &self.op.operands;
+
+        if self.op.operation == LLIL_REG_SSA_PARTIAL {
+            self.ssa_partial_reg_from_raw(operands[0], operands[1], operands[2])
+        } else {
+            self.ssa_reg_from_raw(operands[0], operands[1])
+        }
+    }
+}
+
 // LLIL_FLAG, LLIL_FLAG_SSA
 pub struct Flag;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Flag>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn source_flag(&self) -> Option<SSAFlag<A::Flag>> {
+        self.ssa_flag_from_raw(self.op.operands[0], self.op.operands[1])
+    }
+}
+
 // LLIL_FLAG_BIT, LLIL_FLAG_BIT_SSA
 pub struct FlagBit;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, FlagBit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn source_flag(&self) -> Option<SSAFlag<A::Flag>> {
+        self.ssa_flag_from_raw(self.op.operands[0], self.op.operands[1])
+    }
+
+    pub fn bit_index(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+}
+
 // LLIL_JUMP
 pub struct Jump;
 
@@ -337,10 +679,24 @@ where
             _ty: PhantomData,
         }
     }
-    // TODO target list
+
+    /// Returns the known destinations of this jump, keyed by target address
+    pub fn target_list(&self) -> Vec<(u64, Instruction<'func, A, M, F>)> {
+        self.operand_list(self.expr_idx, 1)
+            .chunks(2)
+            .map(|pair| {
+                let instr = Instruction {
+                    function: self.function,
+                    instr_idx: pair[1] as usize,
+                };
+
+                (pair[0], instr)
+            })
+            .collect()
+    }
 }
 
-// LLIL_CALL, LLIL_CALL_SSA
+// LLIL_CALL, LLIL_CALL_STACK_ADJUST, LLIL_TAILCALL, LLIL_CALL_SSA, LLIL_TAILCALL_SSA
 pub struct Call;
 
 impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, Call>
@@ -368,6 +724,49 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Call>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn target(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[1] as usize,
+            _ty: PhantomData,
+        }
+    }
+
+    /// Returns the registers written by the call
+    pub fn output_regs(&self) -> Vec<SSARegister<A::Register>> {
+        self.ssa_reg_list(self.op.operands[0] as usize, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        self.sub_expr_op(0).operands[0] as usize
+    }
+
+    pub fn stack_reg(&self) -> SSARegister<A::Register> {
+        let stack = self.sub_expr_op(2);
+        self.ssa_reg_from_raw(stack.operands[0], stack.operands[1])
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.sub_expr_op(2).operands[2] as usize
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, SSA, ValueExpr>> {
+        self.operand_list(self.op.operands[3] as usize, 0)
+            .into_iter()
+            .map(|expr_idx| Expression {
+                function: self.function,
+                expr_idx: expr_idx as usize,
+                _ty: PhantomData,
+            })
+            .collect()
+    }
+}
+
 // LLIL_RET
 pub struct Ret;
 
@@ -469,12 +868,60 @@ where
 // LLIL_REG_PHI
 pub struct RegPhi;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, RegPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_reg(&self) -> SSARegister<A::Register> {
+        self.ssa_reg_from_raw(self.op.operands[0], self.op.operands[1])
+    }
+
+    pub fn source_regs(&self) -> Vec<SSARegister<A::Register>> {
+        self.ssa_reg_list(self.expr_idx, 2)
+    }
+}
+
 // LLIL_FLAG_PHI
 pub struct FlagPhi;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, FlagPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_flag(&self) -> Option<SSAFlag<A::Flag>> {
+        self.ssa_flag_from_raw(self.op.operands[0], self.op.operands[1])
+    }
+
+    pub fn source_flags(&self) -> Vec<SSAFlag<A::Flag>> {
+        self.operand_list(self.expr_idx, 2)
+            .chunks(2)
+            .filter_map(|pair| self.ssa_flag_from_raw(pair[0], pair[1]))
+            .collect()
+    }
+}
+
 // LLIL_MEM_PHI
 pub struct MemPhi;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, MemPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[0] as usize
+    }
+
+    pub fn source_memory_versions(&self) -> Vec<usize> {
+        self.operand_list(self.expr_idx, 1)
+            .into_iter()
+            .map(|version| version as usize)
+            .collect()
+    }
+}
+
 // LLIL_CONST, LLIL_CONST_PTR
 pub struct Const;
 
@@ -521,7 +968,9 @@ where
 // LLIL_XOR, LLIL_LSL, LLIL_LSR, LLIL_ASR
 // LLIL_ROL, LLIL_ROR, LLIL_MUL, LLIL_MULU_DP,
 // LLIL_MULS_DP, LLIL_DIVU, LLIL_DIVS, LLIL_MODU,
-// LLIL_MODS
+// LLIL_MODS, LLIL_DIVU_DP, LLIL_DIVS_DP, LLIL_MODU_DP,
+// LLIL_MODS_DP, LLIL_ADD_OVERFLOW, LLIL_FADD, LLIL_FSUB,
+// LLIL_FMUL, LLIL_FDIV
 pub struct BinaryOp;
 
 impl<'func, A, M, F> Operation<'func, A, M, F, BinaryOp>
@@ -589,10 +1038,14 @@ where
     }
 }
 
-// LLIL_DIVS_DP, LLIL_DIVU_DP, LLIL_MODU_DP, LLIL_MODS_DP
-pub struct DoublePrecDivOp;
+// LLIL_PUSH, LLIL_NEG, LLIL_NOT, LLIL_SX,
+// LLIL_ZX, LLIL_LOW_PART, LLIL_BOOL_TO_INT, LLIL_FSQRT,
+// LLIL_FNEG, LLIL_FABS, LLIL_FLOAT_TO_INT, LLIL_INT_TO_FLOAT,
+// LLIL_FLOAT_CONV, LLIL_ROUND_TO_INT, LLIL_FLOOR, LLIL_CEIL,
+// LLIL_FTRUNC
+pub struct UnaryOp;
 
-impl<'func, A, M, F> Operation<'func, A, M, F, DoublePrecDivOp>
+impl<'func, A, M, F> Operation<'func, A, M, F, UnaryOp>
 where
     A: 'func + Architecture,
     M: FunctionMutability,
@@ -602,18 +1055,32 @@ where
         self.op.size
     }
 
-    pub fn high(&self) -> Expression<'func, A, M, F, ValueExpr> {
+    pub fn operand(&self) -> Expression<'func, A, M, F, ValueExpr> {
         Expression {
             function: self.function,
             expr_idx: self.op.operands[0] as usize,
             _ty: PhantomData,
         }
     }
+}
+
+// LLIL_CMP_X, LLIL_FCMP_X, LLIL_TEST_BIT
+pub struct Condition;
 
-    pub fn low(&self) -> Expression<'func, A, M, F, ValueExpr> {
+impl<'func, A, M, F> Operation<'func, A, M, F, Condition>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func, A, M, F, ValueExpr> {
         Expression {
             function: self.function,
-            expr_idx: self.op.operands[1] as usize,
+            expr_idx: self.op.operands[0] as usize,
             _ty: PhantomData,
         }
     }
@@ -621,17 +1088,16 @@ where
     pub fn right(&self) -> Expression<'func, A, M, F, ValueExpr> {
         Expression {
             function: self.function,
-            expr_idx: self.op.operands[2] as usize,
+            expr_idx: self.op.operands[1] as usize,
             _ty: PhantomData,
         }
     }
 }
 
-// LLIL_PUSH, LLIL_NEG, LLIL_NOT, LLIL_SX,
-// LLIL_ZX, LLIL_LOW_PART, LLIL_BOOL_TO_INT, LLIL_UNIMPL_MEM
-pub struct UnaryOp;
+// LLIL_UNIMPL_MEM
+pub struct UnimplMem;
 
-impl<'func, A, M, F> Operation<'func, A, M, F, UnaryOp>
+impl<'func, A, M, F> Operation<'func, A, M, F, UnimplMem>
 where
     A: 'func + Architecture,
     M: FunctionMutability,
@@ -641,7 +1107,7 @@ where
         self.op.size
     }
 
-    pub fn operand(&self) -> Expression<'func, A, M, F, ValueExpr> {
+    pub fn mem_expr(&self) -> Expression<'func, A, M, F, ValueExpr> {
         Expression {
             function: self.function,
             expr_idx: self.op.operands[0] as usize,
@@ -650,28 +1116,121 @@ where
     }
 }
 
-// LLIL_CMP_X
-pub struct Condition;
+// LLIL_REG_SPLIT, LLIL_REG_SPLIT_SSA
+pub struct RegSplit;
 
-impl<'func, A, M, F> Operation<'func, A, M, F, Condition>
+impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, RegSplit>
 where
     A: 'func + Architecture,
     M: FunctionMutability,
-    F: FunctionForm,
+    V: NonSSAVariant,
 {
     pub fn size(&self) -> usize {
         self.op.size
     }
 
-    pub fn left(&self) -> Expression<'func, A, M, F, ValueExpr> {
+    pub fn high_reg(&self) -> Register<A::Register> {
+        self.reg_from_raw(self.op.operands[0] as u32)
+    }
+
+    pub fn low_reg(&self) -> Register<A::Register> {
+        self.reg_from_raw(self.op.operands[1] as u32)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, RegSplit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn high_reg(&self) -> SSARegister<A::Register> {
+        self.ssa_reg_from_raw(self.op.operands[0], self.op.operands[1])
+    }
+
+    pub fn low_reg(&self) -> SSARegister<A::Register> {
+        self.ssa_reg_from_raw(self.op.operands[2], self.op.operands[3])
+    }
+}
+
+// LLIL_REG_STACK_PUSH
+pub struct RegStackPush;
+
+impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, RegStackPush>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    V: NonSSAVariant,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_reg_stack_id(&self) -> u32 {
+        self.op.operands[0] as u32
+    }
+
+    pub fn dest_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.dest_reg_stack_id())
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, NonSSA<V>, ValueExpr> {
         Expression {
             function: self.function,
-            expr_idx: self.op.operands[0] as usize,
+            expr_idx: self.op.operands[1] as usize,
             _ty: PhantomData,
         }
     }
+}
 
-    pub fn right(&self) -> Expression<'func, A, M, F, ValueExpr> {
+// LLIL_REG_STACK_POP
+pub struct RegStackPop;
+
+impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, RegStackPop>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    V: NonSSAVariant,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_reg_stack_id(&self) -> u32 {
+        self.op.operands[0] as u32
+    }
+
+    pub fn source_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.source_reg_stack_id())
+    }
+}
+
+// LLIL_REG_STACK_REL, LLIL_REG_STACK_REL_SSA
+pub struct RegStackRel;
+
+impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, RegStackRel>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    V: NonSSAVariant,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_reg_stack_id(&self) -> u32 {
+        self.op.operands[0] as u32
+    }
+
+    pub fn source_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.source_reg_stack_id())
+    }
+
+    /// Returns the index of the accessed entry, relative to the top of the stack
+    pub fn source_entry(&self) -> Expression<'func, A, M, NonSSA<V>, ValueExpr> {
         Expression {
             function: self.function,
             expr_idx: self.op.operands[1] as usize,
@@ -680,29 +1239,452 @@ where
     }
 }
 
-// LLIL_UNIMPL_MEM
-pub struct UnimplMem;
+impl<'func, A, M> Operation<'func, A, M, SSA, RegStackRel>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
 
-impl<'func, A, M, F> Operation<'func, A, M, F, UnimplMem>
+    pub fn source_reg_stack_id(&self) -> u32 {
+        self.op.operands[0] as u32
+    }
+
+    pub fn source_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.source_reg_stack_id())
+    }
+
+    pub fn source_version(&self) -> u32 {
+        self.op.operands[1] as u32
+    }
+
+    /// Returns the index of the accessed entry, relative to the top of the stack
+    pub fn source_entry(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[2] as usize,
+            _ty: PhantomData,
+        }
+    }
+
+    /// Returns the register holding the index of the top of the stack
+    pub fn top_reg(&self) -> SSARegister<A::Register> {
+        let top = self.sub_expr_op(3);
+        self.ssa_reg_from_raw(top.operands[0], top.operands[1])
+    }
+}
+
+// LLIL_REG_STACK_ABS_SSA
+pub struct RegStackAbs;
+
+impl<'func, A, M> Operation<'func, A, M, SSA, RegStackAbs>
 where
     A: 'func + Architecture,
     M: FunctionMutability,
-    F: FunctionForm,
 {
     pub fn size(&self) -> usize {
         self.op.size
     }
 
-    pub fn mem_expr(&self) -> Expression<'func, A, M, F, ValueExpr> {
+    pub fn source_reg_stack_id(&self) -> u32 {
+        self.op.operands[0] as u32
+    }
+
+    pub fn source_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.source_reg_stack_id())
+    }
+
+    pub fn source_version(&self) -> u32 {
+        self.op.operands[1] as u32
+    }
+
+    pub fn source_reg(&self) -> Register<A::Register> {
+        self.reg_from_raw(self.op.operands[2] as u32)
+    }
+}
+
+// LLIL_SET_REG_STACK_REL, LLIL_SET_REG_STACK_REL_SSA
+pub struct SetRegStackRel;
+
+impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, SetRegStackRel>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    V: NonSSAVariant,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_reg_stack_id(&self) -> u32 {
+        self.op.operands[0] as u32
+    }
+
+    pub fn dest_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.dest_reg_stack_id())
+    }
+
+    /// Returns the index of the written entry, relative to the top of the stack
+    pub fn dest_entry(&self) -> Expression<'func, A, M, NonSSA<V>, ValueExpr> {
         Expression {
             function: self.function,
-            expr_idx: self.op.operands[0] as usize,
+            expr_idx: self.op.operands[1] as usize,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, NonSSA<V>, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[2] as usize,
+            _ty: PhantomData,
+        }
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, SetRegStackRel>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_reg_stack_id(&self) -> u32 {
+        self.sub_expr_op(0).operands[0] as u32
+    }
+
+    pub fn dest_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.dest_reg_stack_id())
+    }
+
+    pub fn dest_version(&self) -> u32 {
+        self.sub_expr_op(0).operands[1] as u32
+    }
+
+    pub fn source_version(&self) -> u32 {
+        self.sub_expr_op(0).operands[2] as u32
+    }
+
+    /// Returns the index of the written entry, relative to the top of the stack
+    pub fn dest_entry(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[1] as usize,
+            _ty: PhantomData,
+        }
+    }
+
+    /// Returns the register holding the index of the top of the stack
+    pub fn top_reg(&self) -> SSARegister<A::Register> {
+        let top = self.sub_expr_op(2);
+        self.ssa_reg_from_raw(top.operands[0], top.operands[1])
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[3] as usize,
+            _ty: PhantomData,
+        }
+    }
+}
+
+// LLIL_SET_REG_STACK_ABS_SSA
+pub struct SetRegStackAbs;
+
+impl<'func, A, M> Operation<'func, A, M, SSA, SetRegStackAbs>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_reg_stack_id(&self) -> u32 {
+        self.sub_expr_op(0).operands[0] as u32
+    }
+
+    pub fn dest_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.dest_reg_stack_id())
+    }
+
+    pub fn dest_version(&self) -> u32 {
+        self.sub_expr_op(0).operands[1] as u32
+    }
+
+    pub fn source_version(&self) -> u32 {
+        self.sub_expr_op(0).operands[2] as u32
+    }
+
+    pub fn dest_reg(&self) -> Register<A::Register> {
+        self.reg_from_raw(self.op.operands[1] as u32)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[2] as usize,
             _ty: PhantomData,
         }
     }
 }
 
-// TODO TEST_BIT
+// LLIL_REG_STACK_FREE_REL, LLIL_REG_STACK_FREE_REL_SSA
+pub struct RegStackFreeRel;
+
+impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, RegStackFreeRel>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    V: NonSSAVariant,
+{
+    pub fn dest_reg_stack_id(&self) -> u32 {
+        self.op.operands[0] as u32
+    }
+
+    pub fn dest_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.dest_reg_stack_id())
+    }
+
+    pub fn dest_entry(&self) -> Expression<'func, A, M, NonSSA<V>, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[1] as usize,
+            _ty: PhantomData,
+        }
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, RegStackFreeRel>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_reg_stack_id(&self) -> u32 {
+        self.sub_expr_op(0).operands[0] as u32
+    }
+
+    pub fn dest_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.dest_reg_stack_id())
+    }
+
+    pub fn dest_version(&self) -> u32 {
+        self.sub_expr_op(0).operands[1] as u32
+    }
+
+    pub fn source_version(&self) -> u32 {
+        self.sub_expr_op(0).operands[2] as u32
+    }
+
+    pub fn dest_entry(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression {
+            function: self.function,
+            expr_idx: self.op.operands[1] as usize,
+            _ty: PhantomData,
+        }
+    }
+
+    /// Returns the register holding the index of the top of the stack
+    pub fn top_reg(&self) -> SSARegister<A::Register> {
+        let top = self.sub_expr_op(2);
+        self.ssa_reg_from_raw(top.operands[0], top.operands[1])
+    }
+}
+
+// LLIL_REG_STACK_FREE_REG, LLIL_REG_STACK_FREE_ABS_SSA
+pub struct RegStackFreeReg;
+
+impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, RegStackFreeReg>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    V: NonSSAVariant,
+{
+    pub fn dest_reg(&self) -> Register<A::Register> {
+        self.reg_from_raw(self.op.operands[0] as u32)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, RegStackFreeReg>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_reg_stack_id(&self) -> u32 {
+        self.sub_expr_op(0).operands[0] as u32
+    }
+
+    pub fn dest_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.dest_reg_stack_id())
+    }
+
+    pub fn dest_version(&self) -> u32 {
+        self.sub_expr_op(0).operands[1] as u32
+    }
+
+    pub fn source_version(&self) -> u32 {
+        self.sub_expr_op(0).operands[2] as u32
+    }
+
+    pub fn dest_reg(&self) -> Register<A::Register> {
+        self.reg_from_raw(self.op.operands[1] as u32)
+    }
+}
+
+// LLIL_REG_STACK_PHI
+pub struct RegStackPhi;
+
+impl<'func, A, M> Operation<'func, A, M, SSA, RegStackPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_reg_stack_id(&self) -> u32 {
+        self.op.operands[0] as u32
+    }
+
+    pub fn dest_reg_stack(&self) -> Option<A::RegisterStack> {
+        self.reg_stack_from_raw(self.dest_reg_stack_id())
+    }
+
+    pub fn dest_version(&self) -> u32 {
+        self.op.operands[1] as u32
+    }
+
+    pub fn source_versions(&self) -> Vec<u32> {
+        self.operand_list(self.expr_idx, 2)
+            .chunks(2)
+            .map(|pair| pair[1] as u32)
+            .collect()
+    }
+}
+
+// LLIL_INTRINSIC, LLIL_INTRINSIC_SSA
+pub struct Intrinsic;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Intrinsic>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn intrinsic_id(&self) -> u32 {
+        self.op.operands[2] as u32
+    }
+
+    pub fn intrinsic(&self) -> Option<A::Intrinsic> {
+        let intrinsic = self.function.arch().intrinsic_from_id(self.intrinsic_id());
+
+        if intrinsic.is_none() {
+            error!(
+                "got garbage intrinsic from {:?} @ 0x{:x}",
+                self.op.operation, self.op.address
+            );
+        }
+
+        intrinsic
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.operand_list(self.op.operands[3] as usize, 0)
+            .into_iter()
+            .map(|expr_idx| Expression {
+                function: self.function,
+                expr_idx: expr_idx as usize,
+                _ty: PhantomData,
+            })
+            .collect()
+    }
+}
+
+impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, Intrinsic>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    V: NonSSAVariant,
+{
+    /// Returns the registers and flags written by the intrinsic
+    pub fn outputs(&self) -> Vec<RegisterOrFlag<A::Register, A::Flag>> {
+        self.operand_list(self.expr_idx, 0)
+            .into_iter()
+            .filter_map(|raw| self.reg_or_flag_from_raw(raw))
+            .collect()
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Intrinsic>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    /// Returns the registers and flags written by the intrinsic
+    pub fn outputs(&self) -> Vec<SSARegisterOrFlag<A::Register, A::Flag>> {
+        self.operand_list(self.expr_idx, 0)
+            .chunks(2)
+            .filter_map(|pair| match self.reg_or_flag_from_raw(pair[0])? {
+                RegisterOrFlag::Register(reg) => Some(SSARegisterOrFlag::Register(
+                    SSARegister::Full(reg, pair[1] as u32),
+                )),
+                RegisterOrFlag::Flag(flag) => {
+                    Some(SSARegisterOrFlag::Flag(SSAFlag::new(flag, pair[1] as u32)))
+                }
+            })
+            .collect()
+    }
+}
+
+// LLIL_EXTERN_PTR
+pub struct ExternPtr;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, ExternPtr>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn value(&self) -> u64 {
+        self.op.operands[0]
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+// LLIL_FLOAT_CONST
+pub struct FloatConst;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, FloatConst>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    /// Returns the bit pattern of the constant, as it was lifted
+    pub fn raw_value(&self) -> u64 {
+        self.op.operands[0]
+    }
+
+    pub fn value(&self) -> f64 {
+        match self.op.size {
+            4 => f32::from_bits(self.op.operands[0] as u32) as f64,
+            _ => f64::from_bits(self.op.operands[0]),
+        }
+    }
+}
 
 pub trait OperationArguments: 'static {}
 
@@ -732,7 +1714,19 @@ impl OperationArguments for MemPhi {}
 impl OperationArguments for Const {}
 impl OperationArguments for BinaryOp {}
 impl OperationArguments for BinaryOpCarry {}
-impl OperationArguments for DoublePrecDivOp {}
 impl OperationArguments for UnaryOp {}
 impl OperationArguments for Condition {}
 impl OperationArguments for UnimplMem {}
+impl OperationArguments for RegSplit {}
+impl OperationArguments for RegStackPush {}
+impl OperationArguments for RegStackPop {}
+impl OperationArguments for RegStackRel {}
+impl OperationArguments for RegStackAbs {}
+impl OperationArguments for SetRegStackRel {}
+impl OperationArguments for SetRegStackAbs {}
+impl OperationArguments for RegStackFreeRel {}
+impl OperationArguments for RegStackFreeReg {}
+impl OperationArguments for RegStackPhi {}
+impl OperationArguments for Intrinsic {}
+impl OperationArguments for ExternPtr {}
+impl OperationArguments for FloatConst {}
@@ -0,0 +1,1126 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metadata is a tree of values that can be stored in a `BinaryView` (and saved with it in the
+//! database) or in a `TypeLibrary`.
+//!
+//! ```ignore
+//! use binaryninja::binaryview::BinaryViewExt;
+//! use binaryninja::metadata::Metadata;
+//!
+//! bv.store_metadata("triage.version", 3u64, false)?;
+//!
+//! if let Some(Metadata::UnsignedInteger(version)) = bv.query_metadata("triage.version") {
+//!     // ...
+//! }
+//! ```
+//!
+//! With the `serde` feature, anything that implements `Serialize` and `Deserialize` can be
+//! converted to and from `Metadata`:
+//!
+//! ```ignore
+//! use binaryninja::metadata::{from_metadata, to_metadata};
+//!
+//! bv.store_metadata("triage.state", to_metadata(&state)?, false)?;
+//!
+//! let state: TriageState = from_metadata(bv.query_metadata("triage.state").unwrap())?;
+//! ```
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNMetadataType as MetadataType;
+
+use std::collections::BTreeMap;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::slice;
+
+use crate::string::*;
+
+#[cfg(feature = "serde")]
+pub use self::serde_impl::{from_metadata, to_metadata, MetadataError};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Metadata {
+    Bool(bool),
+    String(String),
+    UnsignedInteger(u64),
+    SignedInteger(i64),
+    Double(f64),
+    Raw(Vec<u8>),
+    Array(Vec<Metadata>),
+    KeyValue(BTreeMap<String, Metadata>),
+}
+
+impl Metadata {
+    /// Copies the value of `handle`, which is only borrowed. Returns `None` for invalid
+    /// metadata.
+    pub(crate) unsafe fn from_raw(handle: *mut BNMetadata) -> Option<Self> {
+        if handle.is_null() {
+            return None;
+        }
+
+        let value = match BNMetadataGetType(handle) {
+            MetadataType::InvalidDataType => return None,
+            MetadataType::BooleanDataType => Metadata::Bool(BNMetadataGetBoolean(handle)),
+            MetadataType::StringDataType => {
+                let string = BnString::from_raw(BNMetadataGetString(handle));
+                Metadata::String(string.as_str().to_string())
+            }
+            MetadataType::UnsignedIntegerDataType => {
+                Metadata::UnsignedInteger(BNMetadataGetUnsignedInteger(handle))
+            }
+            MetadataType::SignedIntegerDataType => {
+                Metadata::SignedInteger(BNMetadataGetSignedInteger(handle))
+            }
+            MetadataType::DoubleDataType => Metadata::Double(BNMetadataGetDouble(handle)),
+            MetadataType::RawDataType => {
+                let mut size = 0;
+                let data = BNMetadataGetRaw(handle, &mut size);
+
+                if data.is_null() {
+                    Metadata::Raw(Vec::new())
+                } else {
+                    let raw = slice::from_raw_parts(data, size).to_vec();
+                    BNFreeMetadataRaw(data);
+
+                    Metadata::Raw(raw)
+                }
+            }
+            MetadataType::ArrayDataType => {
+                let mut size = 0;
+                let items = BNMetadataGetArray(handle, &mut size);
+
+                let array = slice::from_raw_parts(items, size)
+                    .iter()
+                    .filter_map(|&item| Metadata::from_raw(item))
+                    .collect();
+
+                BNFreeMetadataArray(items);
+
+                Metadata::Array(array)
+            }
+            MetadataType::KeyValueDataType => {
+                let store = BNMetadataGetValueStore(handle);
+
+                let keys = slice::from_raw_parts((*store).keys, (*store).size);
+                let values = slice::from_raw_parts((*store).values, (*store).size);
+
+                let map = keys
+                    .iter()
+                    .zip(values.iter())
+                    .filter_map(|(&key, &value)| {
+                        let key = BnStr::from_raw(key).as_str().to_string();
+                        Metadata::from_raw(value).map(|value| (key, value))
+                    })
+                    .collect();
+
+                BNFreeMetadataValueStore(store);
+
+                Metadata::KeyValue(map)
+            }
+        };
+
+        Some(value)
+    }
+
+    /// Creates a core object holding a copy of this value. Fails if a string or key contains a
+    /// nul byte, which the core can't store.
+    pub(crate) fn to_raw(&self) -> Result<CoreMetadata, ()> {
+        unsafe {
+            let handle = match self {
+                Metadata::Bool(value) => BNCreateMetadataBooleanData(*value),
+                Metadata::String(value) => {
+                    let value = CString::new(value.as_str()).map_err(|_| ())?;
+                    BNCreateMetadataStringData(value.as_ptr())
+                }
+                Metadata::UnsignedInteger(value) => BNCreateMetadataUnsignedIntegerData(*value),
+                Metadata::SignedInteger(value) => BNCreateMetadataSignedIntegerData(*value),
+                Metadata::Double(value) => BNCreateMetadataDoubleData(*value),
+                Metadata::Raw(value) => BNCreateMetadataRawData(value.as_ptr(), value.len()),
+                Metadata::Array(items) => {
+                    // the array takes its own references to the items
+                    let items = items
+                        .iter()
+                        .map(|i| i.to_raw())
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let mut raw: Vec<*mut BNMetadata> = items.iter().map(|i| i.0).collect();
+
+                    BNCreateMetadataArray(raw.as_mut_ptr(), raw.len())
+                }
+                Metadata::KeyValue(map) => {
+                    let keys = map
+                        .keys()
+                        .map(|k| CString::new(k.as_str()))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|_| ())?;
+                    let values = map
+                        .values()
+                        .map(|v| v.to_raw())
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    let mut raw_keys: Vec<*const c_char> =
+                        keys.iter().map(|k| k.as_ptr()).collect();
+                    let mut raw_values: Vec<*mut BNMetadata> = values.iter().map(|v| v.0).collect();
+
+                    BNCreateMetadataValueStore(
+                        raw_keys.as_mut_ptr(),
+                        raw_values.as_mut_ptr(),
+                        map.len(),
+                    )
+                }
+            };
+
+            Ok(CoreMetadata(handle))
+        }
+    }
+
+    pub fn metadata_type(&self) -> MetadataType {
+        match self {
+            Metadata::Bool(_) => MetadataType::BooleanDataType,
+            Metadata::String(_) => MetadataType::StringDataType,
+            Metadata::UnsignedInteger(_) => MetadataType::UnsignedIntegerDataType,
+            Metadata::SignedInteger(_) => MetadataType::SignedIntegerDataType,
+            Metadata::Double(_) => MetadataType::DoubleDataType,
+            Metadata::Raw(_) => MetadataType::RawDataType,
+            Metadata::Array(_) => MetadataType::ArrayDataType,
+            Metadata::KeyValue(_) => MetadataType::KeyValueDataType,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Metadata::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Metadata::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns integers of either signedness, as long as they fit in a `u64`.
+    pub fn as_unsigned(&self) -> Option<u64> {
+        match self {
+            Metadata::UnsignedInteger(value) => Some(*value),
+            Metadata::SignedInteger(value) if *value >= 0 => Some(*value as u64),
+            _ => None,
+        }
+    }
+
+    /// Returns integers of either signedness, as long as they fit in an `i64`.
+    pub fn as_signed(&self) -> Option<i64> {
+        match self {
+            Metadata::SignedInteger(value) => Some(*value),
+            Metadata::UnsignedInteger(value) if *value <= i64::MAX as u64 => Some(*value as i64),
+            _ => None,
+        }
+    }
+
+    pub fn as_double(&self) -> Option<f64> {
+        match self {
+            Metadata::Double(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_raw(&self) -> Option<&[u8]> {
+        match self {
+            Metadata::Raw(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Metadata]> {
+        match self {
+            Metadata::Array(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&BTreeMap<String, Metadata>> {
+        match self {
+            Metadata::KeyValue(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Looks up `key` in a key-value store.
+    pub fn get(&self, key: &str) -> Option<&Metadata> {
+        self.as_map().and_then(|map| map.get(key))
+    }
+}
+
+impl From<bool> for Metadata {
+    fn from(value: bool) -> Self {
+        Metadata::Bool(value)
+    }
+}
+
+impl From<&str> for Metadata {
+    fn from(value: &str) -> Self {
+        Metadata::String(value.to_string())
+    }
+}
+
+impl From<String> for Metadata {
+    fn from(value: String) -> Self {
+        Metadata::String(value)
+    }
+}
+
+impl From<u64> for Metadata {
+    fn from(value: u64) -> Self {
+        Metadata::UnsignedInteger(value)
+    }
+}
+
+impl From<u32> for Metadata {
+    fn from(value: u32) -> Self {
+        Metadata::UnsignedInteger(value as u64)
+    }
+}
+
+impl From<i64> for Metadata {
+    fn from(value: i64) -> Self {
+        Metadata::SignedInteger(value)
+    }
+}
+
+impl From<i32> for Metadata {
+    fn from(value: i32) -> Self {
+        Metadata::SignedInteger(value as i64)
+    }
+}
+
+impl From<f64> for Metadata {
+    fn from(value: f64) -> Self {
+        Metadata::Double(value)
+    }
+}
+
+impl From<&[u8]> for Metadata {
+    fn from(value: &[u8]) -> Self {
+        Metadata::Raw(value.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for Metadata {
+    fn from(value: Vec<u8>) -> Self {
+        Metadata::Raw(value)
+    }
+}
+
+impl From<Vec<Metadata>> for Metadata {
+    fn from(value: Vec<Metadata>) -> Self {
+        Metadata::Array(value)
+    }
+}
+
+impl From<BTreeMap<String, Metadata>> for Metadata {
+    fn from(value: BTreeMap<String, Metadata>) -> Self {
+        Metadata::KeyValue(value)
+    }
+}
+
+/// An owned reference to a core metadata object.
+pub(crate) struct CoreMetadata(pub(crate) *mut BNMetadata);
+
+impl CoreMetadata {
+    /// Takes ownership of `handle`, which may be null.
+    pub(crate) unsafe fn from_raw(handle: *mut BNMetadata) -> Option<Self> {
+        if handle.is_null() {
+            None
+        } else {
+            Some(CoreMetadata(handle))
+        }
+    }
+
+    pub(crate) fn value(&self) -> Option<Metadata> {
+        unsafe { Metadata::from_raw(self.0) }
+    }
+}
+
+impl Clone for CoreMetadata {
+    fn clone(&self) -> Self {
+        unsafe { CoreMetadata(BNNewMetadataReference(self.0)) }
+    }
+}
+
+impl Drop for CoreMetadata {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe { BNFreeMetadata(self.0) }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use std::collections::BTreeMap;
+    use std::error;
+    use std::fmt;
+
+    use serde::de::value::{MapDeserializer, SeqDeserializer};
+    use serde::de::{
+        self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess,
+        Visitor,
+    };
+    use serde::ser::{self, Serialize};
+
+    use super::Metadata;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct MetadataError(String);
+
+    impl fmt::Display for MetadataError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl error::Error for MetadataError {}
+
+    impl ser::Error for MetadataError {
+        fn custom<T: fmt::Display>(msg: T) -> Self {
+            MetadataError(msg.to_string())
+        }
+    }
+
+    impl de::Error for MetadataError {
+        fn custom<T: fmt::Display>(msg: T) -> Self {
+            MetadataError(msg.to_string())
+        }
+    }
+
+    type Result<T> = std::result::Result<T, MetadataError>;
+
+    /// Converts `value` to `Metadata`.
+    ///
+    /// Structures and maps become key-value stores, sequences and tuples become arrays and enum
+    /// variants with data become a key-value store with the variant name as the only key. There
+    /// is no null metadata, so `None` and `()` are stored as empty arrays, and `Some(value)` as an
+    /// array holding `value`. Map keys have to be strings or integers, and strings can't contain
+    /// nul bytes.
+    pub fn to_metadata<T: Serialize + ?Sized>(value: &T) -> Result<Metadata> {
+        value.serialize(Serializer)
+    }
+
+    /// Converts `metadata` back to a `T`, using the same representation as `to_metadata`.
+    pub fn from_metadata<T: DeserializeOwned>(metadata: Metadata) -> Result<T> {
+        T::deserialize(metadata)
+    }
+
+    fn string(value: String) -> Result<Metadata> {
+        if value.contains('\0') {
+            return Err(MetadataError("strings can't contain nul bytes".into()));
+        }
+
+        Ok(Metadata::String(value))
+    }
+
+    fn variant(name: &'static str, value: Metadata) -> Metadata {
+        let mut map = BTreeMap::new();
+        map.insert(name.to_string(), value);
+        Metadata::KeyValue(map)
+    }
+
+    struct Serializer;
+
+    impl ser::Serializer for Serializer {
+        type Ok = Metadata;
+        type Error = MetadataError;
+
+        type SerializeSeq = SerializeArray;
+        type SerializeTuple = SerializeArray;
+        type SerializeTupleStruct = SerializeArray;
+        type SerializeTupleVariant = SerializeArray;
+        type SerializeMap = SerializeKeyValue;
+        type SerializeStruct = SerializeKeyValue;
+        type SerializeStructVariant = SerializeKeyValue;
+
+        fn serialize_bool(self, v: bool) -> Result<Metadata> {
+            Ok(Metadata::Bool(v))
+        }
+
+        fn serialize_i8(self, v: i8) -> Result<Metadata> {
+            self.serialize_i64(v as i64)
+        }
+
+        fn serialize_i16(self, v: i16) -> Result<Metadata> {
+            self.serialize_i64(v as i64)
+        }
+
+        fn serialize_i32(self, v: i32) -> Result<Metadata> {
+            self.serialize_i64(v as i64)
+        }
+
+        fn serialize_i64(self, v: i64) -> Result<Metadata> {
+            Ok(Metadata::SignedInteger(v))
+        }
+
+        fn serialize_u8(self, v: u8) -> Result<Metadata> {
+            self.serialize_u64(v as u64)
+        }
+
+        fn serialize_u16(self, v: u16) -> Result<Metadata> {
+            self.serialize_u64(v as u64)
+        }
+
+        fn serialize_u32(self, v: u32) -> Result<Metadata> {
+            self.serialize_u64(v as u64)
+        }
+
+        fn serialize_u64(self, v: u64) -> Result<Metadata> {
+            Ok(Metadata::UnsignedInteger(v))
+        }
+
+        fn serialize_f32(self, v: f32) -> Result<Metadata> {
+            self.serialize_f64(v as f64)
+        }
+
+        fn serialize_f64(self, v: f64) -> Result<Metadata> {
+            Ok(Metadata::Double(v))
+        }
+
+        fn serialize_char(self, v: char) -> Result<Metadata> {
+            string(v.to_string())
+        }
+
+        fn serialize_str(self, v: &str) -> Result<Metadata> {
+            string(v.to_string())
+        }
+
+        fn serialize_bytes(self, v: &[u8]) -> Result<Metadata> {
+            Ok(Metadata::Raw(v.to_vec()))
+        }
+
+        fn serialize_none(self) -> Result<Metadata> {
+            Ok(Metadata::Array(Vec::new()))
+        }
+
+        fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Metadata> {
+            Ok(Metadata::Array(vec![value.serialize(Serializer)?]))
+        }
+
+        fn serialize_unit(self) -> Result<Metadata> {
+            Ok(Metadata::Array(Vec::new()))
+        }
+
+        fn serialize_unit_struct(self, _name: &'static str) -> Result<Metadata> {
+            self.serialize_unit()
+        }
+
+        fn serialize_unit_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            variant: &'static str,
+        ) -> Result<Metadata> {
+            Ok(Metadata::String(variant.to_string()))
+        }
+
+        fn serialize_newtype_struct<T: Serialize + ?Sized>(
+            self,
+            _name: &'static str,
+            value: &T,
+        ) -> Result<Metadata> {
+            value.serialize(Serializer)
+        }
+
+        fn serialize_newtype_variant<T: Serialize + ?Sized>(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            variant: &'static str,
+            value: &T,
+        ) -> Result<Metadata> {
+            Ok(self::variant(variant, value.serialize(Serializer)?))
+        }
+
+        fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray> {
+            Ok(SerializeArray {
+                variant: None,
+                items: Vec::with_capacity(len.unwrap_or(0)),
+            })
+        }
+
+        fn serialize_tuple(self, len: usize) -> Result<SerializeArray> {
+            self.serialize_seq(Some(len))
+        }
+
+        fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SerializeArray> {
+            self.serialize_seq(Some(len))
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            variant: &'static str,
+            len: usize,
+        ) -> Result<SerializeArray> {
+            Ok(SerializeArray {
+                variant: Some(variant),
+                items: Vec::with_capacity(len),
+            })
+        }
+
+        fn serialize_map(self, _len: Option<usize>) -> Result<SerializeKeyValue> {
+            Ok(SerializeKeyValue {
+                variant: None,
+                map: BTreeMap::new(),
+                key: None,
+            })
+        }
+
+        fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<SerializeKeyValue> {
+            self.serialize_map(None)
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            variant: &'static str,
+            _len: usize,
+        ) -> Result<SerializeKeyValue> {
+            Ok(SerializeKeyValue {
+                variant: Some(variant),
+                map: BTreeMap::new(),
+                key: None,
+            })
+        }
+    }
+
+    struct SerializeArray {
+        variant: Option<&'static str>,
+        items: Vec<Metadata>,
+    }
+
+    impl SerializeArray {
+        fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+            self.items.push(value.serialize(Serializer)?);
+            Ok(())
+        }
+
+        fn finish(self) -> Result<Metadata> {
+            let array = Metadata::Array(self.items);
+
+            Ok(match self.variant {
+                Some(name) => variant(name, array),
+                None => array,
+            })
+        }
+    }
+
+    impl ser::SerializeSeq for SerializeArray {
+        type Ok = Metadata;
+        type Error = MetadataError;
+
+        fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+            self.push(value)
+        }
+
+        fn end(self) -> Result<Metadata> {
+            self.finish()
+        }
+    }
+
+    impl ser::SerializeTuple for SerializeArray {
+        type Ok = Metadata;
+        type Error = MetadataError;
+
+        fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+            self.push(value)
+        }
+
+        fn end(self) -> Result<Metadata> {
+            self.finish()
+        }
+    }
+
+    impl ser::SerializeTupleStruct for SerializeArray {
+        type Ok = Metadata;
+        type Error = MetadataError;
+
+        fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+            self.push(value)
+        }
+
+        fn end(self) -> Result<Metadata> {
+            self.finish()
+        }
+    }
+
+    impl ser::SerializeTupleVariant for SerializeArray {
+        type Ok = Metadata;
+        type Error = MetadataError;
+
+        fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+            self.push(value)
+        }
+
+        fn end(self) -> Result<Metadata> {
+            self.finish()
+        }
+    }
+
+    struct SerializeKeyValue {
+        variant: Option<&'static str>,
+        map: BTreeMap<String, Metadata>,
+        key: Option<String>,
+    }
+
+    impl SerializeKeyValue {
+        fn finish(self) -> Result<Metadata> {
+            let map = Metadata::KeyValue(self.map);
+
+            Ok(match self.variant {
+                Some(name) => variant(name, map),
+                None => map,
+            })
+        }
+    }
+
+    impl ser::SerializeMap for SerializeKeyValue {
+        type Ok = Metadata;
+        type Error = MetadataError;
+
+        fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+            let key = match key.serialize(Serializer)? {
+                Metadata::String(key) => key,
+                Metadata::UnsignedInteger(key) => key.to_string(),
+                Metadata::SignedInteger(key) => key.to_string(),
+                _ => return Err(MetadataError("map keys must be strings or integers".into())),
+            };
+
+            self.key = Some(key);
+            Ok(())
+        }
+
+        fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+            let key = self
+                .key
+                .take()
+                .ok_or_else(|| MetadataError("map value without a key".into()))?;
+
+            self.map.insert(key, value.serialize(Serializer)?);
+            Ok(())
+        }
+
+        fn end(self) -> Result<Metadata> {
+            self.finish()
+        }
+    }
+
+    impl ser::SerializeStruct for SerializeKeyValue {
+        type Ok = Metadata;
+        type Error = MetadataError;
+
+        fn serialize_field<T: Serialize + ?Sized>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<()> {
+            self.map
+                .insert(key.to_string(), value.serialize(Serializer)?);
+            Ok(())
+        }
+
+        fn end(self) -> Result<Metadata> {
+            self.finish()
+        }
+    }
+
+    impl ser::SerializeStructVariant for SerializeKeyValue {
+        type Ok = Metadata;
+        type Error = MetadataError;
+
+        fn serialize_field<T: Serialize + ?Sized>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<()> {
+            self.map
+                .insert(key.to_string(), value.serialize(Serializer)?);
+            Ok(())
+        }
+
+        fn end(self) -> Result<Metadata> {
+            self.finish()
+        }
+    }
+
+    impl<'de> IntoDeserializer<'de, MetadataError> for Metadata {
+        type Deserializer = Self;
+
+        fn into_deserializer(self) -> Self {
+            self
+        }
+    }
+
+    impl<'de> de::Deserializer<'de> for Metadata {
+        type Error = MetadataError;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            match self {
+                Metadata::Bool(v) => visitor.visit_bool(v),
+                Metadata::String(v) => visitor.visit_string(v),
+                Metadata::UnsignedInteger(v) => visitor.visit_u64(v),
+                Metadata::SignedInteger(v) => visitor.visit_i64(v),
+                Metadata::Double(v) => visitor.visit_f64(v),
+                Metadata::Raw(v) => visitor.visit_byte_buf(v),
+                Metadata::Array(v) => {
+                    let mut seq = SeqDeserializer::new(v.into_iter());
+                    let value = visitor.visit_seq(&mut seq)?;
+                    seq.end()?;
+                    Ok(value)
+                }
+                Metadata::KeyValue(v) => {
+                    let mut map = MapDeserializer::new(v.into_iter().map(|(k, v)| (MapKey(k), v)));
+                    let value = visitor.visit_map(&mut map)?;
+                    map.end()?;
+                    Ok(value)
+                }
+            }
+        }
+
+        fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            match self {
+                Metadata::Array(mut v) if v.len() <= 1 => match v.pop() {
+                    Some(value) => visitor.visit_some(value),
+                    None => visitor.visit_none(),
+                },
+                other => Err(de::Error::invalid_type(other.unexpected(), &visitor)),
+            }
+        }
+
+        fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            match self {
+                Metadata::Array(v) if v.is_empty() => visitor.visit_unit(),
+                other => Err(de::Error::invalid_type(other.unexpected(), &visitor)),
+            }
+        }
+
+        fn deserialize_unit_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            visitor: V,
+        ) -> Result<V::Value> {
+            self.deserialize_unit(visitor)
+        }
+
+        fn deserialize_newtype_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            visitor: V,
+        ) -> Result<V::Value> {
+            visitor.visit_newtype_struct(self)
+        }
+
+        fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            match self {
+                // bytes serialized through `serialize_bytes` into something that's read as a
+                // sequence, like a `Vec<u8>`
+                Metadata::Raw(v) => {
+                    let mut seq = SeqDeserializer::new(v.into_iter());
+                    let value = visitor.visit_seq(&mut seq)?;
+                    seq.end()?;
+                    Ok(value)
+                }
+                other => other.deserialize_any(visitor),
+            }
+        }
+
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            _variants: &'static [&'static str],
+            visitor: V,
+        ) -> Result<V::Value> {
+            match self {
+                Metadata::String(variant) => visitor.visit_enum(Enum {
+                    variant,
+                    value: None,
+                }),
+                Metadata::KeyValue(map) if map.len() == 1 => {
+                    let (variant, value) = map.into_iter().next().unwrap();
+                    visitor.visit_enum(Enum {
+                        variant,
+                        value: Some(value),
+                    })
+                }
+                other => Err(de::Error::invalid_type(other.unexpected(), &visitor)),
+            }
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf tuple tuple_struct map struct identifier ignored_any
+        }
+    }
+
+    /// Map keys are always strings, but may have been integers when serialized.
+    struct MapKey(String);
+
+    macro_rules! deserialize_parsed_key {
+        ($($method:ident => $visit:ident),*) => {
+            $(
+                fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                    match self.0.parse() {
+                        Ok(key) => visitor.$visit(key),
+                        Err(_) => Err(de::Error::invalid_value(
+                            de::Unexpected::Str(&self.0),
+                            &visitor,
+                        )),
+                    }
+                }
+            )*
+        };
+    }
+
+    impl<'de> IntoDeserializer<'de, MetadataError> for MapKey {
+        type Deserializer = Self;
+
+        fn into_deserializer(self) -> Self {
+            self
+        }
+    }
+
+    impl<'de> de::Deserializer<'de> for MapKey {
+        type Error = MetadataError;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            visitor.visit_string(self.0)
+        }
+
+        fn deserialize_newtype_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            visitor: V,
+        ) -> Result<V::Value> {
+            visitor.visit_newtype_struct(self)
+        }
+
+        deserialize_parsed_key! {
+            deserialize_i8 => visit_i8,
+            deserialize_i16 => visit_i16,
+            deserialize_i32 => visit_i32,
+            deserialize_i64 => visit_i64,
+            deserialize_u8 => visit_u8,
+            deserialize_u16 => visit_u16,
+            deserialize_u32 => visit_u32,
+            deserialize_u64 => visit_u64
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i128 u128 f32 f64 char str string bytes byte_buf option unit unit_struct seq
+            tuple tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    impl Metadata {
+        fn unexpected(&self) -> de::Unexpected<'_> {
+            match self {
+                Metadata::Bool(v) => de::Unexpected::Bool(*v),
+                Metadata::String(v) => de::Unexpected::Str(v),
+                Metadata::UnsignedInteger(v) => de::Unexpected::Unsigned(*v),
+                Metadata::SignedInteger(v) => de::Unexpected::Signed(*v),
+                Metadata::Double(v) => de::Unexpected::Float(*v),
+                Metadata::Raw(v) => de::Unexpected::Bytes(v),
+                Metadata::Array(_) => de::Unexpected::Seq,
+                Metadata::KeyValue(_) => de::Unexpected::Map,
+            }
+        }
+    }
+
+    struct Enum {
+        variant: String,
+        value: Option<Metadata>,
+    }
+
+    impl<'de> EnumAccess<'de> for Enum {
+        type Error = MetadataError;
+        type Variant = Variant;
+
+        fn variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, Variant)> {
+            let variant = seed.deserialize(Metadata::String(self.variant))?;
+            Ok((variant, Variant(self.value)))
+        }
+    }
+
+    struct Variant(Option<Metadata>);
+
+    impl<'de> VariantAccess<'de> for Variant {
+        type Error = MetadataError;
+
+        fn unit_variant(self) -> Result<()> {
+            match self.0 {
+                None => Ok(()),
+                Some(value) => {
+                    de::Deserializer::deserialize_unit(value, de::IgnoredAny).map(|_| ())
+                }
+            }
+        }
+
+        fn newtype_variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<S::Value> {
+            match self.0 {
+                Some(value) => seed.deserialize(value),
+                None => Err(de::Error::invalid_type(
+                    de::Unexpected::UnitVariant,
+                    &"newtype variant",
+                )),
+            }
+        }
+
+        fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+            match self.0 {
+                Some(value) => de::Deserializer::deserialize_seq(value, visitor),
+                None => Err(de::Error::invalid_type(
+                    de::Unexpected::UnitVariant,
+                    &"tuple variant",
+                )),
+            }
+        }
+
+        fn struct_variant<V: Visitor<'de>>(
+            self,
+            _fields: &'static [&'static str],
+            visitor: V,
+        ) -> Result<V::Value> {
+            match self.0 {
+                Some(value) => de::Deserializer::deserialize_map(value, visitor),
+                None => Err(de::Error::invalid_type(
+                    de::Unexpected::UnitVariant,
+                    &"struct variant",
+                )),
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::fmt::Debug;
+
+    use serde::de::{DeserializeOwned, Deserializer, Visitor};
+    use serde::{Deserialize, Serialize, Serializer};
+
+    use super::*;
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T) -> Metadata {
+        let metadata = to_metadata(&value).unwrap();
+        assert_eq!(from_metadata::<T>(metadata.clone()).unwrap(), value);
+        metadata
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Line(i32, i32),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(PartialEq, Debug)]
+    struct Bytes(Vec<u8>);
+
+    impl Serialize for Bytes {
+        fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Bytes {
+        fn deserialize<D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> std::result::Result<Self, D::Error> {
+            struct BytesVisitor;
+
+            impl<'de> Visitor<'de> for BytesVisitor {
+                type Value = Bytes;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("bytes")
+                }
+
+                fn visit_byte_buf<E>(self, v: Vec<u8>) -> std::result::Result<Bytes, E> {
+                    Ok(Bytes(v))
+                }
+            }
+
+            deserializer.deserialize_byte_buf(BytesVisitor)
+        }
+    }
+
+    #[test]
+    fn enums() {
+        assert_eq!(round_trip(Shape::Empty), Metadata::String("Empty".into()));
+        round_trip(Shape::Circle(1.5));
+        round_trip(Shape::Line(-1, 2));
+        round_trip(Shape::Rect {
+            width: 3,
+            height: 4,
+        });
+        round_trip(vec![Shape::Empty, Shape::Circle(0.0)]);
+    }
+
+    #[test]
+    fn nested_options() {
+        assert_eq!(round_trip(None::<Option<u32>>), Metadata::Array(vec![]));
+        assert_eq!(
+            round_trip(Some(None::<u32>)),
+            Metadata::Array(vec![Metadata::Array(vec![])])
+        );
+        round_trip(Some(Some(7u32)));
+        round_trip(Some(Some(())));
+    }
+
+    #[test]
+    fn integer_map_keys() {
+        let mut unsigned = BTreeMap::new();
+        unsigned.insert(0x1000u64, "start".to_string());
+        unsigned.insert(u64::MAX, "end".to_string());
+        round_trip(unsigned);
+
+        let mut signed = BTreeMap::new();
+        signed.insert(-8i32, true);
+        signed.insert(8i32, false);
+        round_trip(signed);
+    }
+
+    #[test]
+    fn bytes() {
+        let raw = round_trip(Bytes(vec![0, 1, 0xff]));
+        assert_eq!(raw, Metadata::Raw(vec![0, 1, 0xff]));
+
+        assert_eq!(from_metadata::<Vec<u8>>(raw).unwrap(), vec![0, 1, 0xff]);
+        round_trip(vec![0u8, 1, 0xff]);
+    }
+
+    #[test]
+    fn nul_in_string() {
+        assert!(to_metadata("a\0b").is_err());
+        assert!(to_metadata(&'\0').is_err());
+
+        let mut map = BTreeMap::new();
+        map.insert("a\0b".to_string(), 1u32);
+        assert!(to_metadata(&map).is_err());
+    }
+}
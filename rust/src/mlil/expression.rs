@@ -0,0 +1,687 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNGetMediumLevelILByIndex;
+use binaryninjacore_sys::BNMediumLevelILInstruction;
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::operation;
+use super::operation::Operation;
+use super::*;
+
+use crate::architecture::Architecture;
+use crate::rc::Ref;
+use crate::types::{Conf, Type};
+
+pub use crate::llil::{ExpressionResultType, ValueExpr, VoidExpr};
+
+pub struct Expression<'func, A, M, F, R>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+    R: ExpressionResultType,
+{
+    pub(crate) function: &'func Function<A, M, F>,
+    pub(crate) expr_idx: usize,
+
+    // tag the 'return' type of this expression
+    pub(crate) _ty: PhantomData<R>,
+}
+
+impl<'func, A, M, F, R> Expression<'func, A, M, F, R>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+    R: ExpressionResultType,
+{
+    pub(crate) fn new(function: &'func Function<A, M, F>, expr_idx: usize) -> Self {
+        Self {
+            function,
+            expr_idx,
+            _ty: PhantomData,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.expr_idx
+    }
+}
+
+impl<'func, A, M> fmt::Debug for Expression<'func, A, M, NonSSA, ValueExpr>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op_info = self.info();
+        write!(f, "<expr {}: {:?}>", self.expr_idx, op_info)
+    }
+}
+
+fn common_info<'func, A, M, F>(
+    function: &'func Function<A, M, F>,
+    expr_idx: usize,
+    op: BNMediumLevelILInstruction,
+) -> ExprInfo<'func, A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    use binaryninjacore_sys::BNMediumLevelILOperation::*;
+
+    match op.operation {
+        MLIL_ADDRESS_OF => ExprInfo::AddressOf(Operation::new(function, expr_idx, op)),
+        MLIL_ADDRESS_OF_FIELD => ExprInfo::AddressOfField(Operation::new(function, expr_idx, op)),
+
+        MLIL_CONST => ExprInfo::Const(Operation::new(function, expr_idx, op)),
+        MLIL_CONST_PTR => ExprInfo::ConstPtr(Operation::new(function, expr_idx, op)),
+        MLIL_EXTERN_PTR => ExprInfo::ExternPtr(Operation::new(function, expr_idx, op)),
+        MLIL_FLOAT_CONST => ExprInfo::FloatConst(Operation::new(function, expr_idx, op)),
+        MLIL_IMPORT => ExprInfo::Import(Operation::new(function, expr_idx, op)),
+
+        MLIL_ADD => ExprInfo::Add(Operation::new(function, expr_idx, op)),
+        MLIL_ADC => ExprInfo::Adc(Operation::new(function, expr_idx, op)),
+        MLIL_SUB => ExprInfo::Sub(Operation::new(function, expr_idx, op)),
+        MLIL_SBB => ExprInfo::Sbb(Operation::new(function, expr_idx, op)),
+        MLIL_AND => ExprInfo::And(Operation::new(function, expr_idx, op)),
+        MLIL_OR => ExprInfo::Or(Operation::new(function, expr_idx, op)),
+        MLIL_XOR => ExprInfo::Xor(Operation::new(function, expr_idx, op)),
+        MLIL_LSL => ExprInfo::Lsl(Operation::new(function, expr_idx, op)),
+        MLIL_LSR => ExprInfo::Lsr(Operation::new(function, expr_idx, op)),
+        MLIL_ASR => ExprInfo::Asr(Operation::new(function, expr_idx, op)),
+        MLIL_ROL => ExprInfo::Rol(Operation::new(function, expr_idx, op)),
+        MLIL_RLC => ExprInfo::Rlc(Operation::new(function, expr_idx, op)),
+        MLIL_ROR => ExprInfo::Ror(Operation::new(function, expr_idx, op)),
+        MLIL_RRC => ExprInfo::Rrc(Operation::new(function, expr_idx, op)),
+        MLIL_MUL => ExprInfo::Mul(Operation::new(function, expr_idx, op)),
+
+        MLIL_MULU_DP => ExprInfo::MuluDp(Operation::new(function, expr_idx, op)),
+        MLIL_MULS_DP => ExprInfo::MulsDp(Operation::new(function, expr_idx, op)),
+
+        MLIL_DIVU => ExprInfo::Divu(Operation::new(function, expr_idx, op)),
+        MLIL_DIVS => ExprInfo::Divs(Operation::new(function, expr_idx, op)),
+
+        MLIL_DIVU_DP => ExprInfo::DivuDp(Operation::new(function, expr_idx, op)),
+        MLIL_DIVS_DP => ExprInfo::DivsDp(Operation::new(function, expr_idx, op)),
+
+        MLIL_MODU => ExprInfo::Modu(Operation::new(function, expr_idx, op)),
+        MLIL_MODS => ExprInfo::Mods(Operation::new(function, expr_idx, op)),
+
+        MLIL_MODU_DP => ExprInfo::ModuDp(Operation::new(function, expr_idx, op)),
+        MLIL_MODS_DP => ExprInfo::ModsDp(Operation::new(function, expr_idx, op)),
+
+        MLIL_NEG => ExprInfo::Neg(Operation::new(function, expr_idx, op)),
+        MLIL_NOT => ExprInfo::Not(Operation::new(function, expr_idx, op)),
+
+        MLIL_SX => ExprInfo::Sx(Operation::new(function, expr_idx, op)),
+        MLIL_ZX => ExprInfo::Zx(Operation::new(function, expr_idx, op)),
+        MLIL_LOW_PART => ExprInfo::LowPart(Operation::new(function, expr_idx, op)),
+
+        MLIL_CMP_E => ExprInfo::CmpE(Operation::new(function, expr_idx, op)),
+        MLIL_CMP_NE => ExprInfo::CmpNe(Operation::new(function, expr_idx, op)),
+        MLIL_CMP_SLT => ExprInfo::CmpSlt(Operation::new(function, expr_idx, op)),
+        MLIL_CMP_ULT => ExprInfo::CmpUlt(Operation::new(function, expr_idx, op)),
+        MLIL_CMP_SLE => ExprInfo::CmpSle(Operation::new(function, expr_idx, op)),
+        MLIL_CMP_ULE => ExprInfo::CmpUle(Operation::new(function, expr_idx, op)),
+        MLIL_CMP_SGE => ExprInfo::CmpSge(Operation::new(function, expr_idx, op)),
+        MLIL_CMP_UGE => ExprInfo::CmpUge(Operation::new(function, expr_idx, op)),
+        MLIL_CMP_SGT => ExprInfo::CmpSgt(Operation::new(function, expr_idx, op)),
+        MLIL_CMP_UGT => ExprInfo::CmpUgt(Operation::new(function, expr_idx, op)),
+
+        MLIL_TEST_BIT => ExprInfo::TestBit(Operation::new(function, expr_idx, op)),
+        MLIL_BOOL_TO_INT => ExprInfo::BoolToInt(Operation::new(function, expr_idx, op)),
+        MLIL_ADD_OVERFLOW => ExprInfo::AddOverflow(Operation::new(function, expr_idx, op)),
+
+        MLIL_FADD => ExprInfo::Fadd(Operation::new(function, expr_idx, op)),
+        MLIL_FSUB => ExprInfo::Fsub(Operation::new(function, expr_idx, op)),
+        MLIL_FMUL => ExprInfo::Fmul(Operation::new(function, expr_idx, op)),
+        MLIL_FDIV => ExprInfo::Fdiv(Operation::new(function, expr_idx, op)),
+
+        MLIL_FSQRT => ExprInfo::Fsqrt(Operation::new(function, expr_idx, op)),
+        MLIL_FNEG => ExprInfo::Fneg(Operation::new(function, expr_idx, op)),
+        MLIL_FABS => ExprInfo::Fabs(Operation::new(function, expr_idx, op)),
+        MLIL_FLOAT_TO_INT => ExprInfo::FloatToInt(Operation::new(function, expr_idx, op)),
+        MLIL_INT_TO_FLOAT => ExprInfo::IntToFloat(Operation::new(function, expr_idx, op)),
+        MLIL_FLOAT_CONV => ExprInfo::FloatConv(Operation::new(function, expr_idx, op)),
+        MLIL_ROUND_TO_INT => ExprInfo::RoundToInt(Operation::new(function, expr_idx, op)),
+        MLIL_FLOOR => ExprInfo::Floor(Operation::new(function, expr_idx, op)),
+        MLIL_CEIL => ExprInfo::Ceil(Operation::new(function, expr_idx, op)),
+        MLIL_FTRUNC => ExprInfo::Ftrunc(Operation::new(function, expr_idx, op)),
+
+        MLIL_FCMP_E => ExprInfo::FcmpE(Operation::new(function, expr_idx, op)),
+        MLIL_FCMP_NE => ExprInfo::FcmpNe(Operation::new(function, expr_idx, op)),
+        MLIL_FCMP_LT => ExprInfo::FcmpLt(Operation::new(function, expr_idx, op)),
+        MLIL_FCMP_LE => ExprInfo::FcmpLe(Operation::new(function, expr_idx, op)),
+        MLIL_FCMP_GE => ExprInfo::FcmpGe(Operation::new(function, expr_idx, op)),
+        MLIL_FCMP_GT => ExprInfo::FcmpGt(Operation::new(function, expr_idx, op)),
+        MLIL_FCMP_O => ExprInfo::FcmpO(Operation::new(function, expr_idx, op)),
+        MLIL_FCMP_UO => ExprInfo::FcmpUo(Operation::new(function, expr_idx, op)),
+
+        MLIL_UNIMPL => ExprInfo::Unimpl(Operation::new(function, expr_idx, op)),
+        MLIL_UNIMPL_MEM => ExprInfo::UnimplMem(Operation::new(function, expr_idx, op)),
+
+        _ => {
+            #[cfg(debug_assertions)]
+            {
+                error!(
+                    "Got unexpected operation {:?} in value expr at 0x{:x}",
+                    op.operation, op.address
+                );
+            }
+
+            ExprInfo::Undef(Operation::new(function, expr_idx, op))
+        }
+    }
+}
+
+use super::VisitorAction;
+
+macro_rules! visit {
+    ($f:expr, $($e:expr),*) => {
+        if let VisitorAction::Halt = $f($($e,)*) {
+            return VisitorAction::Halt;
+        }
+    }
+}
+
+fn common_visit<'func, A, M, F, CB>(info: &ExprInfo<'func, A, M, F>, f: &mut CB) -> VisitorAction
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+    CB: FnMut(&Expression<'func, A, M, F, ValueExpr>) -> VisitorAction,
+{
+    use self::ExprInfo::*;
+
+    if let Some(op) = info.as_cmp_op() {
+        visit!(f, &op.left());
+        visit!(f, &op.right());
+    } else if let Some(op) = info.as_binary_op() {
+        visit!(f, &op.left());
+        visit!(f, &op.right());
+    } else if let Some(op) = info.as_binary_op_carry() {
+        visit!(f, &op.left());
+        visit!(f, &op.right());
+        visit!(f, &op.carry());
+    } else if let Some(op) = info.as_unary_op() {
+        visit!(f, &op.operand());
+    } else if let UnimplMem(ref op) = *info {
+        visit!(f, &op.mem_expr());
+    }
+
+    VisitorAction::Sibling
+}
+
+impl<'func, A, M> Expression<'func, A, M, NonSSA, ValueExpr>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub(crate) unsafe fn info_from_op(
+        &self,
+        op: BNMediumLevelILInstruction,
+    ) -> ExprInfo<'func, A, M, NonSSA> {
+        use binaryninjacore_sys::BNMediumLevelILOperation::*;
+
+        let expr_idx = self.expr_idx;
+
+        match op.operation {
+            MLIL_LOAD => ExprInfo::Load(Operation::new(self.function, expr_idx, op)),
+            MLIL_LOAD_STRUCT => ExprInfo::LoadStruct(Operation::new(self.function, expr_idx, op)),
+            MLIL_VAR => ExprInfo::Var(Operation::new(self.function, expr_idx, op)),
+            MLIL_VAR_FIELD => ExprInfo::VarField(Operation::new(self.function, expr_idx, op)),
+            MLIL_VAR_SPLIT => ExprInfo::VarSplit(Operation::new(self.function, expr_idx, op)),
+            _ => common_info(self.function, expr_idx, op),
+        }
+    }
+
+    pub fn info(&self) -> ExprInfo<'func, A, M, NonSSA> {
+        unsafe {
+            let op = BNGetMediumLevelILByIndex(self.function.handle, self.expr_idx);
+            self.info_from_op(op)
+        }
+    }
+
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(&Self, &ExprInfo<'func, A, M, NonSSA>) -> VisitorAction,
+    {
+        use self::ExprInfo::*;
+
+        let info = self.info();
+
+        match f(self, &info) {
+            VisitorAction::Descend => {}
+            action => return action,
+        };
+
+        match info {
+            Load(ref op) => visit!(Self::visit_tree, &op.source_mem_expr(), f),
+            LoadStruct(ref op) => visit!(Self::visit_tree, &op.source_mem_expr(), f),
+            _ => {
+                let mut fb = |e: &Self| e.visit_tree(f);
+                visit!(common_visit, &info, &mut fb);
+            }
+        };
+
+        VisitorAction::Sibling
+    }
+}
+
+impl<'func, A, M> Expression<'func, A, M, SSA, ValueExpr>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub(crate) unsafe fn info_from_op(
+        &self,
+        op: BNMediumLevelILInstruction,
+    ) -> ExprInfo<'func, A, M, SSA> {
+        use binaryninjacore_sys::BNMediumLevelILOperation::*;
+
+        let expr_idx = self.expr_idx;
+
+        match op.operation {
+            MLIL_LOAD_SSA => ExprInfo::Load(Operation::new(self.function, expr_idx, op)),
+            MLIL_LOAD_STRUCT_SSA => {
+                ExprInfo::LoadStruct(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_VAR_SSA => ExprInfo::Var(Operation::new(self.function, expr_idx, op)),
+            MLIL_VAR_SSA_FIELD => ExprInfo::VarField(Operation::new(self.function, expr_idx, op)),
+            MLIL_VAR_SPLIT_SSA => ExprInfo::VarSplit(Operation::new(self.function, expr_idx, op)),
+            MLIL_VAR_ALIASED => ExprInfo::VarAliased(Operation::new(self.function, expr_idx, op)),
+            MLIL_VAR_ALIASED_FIELD => {
+                ExprInfo::VarAliasedField(Operation::new(self.function, expr_idx, op))
+            }
+            _ => common_info(self.function, expr_idx, op),
+        }
+    }
+
+    pub fn info(&self) -> ExprInfo<'func, A, M, SSA> {
+        unsafe {
+            let op = BNGetMediumLevelILByIndex(self.function.handle, self.expr_idx);
+            self.info_from_op(op)
+        }
+    }
+
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(&Self, &ExprInfo<'func, A, M, SSA>) -> VisitorAction,
+    {
+        use self::ExprInfo::*;
+
+        let info = self.info();
+
+        match f(self, &info) {
+            VisitorAction::Descend => {}
+            action => return action,
+        };
+
+        match info {
+            Load(ref op) => visit!(Self::visit_tree, &op.source_mem_expr(), f),
+            LoadStruct(ref op) => visit!(Self::visit_tree, &op.source_mem_expr(), f),
+            _ => {
+                let mut fb = |e: &Self| e.visit_tree(f);
+                visit!(common_visit, &info, &mut fb);
+            }
+        };
+
+        VisitorAction::Sibling
+    }
+}
+
+impl<'func, A, F> Expression<'func, A, Finalized, F, ValueExpr>
+where
+    A: 'func + Architecture,
+    F: FunctionForm,
+{
+    /// Returns the type analysis assigned to the result of this expression
+    pub fn expr_type(&self) -> Option<Conf<Ref<Type>>> {
+        use binaryninjacore_sys::BNGetMediumLevelILExprType;
+
+        let result = unsafe { BNGetMediumLevelILExprType(self.function.handle, self.expr_idx) };
+
+        if result.type_.is_null() {
+            None
+        } else {
+            Some(result.into())
+        }
+    }
+}
+
+pub enum ExprInfo<'func, A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    Load(Operation<'func, A, M, F, operation::Load>),
+    LoadStruct(Operation<'func, A, M, F, operation::LoadStruct>),
+    Var(Operation<'func, A, M, F, operation::Var>),
+    VarField(Operation<'func, A, M, F, operation::VarField>),
+    VarSplit(Operation<'func, A, M, F, operation::VarSplit>),
+    VarAliased(Operation<'func, A, M, F, operation::Var>),
+    VarAliasedField(Operation<'func, A, M, F, operation::VarField>),
+    AddressOf(Operation<'func, A, M, F, operation::AddressOf>),
+    AddressOfField(Operation<'func, A, M, F, operation::AddressOfField>),
+
+    Const(Operation<'func, A, M, F, operation::Const>),
+    ConstPtr(Operation<'func, A, M, F, operation::Const>),
+    ExternPtr(Operation<'func, A, M, F, operation::ExternPtr>),
+    FloatConst(Operation<'func, A, M, F, operation::FloatConst>),
+    Import(Operation<'func, A, M, F, operation::Const>),
+
+    Add(Operation<'func, A, M, F, operation::BinaryOp>),
+    Adc(Operation<'func, A, M, F, operation::BinaryOpCarry>),
+    Sub(Operation<'func, A, M, F, operation::BinaryOp>),
+    Sbb(Operation<'func, A, M, F, operation::BinaryOpCarry>),
+    And(Operation<'func, A, M, F, operation::BinaryOp>),
+    Or(Operation<'func, A, M, F, operation::BinaryOp>),
+    Xor(Operation<'func, A, M, F, operation::BinaryOp>),
+    Lsl(Operation<'func, A, M, F, operation::BinaryOp>),
+    Lsr(Operation<'func, A, M, F, operation::BinaryOp>),
+    Asr(Operation<'func, A, M, F, operation::BinaryOp>),
+    Rol(Operation<'func, A, M, F, operation::BinaryOp>),
+    Rlc(Operation<'func, A, M, F, operation::BinaryOpCarry>),
+    Ror(Operation<'func, A, M, F, operation::BinaryOp>),
+    Rrc(Operation<'func, A, M, F, operation::BinaryOpCarry>),
+    Mul(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    MulsDp(Operation<'func, A, M, F, operation::BinaryOp>),
+    MuluDp(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Divu(Operation<'func, A, M, F, operation::BinaryOp>),
+    Divs(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    DivuDp(Operation<'func, A, M, F, operation::BinaryOp>),
+    DivsDp(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Modu(Operation<'func, A, M, F, operation::BinaryOp>),
+    Mods(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    ModuDp(Operation<'func, A, M, F, operation::BinaryOp>),
+    ModsDp(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Neg(Operation<'func, A, M, F, operation::UnaryOp>),
+    Not(Operation<'func, A, M, F, operation::UnaryOp>),
+    Sx(Operation<'func, A, M, F, operation::UnaryOp>),
+    Zx(Operation<'func, A, M, F, operation::UnaryOp>),
+    LowPart(Operation<'func, A, M, F, operation::UnaryOp>),
+
+    CmpE(Operation<'func, A, M, F, operation::Condition>),
+    CmpNe(Operation<'func, A, M, F, operation::Condition>),
+    CmpSlt(Operation<'func, A, M, F, operation::Condition>),
+    CmpUlt(Operation<'func, A, M, F, operation::Condition>),
+    CmpSle(Operation<'func, A, M, F, operation::Condition>),
+    CmpUle(Operation<'func, A, M, F, operation::Condition>),
+    CmpSge(Operation<'func, A, M, F, operation::Condition>),
+    CmpUge(Operation<'func, A, M, F, operation::Condition>),
+    CmpSgt(Operation<'func, A, M, F, operation::Condition>),
+    CmpUgt(Operation<'func, A, M, F, operation::Condition>),
+
+    TestBit(Operation<'func, A, M, F, operation::Condition>),
+    BoolToInt(Operation<'func, A, M, F, operation::UnaryOp>),
+    AddOverflow(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Fadd(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fsub(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fmul(Operation<'func, A, M, F, operation::BinaryOp>),
+    Fdiv(Operation<'func, A, M, F, operation::BinaryOp>),
+
+    Fsqrt(Operation<'func, A, M, F, operation::UnaryOp>),
+    Fneg(Operation<'func, A, M, F, operation::UnaryOp>),
+    Fabs(Operation<'func, A, M, F, operation::UnaryOp>),
+    FloatToInt(Operation<'func, A, M, F, operation::UnaryOp>),
+    IntToFloat(Operation<'func, A, M, F, operation::UnaryOp>),
+    FloatConv(Operation<'func, A, M, F, operation::UnaryOp>),
+    RoundToInt(Operation<'func, A, M, F, operation::UnaryOp>),
+    Floor(Operation<'func, A, M, F, operation::UnaryOp>),
+    Ceil(Operation<'func, A, M, F, operation::UnaryOp>),
+    Ftrunc(Operation<'func, A, M, F, operation::UnaryOp>),
+
+    FcmpE(Operation<'func, A, M, F, operation::Condition>),
+    FcmpNe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpLt(Operation<'func, A, M, F, operation::Condition>),
+    FcmpLe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpGe(Operation<'func, A, M, F, operation::Condition>),
+    FcmpGt(Operation<'func, A, M, F, operation::Condition>),
+    FcmpO(Operation<'func, A, M, F, operation::Condition>),
+    FcmpUo(Operation<'func, A, M, F, operation::Condition>),
+
+    Unimpl(Operation<'func, A, M, F, operation::NoArgs>),
+    UnimplMem(Operation<'func, A, M, F, operation::UnimplMem>),
+
+    Undef(Operation<'func, A, M, F, operation::NoArgs>),
+}
+
+impl<'func, A, M, F> ExprInfo<'func, A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    /// Returns the size of the result of this expression
+    ///
+    /// If the expression is malformed or is `Unimpl` there
+    /// is no meaningful size associated with the result.
+    pub fn size(&self) -> Option<usize> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Undef(..) | Unimpl(..) => None,
+
+            _ if self.as_cmp_op().is_some() => Some(0),
+
+            _ => Some(self.raw_struct().size),
+        }
+    }
+
+    pub fn address(&self) -> u64 {
+        self.raw_struct().address
+    }
+
+    /// Determines if the expressions represent the same operation
+    ///
+    /// It does not examine the operands for equality.
+    pub fn is_same_op_as(&self, other: &Self) -> bool {
+        self.raw_struct().operation == other.raw_struct().operation
+    }
+
+    pub fn as_cmp_op(&self) -> Option<&Operation<'func, A, M, F, operation::Condition>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            CmpE(ref op) | CmpNe(ref op) | CmpSlt(ref op) | CmpUlt(ref op) | CmpSle(ref op)
+            | CmpUle(ref op) | CmpSge(ref op) | CmpUge(ref op) | CmpSgt(ref op)
+            | CmpUgt(ref op) | TestBit(ref op) | FcmpE(ref op) | FcmpNe(ref op)
+            | FcmpLt(ref op) | FcmpLe(ref op) | FcmpGe(ref op) | FcmpGt(ref op) | FcmpO(ref op)
+            | FcmpUo(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub fn as_binary_op(&self) -> Option<&Operation<'func, A, M, F, operation::BinaryOp>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Add(ref op) | Sub(ref op) | And(ref op) | Or(ref op) | Xor(ref op) | Lsl(ref op)
+            | Lsr(ref op) | Asr(ref op) | Rol(ref op) | Ror(ref op) | Mul(ref op)
+            | MulsDp(ref op) | MuluDp(ref op) | Divu(ref op) | Divs(ref op) | DivuDp(ref op)
+            | DivsDp(ref op) | Modu(ref op) | Mods(ref op) | ModuDp(ref op) | ModsDp(ref op)
+            | AddOverflow(ref op) | Fadd(ref op) | Fsub(ref op) | Fmul(ref op) | Fdiv(ref op) => {
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_binary_op_carry(
+        &self,
+    ) -> Option<&Operation<'func, A, M, F, operation::BinaryOpCarry>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Adc(ref op) | Sbb(ref op) | Rlc(ref op) | Rrc(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub fn as_unary_op(&self) -> Option<&Operation<'func, A, M, F, operation::UnaryOp>> {
+        use self::ExprInfo::*;
+
+        match *self {
+            Neg(ref op) | Not(ref op) | Sx(ref op) | Zx(ref op) | LowPart(ref op)
+            | BoolToInt(ref op) | Fsqrt(ref op) | Fneg(ref op) | Fabs(ref op)
+            | FloatToInt(ref op) | IntToFloat(ref op) | FloatConv(ref op) | RoundToInt(ref op)
+            | Floor(ref op) | Ceil(ref op) | Ftrunc(ref op) => Some(op),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn raw_struct(&self) -> &BNMediumLevelILInstruction {
+        use self::ExprInfo::*;
+
+        if let Some(op) = self.as_cmp_op() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_binary_op() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_binary_op_carry() {
+            return &op.op;
+        }
+
+        if let Some(op) = self.as_unary_op() {
+            return &op.op;
+        }
+
+        match *self {
+            Load(ref op) => &op.op,
+            LoadStruct(ref op) => &op.op,
+            Var(ref op) | VarAliased(ref op) => &op.op,
+            VarField(ref op) | VarAliasedField(ref op) => &op.op,
+            VarSplit(ref op) => &op.op,
+            AddressOf(ref op) => &op.op,
+            AddressOfField(ref op) => &op.op,
+
+            Const(ref op) | ConstPtr(ref op) | Import(ref op) => &op.op,
+            ExternPtr(ref op) => &op.op,
+            FloatConst(ref op) => &op.op,
+
+            Unimpl(ref op) | Undef(ref op) => &op.op,
+            UnimplMem(ref op) => &op.op,
+
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'func, A, M> fmt::Debug for ExprInfo<'func, A, M, NonSSA>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::ExprInfo::*;
+
+        if let Some(op) = self.as_cmp_op() {
+            return write!(
+                f,
+                "{:?}({}, {:?}, {:?})",
+                op.op.operation,
+                op.size(),
+                op.left(),
+                op.right()
+            );
+        }
+
+        if let Some(op) = self.as_binary_op() {
+            return write!(
+                f,
+                "{:?}({}, {:?}, {:?})",
+                op.op.operation,
+                op.size(),
+                op.left(),
+                op.right()
+            );
+        }
+
+        if let Some(op) = self.as_binary_op_carry() {
+            return write!(
+                f,
+                "{:?}({}, {:?}, {:?}, carry: {:?})",
+                op.op.operation,
+                op.size(),
+                op.left(),
+                op.right(),
+                op.carry()
+            );
+        }
+
+        if let Some(op) = self.as_unary_op() {
+            return write!(
+                f,
+                "{:?}({}, {:?})",
+                op.op.operation,
+                op.size(),
+                op.operand()
+            );
+        }
+
+        match *self {
+            Undef(..) => f.write_str("undefined"),
+
+            Unimpl(..) => f.write_str("unimplemented"),
+
+            Load(ref op) => write!(f, "[{:?}].{}", op.source_mem_expr(), op.size()),
+
+            LoadStruct(ref op) => write!(
+                f,
+                "[{:?} + 0x{:x}].{}",
+                op.source_mem_expr(),
+                op.offset(),
+                op.size()
+            ),
+
+            Var(ref op) | VarAliased(ref op) => write!(f, "{:?}", op.source_var()),
+
+            VarField(ref op) | VarAliasedField(ref op) => {
+                write!(f, "{:?}:0x{:x}.{}", op.source_var(), op.offset(), op.size())
+            }
+
+            VarSplit(ref op) => write!(f, "{:?}:{:?}", op.high_var(), op.low_var()),
+
+            AddressOf(ref op) => write!(f, "&{:?}", op.source_var()),
+
+            AddressOfField(ref op) => write!(f, "&{:?}:0x{:x}", op.source_var(), op.offset()),
+
+            Const(ref op) | ConstPtr(ref op) | Import(ref op) => write!(f, "0x{:x}", op.value()),
+
+            ExternPtr(ref op) => write!(f, "extern(0x{:x}) + 0x{:x}", op.value(), op.offset()),
+
+            FloatConst(ref op) => write!(f, "{}", op.value()),
+
+            UnimplMem(ref op) => write!(f, "unimplemented_mem({:?})", op.mem_expr()),
+
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,380 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNFreeMediumLevelILFunction;
+use binaryninjacore_sys::BNMediumLevelILFunction;
+use binaryninjacore_sys::BNNewMediumLevelILFunctionReference;
+
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+use std::slice;
+
+use crate::basicblock::BasicBlock;
+use crate::function::Function as CoreFunction;
+use crate::rc::*;
+
+use super::*;
+
+#[derive(Copy, Clone, Debug)]
+pub struct SSA;
+#[derive(Copy, Clone, Debug)]
+pub struct NonSSA;
+
+pub trait FunctionForm: 'static {}
+impl FunctionForm for SSA {}
+impl FunctionForm for NonSSA {}
+
+pub struct Function<A: Architecture, M: FunctionMutability, F: FunctionForm> {
+    pub(crate) borrower: A::Handle,
+    pub(crate) handle: *mut BNMediumLevelILFunction,
+    _arch: PhantomData<*mut A>,
+    _mutability: PhantomData<M>,
+    _form: PhantomData<F>,
+}
+
+unsafe impl<A: Architecture, M: FunctionMutability, F: FunctionForm> Send for Function<A, M, F> {}
+unsafe impl<A: Architecture, M: FunctionMutability, F: FunctionForm> Sync for Function<A, M, F> {}
+
+impl<A: Architecture, M: FunctionMutability, F: FunctionForm> Eq for Function<A, M, F> {}
+impl<A: Architecture, M: FunctionMutability, F: FunctionForm> PartialEq for Function<A, M, F> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.handle == rhs.handle
+    }
+}
+
+use std::hash::{Hash, Hasher};
+impl<A: Architecture, M: FunctionMutability, F: FunctionForm> Hash for Function<A, M, F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handle.hash(state);
+    }
+}
+
+impl<'func, A, M, F> Function<A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub(crate) unsafe fn from_raw(
+        borrower: A::Handle,
+        handle: *mut BNMediumLevelILFunction,
+    ) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self {
+            borrower,
+            handle,
+            _arch: PhantomData,
+            _mutability: PhantomData,
+            _form: PhantomData,
+        }
+    }
+
+    pub(crate) fn arch(&self) -> &A {
+        self.borrower.borrow()
+    }
+
+    pub fn function(&self) -> Ref<CoreFunction> {
+        use binaryninjacore_sys::BNGetMediumLevelILOwnerFunction;
+
+        unsafe { CoreFunction::from_raw(BNGetMediumLevelILOwnerFunction(self.handle)) }
+    }
+
+    pub fn instruction_at<L: Into<Location>>(&self, loc: L) -> Option<Instruction<A, M, F>> {
+        use binaryninjacore_sys::BNGetMediumLevelILInstructionCount;
+        use binaryninjacore_sys::BNMediumLevelILGetInstructionStart;
+
+        let loc: Location = loc.into();
+        let arch_handle = loc.arch.unwrap_or_else(|| *self.arch().as_ref());
+
+        unsafe {
+            let instr_idx =
+                BNMediumLevelILGetInstructionStart(self.handle, arch_handle.0, loc.addr);
+
+            if instr_idx >= BNGetMediumLevelILInstructionCount(self.handle) {
+                None
+            } else {
+                Some(Instruction {
+                    function: self,
+                    instr_idx,
+                })
+            }
+        }
+    }
+
+    pub fn instruction_from_idx(&self, instr_idx: usize) -> Instruction<A, M, F> {
+        unsafe {
+            use binaryninjacore_sys::BNGetMediumLevelILInstructionCount;
+            if instr_idx >= BNGetMediumLevelILInstructionCount(self.handle) {
+                panic!("instruction index {} out of bounds", instr_idx);
+            }
+
+            Instruction {
+                function: self,
+                instr_idx,
+            }
+        }
+    }
+
+    pub fn instruction_count(&self) -> usize {
+        unsafe {
+            use binaryninjacore_sys::BNGetMediumLevelILInstructionCount;
+            BNGetMediumLevelILInstructionCount(self.handle)
+        }
+    }
+
+    /// Returns every variable referenced by this function
+    pub fn variables(&self) -> Vec<Variable> {
+        use binaryninjacore_sys::BNFreeVariableList;
+        use binaryninjacore_sys::BNGetMediumLevelILVariables;
+
+        unsafe {
+            let mut count = 0;
+            let vars = BNGetMediumLevelILVariables(self.handle, &mut count);
+
+            let result = slice::from_raw_parts(vars, count)
+                .iter()
+                .map(|&v| Variable::from_raw(v))
+                .collect();
+
+            BNFreeVariableList(vars);
+
+            result
+        }
+    }
+
+    pub(crate) fn instruction_list(
+        &self,
+        list: *mut usize,
+        count: usize,
+    ) -> Vec<Instruction<A, M, F>> {
+        use binaryninjacore_sys::BNFreeILInstructionList;
+
+        unsafe {
+            let result = slice::from_raw_parts(list, count)
+                .iter()
+                .map(|&instr_idx| Instruction {
+                    function: self,
+                    instr_idx,
+                })
+                .collect();
+
+            BNFreeILInstructionList(list);
+
+            result
+        }
+    }
+}
+
+// MLIL basic blocks are only available once the function
+// has been finalized
+impl<'func, A, F> Function<A, Finalized, F>
+where
+    A: 'func + Architecture,
+    F: FunctionForm,
+{
+    pub fn basic_blocks(&self) -> Array<BasicBlock<MediumLevelBlock<A, Finalized, F>>> {
+        use binaryninjacore_sys::BNGetMediumLevelILBasicBlockList;
+
+        unsafe {
+            let mut count = 0;
+            let blocks = BNGetMediumLevelILBasicBlockList(self.handle, &mut count);
+            let context = MediumLevelBlock { function: self };
+
+            Array::new(blocks, count, context)
+        }
+    }
+}
+
+impl<'func, A> Function<A, Finalized, NonSSA>
+where
+    A: 'func + Architecture,
+{
+    pub fn ssa_form(&self) -> Result<Ref<Function<A, Finalized, SSA>>, ()> {
+        use binaryninjacore_sys::BNGetMediumLevelILSSAForm;
+
+        unsafe {
+            let ssa = BNGetMediumLevelILSSAForm(self.handle);
+
+            if ssa.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(Function::from_raw(self.borrower.clone(), ssa)))
+        }
+    }
+
+    /// Returns the instructions that write to `var`
+    pub fn variable_definitions(&self, var: &Variable) -> Vec<Instruction<A, Finalized, NonSSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILVariableDefinitions;
+
+        let raw_var = var.into_raw();
+
+        unsafe {
+            let mut count = 0;
+            let instrs = BNGetMediumLevelILVariableDefinitions(self.handle, &raw_var, &mut count);
+
+            self.instruction_list(instrs, count)
+        }
+    }
+
+    /// Returns the instructions that read from `var`
+    pub fn variable_uses(&self, var: &Variable) -> Vec<Instruction<A, Finalized, NonSSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILVariableUses;
+
+        let raw_var = var.into_raw();
+
+        unsafe {
+            let mut count = 0;
+            let instrs = BNGetMediumLevelILVariableUses(self.handle, &raw_var, &mut count);
+
+            self.instruction_list(instrs, count)
+        }
+    }
+}
+
+impl<'func, A> Function<A, Finalized, SSA>
+where
+    A: 'func + Architecture,
+{
+    pub fn non_ssa_form(&self) -> Result<Ref<Function<A, Finalized, NonSSA>>, ()> {
+        use binaryninjacore_sys::BNGetMediumLevelILNonSSAForm;
+
+        unsafe {
+            let non_ssa = BNGetMediumLevelILNonSSAForm(self.handle);
+
+            if non_ssa.is_null() {
+                return Err(());
+            }
+
+            Ok(Ref::new(Function::from_raw(self.borrower.clone(), non_ssa)))
+        }
+    }
+
+    /// Returns the instruction that defines the given version of a variable
+    ///
+    /// Variables that are never written within the function (such as
+    /// incoming parameters at version 0) have no definition.
+    pub fn get_ssa_var_definition(
+        &self,
+        var: &SSAVariable,
+    ) -> Option<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILSSAVarDefinition;
+
+        let raw_var = var.variable.into_raw();
+
+        unsafe {
+            let instr_idx = BNGetMediumLevelILSSAVarDefinition(self.handle, &raw_var, var.version);
+
+            if instr_idx >= self.instruction_count() {
+                None
+            } else {
+                Some(Instruction {
+                    function: self,
+                    instr_idx,
+                })
+            }
+        }
+    }
+
+    pub fn get_ssa_var_uses(&self, var: &SSAVariable) -> Vec<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILSSAVarUses;
+
+        let raw_var = var.variable.into_raw();
+
+        unsafe {
+            let mut count = 0;
+            let instrs =
+                BNGetMediumLevelILSSAVarUses(self.handle, &raw_var, var.version, &mut count);
+
+            self.instruction_list(instrs, count)
+        }
+    }
+
+    pub fn get_ssa_memory_definition(
+        &self,
+        version: usize,
+    ) -> Option<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILSSAMemoryDefinition;
+
+        unsafe {
+            let instr_idx = BNGetMediumLevelILSSAMemoryDefinition(self.handle, version);
+
+            if instr_idx >= self.instruction_count() {
+                None
+            } else {
+                Some(Instruction {
+                    function: self,
+                    instr_idx,
+                })
+            }
+        }
+    }
+
+    pub fn get_ssa_memory_uses(&self, version: usize) -> Vec<Instruction<A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetMediumLevelILSSAMemoryUses;
+
+        unsafe {
+            let mut count = 0;
+            let instrs = BNGetMediumLevelILSSAMemoryUses(self.handle, version, &mut count);
+
+            self.instruction_list(instrs, count)
+        }
+    }
+}
+
+impl<'func, A, M, F> ToOwned for Function<A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl<'func, A, M, F> RefCountable for Function<A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            borrower: handle.borrower.clone(),
+            handle: BNNewMediumLevelILFunctionReference(handle.handle),
+            _arch: PhantomData,
+            _mutability: PhantomData,
+            _form: PhantomData,
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeMediumLevelILFunction(handle.handle);
+    }
+}
+
+impl<'func, A, M, F> fmt::Debug for Function<A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<mlil func handle {:p}>", self.handle)
+    }
+}
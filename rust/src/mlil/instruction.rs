@@ -0,0 +1,368 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNGetMediumLevelILByIndex;
+use binaryninjacore_sys::BNGetMediumLevelILIndexForInstruction;
+use binaryninjacore_sys::BNMediumLevelILInstruction;
+
+use super::operation;
+use super::operation::Operation;
+use super::*;
+
+use crate::architecture::Architecture;
+
+pub struct Instruction<'func, A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub(crate) function: &'func Function<A, M, F>,
+    pub(crate) instr_idx: usize,
+}
+
+impl<'func, A, M, F> Instruction<'func, A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn index(&self) -> usize {
+        self.instr_idx
+    }
+
+    pub fn address(&self) -> u64 {
+        unsafe { self.raw_op().1.address }
+    }
+
+    unsafe fn raw_op(&self) -> (usize, BNMediumLevelILInstruction) {
+        let expr_idx = BNGetMediumLevelILIndexForInstruction(self.function.handle, self.instr_idx);
+        let op = BNGetMediumLevelILByIndex(self.function.handle, expr_idx);
+
+        (expr_idx, op)
+    }
+}
+
+fn common_info<'func, A, M, F>(
+    function: &'func Function<A, M, F>,
+    expr_idx: usize,
+    op: BNMediumLevelILInstruction,
+) -> Option<InstrInfo<'func, A, M, F>>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    use binaryninjacore_sys::BNMediumLevelILOperation::*;
+
+    match op.operation {
+        MLIL_NOP => InstrInfo::Nop(Operation::new(function, expr_idx, op)).into(),
+        MLIL_JUMP => InstrInfo::Jump(Operation::new(function, expr_idx, op)).into(),
+        MLIL_JUMP_TO => InstrInfo::JumpTo(Operation::new(function, expr_idx, op)).into(),
+        MLIL_RET_HINT => InstrInfo::RetHint(Operation::new(function, expr_idx, op)).into(),
+        MLIL_RET => InstrInfo::Ret(Operation::new(function, expr_idx, op)).into(),
+        MLIL_NORET => InstrInfo::NoRet(Operation::new(function, expr_idx, op)).into(),
+        MLIL_IF => InstrInfo::If(Operation::new(function, expr_idx, op)).into(),
+        MLIL_GOTO => InstrInfo::Goto(Operation::new(function, expr_idx, op)).into(),
+        MLIL_BP => InstrInfo::Bp(Operation::new(function, expr_idx, op)).into(),
+        MLIL_TRAP => InstrInfo::Trap(Operation::new(function, expr_idx, op)).into(),
+        MLIL_UNDEF => InstrInfo::Undef(Operation::new(function, expr_idx, op)).into(),
+        _ => None,
+    }
+}
+
+use super::VisitorAction;
+
+macro_rules! visit {
+    ($f:expr, $($e:expr),*) => {
+        if let VisitorAction::Halt = $f($($e,)*) {
+            return VisitorAction::Halt;
+        }
+    }
+}
+
+fn common_visit<'func, A, M, F, CB>(info: &InstrInfo<'func, A, M, F>, f: &mut CB) -> VisitorAction
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+    CB: FnMut(&Expression<'func, A, M, F, ValueExpr>) -> VisitorAction,
+{
+    use self::InstrInfo::*;
+
+    match *info {
+        Jump(ref op) | RetHint(ref op) => visit!(f, &op.target()),
+        JumpTo(ref op) => visit!(f, &op.target()),
+        Ret(ref op) => {
+            for e in &op.source_exprs() {
+                visit!(f, e);
+            }
+        }
+        If(ref op) => visit!(f, &op.condition()),
+        Value(ref e, _) => visit!(f, e),
+        _ => {}
+    };
+
+    VisitorAction::Sibling
+}
+
+impl<'func, A, M> Instruction<'func, A, M, NonSSA>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn info(&self) -> InstrInfo<'func, A, M, NonSSA> {
+        use binaryninjacore_sys::BNMediumLevelILOperation::*;
+
+        let (expr_idx, op) = unsafe { self.raw_op() };
+
+        match op.operation {
+            MLIL_SET_VAR => InstrInfo::SetVar(Operation::new(self.function, expr_idx, op)),
+            MLIL_SET_VAR_FIELD => {
+                InstrInfo::SetVarField(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_SET_VAR_SPLIT => {
+                InstrInfo::SetVarSplit(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_STORE => InstrInfo::Store(Operation::new(self.function, expr_idx, op)),
+            MLIL_STORE_STRUCT => {
+                InstrInfo::StoreStruct(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_CALL => InstrInfo::Call(Operation::new(self.function, expr_idx, op)),
+            MLIL_CALL_UNTYPED => {
+                InstrInfo::CallUntyped(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_TAILCALL => InstrInfo::Tailcall(Operation::new(self.function, expr_idx, op)),
+            MLIL_TAILCALL_UNTYPED => {
+                InstrInfo::TailcallUntyped(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_SYSCALL => InstrInfo::Syscall(Operation::new(self.function, expr_idx, op)),
+            MLIL_SYSCALL_UNTYPED => {
+                InstrInfo::SyscallUntyped(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_INTRINSIC => InstrInfo::Intrinsic(Operation::new(self.function, expr_idx, op)),
+            MLIL_FREE_VAR_SLOT => {
+                InstrInfo::FreeVarSlot(Operation::new(self.function, expr_idx, op))
+            }
+            _ => {
+                common_info(self.function, expr_idx, op).unwrap_or_else(|| {
+                    // Anything that isn't a statement should be a bare value,
+                    // anything else will be reported as undefined when queried.
+                    let expr = Expression::new(self.function, expr_idx);
+                    let info = unsafe { expr.info_from_op(op) };
+
+                    InstrInfo::Value(expr, info)
+                })
+            }
+        }
+    }
+
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(
+            &Expression<'func, A, M, NonSSA, ValueExpr>,
+            &ExprInfo<'func, A, M, NonSSA>,
+        ) -> VisitorAction,
+    {
+        use self::InstrInfo::*;
+        let info = self.info();
+
+        let fb = &mut |e: &Expression<'func, A, M, NonSSA, ValueExpr>| e.visit_tree(f);
+
+        match info {
+            SetVar(ref op) => visit!(fb, &op.source_expr()),
+            SetVarField(ref op) => visit!(fb, &op.source_expr()),
+            SetVarSplit(ref op) => visit!(fb, &op.source_expr()),
+            Store(ref op) => {
+                visit!(fb, &op.dest_mem_expr());
+                visit!(fb, &op.source_expr());
+            }
+            StoreStruct(ref op) => {
+                visit!(fb, &op.dest_mem_expr());
+                visit!(fb, &op.source_expr());
+            }
+            Call(ref op) | Tailcall(ref op) => {
+                visit!(fb, &op.target());
+                for e in &op.params() {
+                    visit!(fb, e);
+                }
+            }
+            CallUntyped(ref op) | TailcallUntyped(ref op) => visit!(fb, &op.target()),
+            Syscall(ref op) => {
+                for e in &op.params() {
+                    visit!(fb, e);
+                }
+            }
+            Intrinsic(ref op) => {
+                for e in &op.params() {
+                    visit!(fb, e);
+                }
+            }
+            _ => visit!(common_visit, &info, fb),
+        }
+
+        VisitorAction::Sibling
+    }
+}
+
+impl<'func, A, M> Instruction<'func, A, M, SSA>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn info(&self) -> InstrInfo<'func, A, M, SSA> {
+        use binaryninjacore_sys::BNMediumLevelILOperation::*;
+
+        let (expr_idx, op) = unsafe { self.raw_op() };
+
+        match op.operation {
+            MLIL_SET_VAR_SSA => InstrInfo::SetVar(Operation::new(self.function, expr_idx, op)),
+            MLIL_SET_VAR_SSA_FIELD => {
+                InstrInfo::SetVarField(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_SET_VAR_SPLIT_SSA => {
+                InstrInfo::SetVarSplit(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_SET_VAR_ALIASED => {
+                InstrInfo::SetVarAliased(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_SET_VAR_ALIASED_FIELD => {
+                InstrInfo::SetVarAliasedField(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_STORE_SSA => InstrInfo::Store(Operation::new(self.function, expr_idx, op)),
+            MLIL_STORE_STRUCT_SSA => {
+                InstrInfo::StoreStruct(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_CALL_SSA => InstrInfo::Call(Operation::new(self.function, expr_idx, op)),
+            MLIL_CALL_UNTYPED_SSA => {
+                InstrInfo::CallUntyped(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_TAILCALL_SSA => InstrInfo::Tailcall(Operation::new(self.function, expr_idx, op)),
+            MLIL_TAILCALL_UNTYPED_SSA => {
+                InstrInfo::TailcallUntyped(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_SYSCALL_SSA => InstrInfo::Syscall(Operation::new(self.function, expr_idx, op)),
+            MLIL_SYSCALL_UNTYPED_SSA => {
+                InstrInfo::SyscallUntyped(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_INTRINSIC_SSA => InstrInfo::Intrinsic(Operation::new(self.function, expr_idx, op)),
+            MLIL_FREE_VAR_SLOT_SSA => {
+                InstrInfo::FreeVarSlot(Operation::new(self.function, expr_idx, op))
+            }
+            MLIL_VAR_PHI => InstrInfo::VarPhi(Operation::new(self.function, expr_idx, op)),
+            MLIL_MEM_PHI => InstrInfo::MemPhi(Operation::new(self.function, expr_idx, op)),
+            _ => common_info(self.function, expr_idx, op).unwrap_or_else(|| {
+                let expr = Expression::new(self.function, expr_idx);
+                let info = unsafe { expr.info_from_op(op) };
+
+                InstrInfo::Value(expr, info)
+            }),
+        }
+    }
+
+    pub fn visit_tree<F>(&self, f: &mut F) -> VisitorAction
+    where
+        F: FnMut(
+            &Expression<'func, A, M, SSA, ValueExpr>,
+            &ExprInfo<'func, A, M, SSA>,
+        ) -> VisitorAction,
+    {
+        use self::InstrInfo::*;
+        let info = self.info();
+
+        let fb = &mut |e: &Expression<'func, A, M, SSA, ValueExpr>| e.visit_tree(f);
+
+        match info {
+            SetVar(ref op) => visit!(fb, &op.source_expr()),
+            SetVarField(ref op) | SetVarAliasedField(ref op) => visit!(fb, &op.source_expr()),
+            SetVarSplit(ref op) => visit!(fb, &op.source_expr()),
+            SetVarAliased(ref op) => visit!(fb, &op.source_expr()),
+            Store(ref op) => {
+                visit!(fb, &op.dest_mem_expr());
+                visit!(fb, &op.source_expr());
+            }
+            StoreStruct(ref op) => {
+                visit!(fb, &op.dest_mem_expr());
+                visit!(fb, &op.source_expr());
+            }
+            Call(ref op) | Tailcall(ref op) => {
+                visit!(fb, &op.target());
+                for e in &op.params() {
+                    visit!(fb, e);
+                }
+            }
+            CallUntyped(ref op) | TailcallUntyped(ref op) => visit!(fb, &op.target()),
+            Syscall(ref op) => {
+                for e in &op.params() {
+                    visit!(fb, e);
+                }
+            }
+            Intrinsic(ref op) => {
+                for e in &op.params() {
+                    visit!(fb, e);
+                }
+            }
+            _ => visit!(common_visit, &info, fb),
+        }
+
+        VisitorAction::Sibling
+    }
+}
+
+pub enum InstrInfo<'func, A, M, F>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    Nop(Operation<'func, A, M, F, operation::NoArgs>),
+    SetVar(Operation<'func, A, M, F, operation::SetVar>),
+    SetVarField(Operation<'func, A, M, F, operation::SetVarField>),
+    SetVarSplit(Operation<'func, A, M, F, operation::SetVarSplit>),
+    SetVarAliased(Operation<'func, A, M, F, operation::SetVarAliased>),
+    SetVarAliasedField(Operation<'func, A, M, F, operation::SetVarField>),
+    Store(Operation<'func, A, M, F, operation::Store>),
+    StoreStruct(Operation<'func, A, M, F, operation::StoreStruct>),
+
+    Jump(Operation<'func, A, M, F, operation::Jump>),
+    JumpTo(Operation<'func, A, M, F, operation::JumpTo>),
+    RetHint(Operation<'func, A, M, F, operation::Jump>),
+
+    Call(Operation<'func, A, M, F, operation::Call>),
+    CallUntyped(Operation<'func, A, M, F, operation::CallUntyped>),
+    Tailcall(Operation<'func, A, M, F, operation::Call>),
+    TailcallUntyped(Operation<'func, A, M, F, operation::CallUntyped>),
+    Syscall(Operation<'func, A, M, F, operation::Syscall>),
+    SyscallUntyped(Operation<'func, A, M, F, operation::SyscallUntyped>),
+    Intrinsic(Operation<'func, A, M, F, operation::Intrinsic>),
+    FreeVarSlot(Operation<'func, A, M, F, operation::FreeVarSlot>),
+
+    Ret(Operation<'func, A, M, F, operation::Ret>),
+    NoRet(Operation<'func, A, M, F, operation::NoArgs>),
+
+    If(Operation<'func, A, M, F, operation::If>),
+    Goto(Operation<'func, A, M, F, operation::Goto>),
+
+    Bp(Operation<'func, A, M, F, operation::NoArgs>),
+    Trap(Operation<'func, A, M, F, operation::Trap>),
+    Undef(Operation<'func, A, M, F, operation::NoArgs>),
+
+    VarPhi(Operation<'func, A, M, F, operation::VarPhi>),
+    MemPhi(Operation<'func, A, M, F, operation::MemPhi>),
+
+    Value(
+        Expression<'func, A, M, F, ValueExpr>,
+        ExprInfo<'func, A, M, F>,
+    ),
+}
@@ -0,0 +1,55 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use crate::architecture::Architecture;
+use crate::function::Location;
+use crate::types::Variable;
+
+mod block;
+mod expression;
+mod function;
+mod instruction;
+pub mod operation;
+
+pub use self::expression::*;
+pub use self::function::*;
+pub use self::instruction::*;
+
+pub use self::block::Block as MediumLevelBlock;
+pub use self::block::BlockIter as MediumLevelBlockIter;
+
+pub use crate::llil::{Finalized, FunctionMutability, Mutable, VisitorAction};
+
+pub type RegularFunction<Arch> = Function<Arch, Finalized, NonSSA>;
+pub type SSAFunction<Arch> = Function<Arch, Finalized, SSA>;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SSAVariable {
+    pub variable: Variable,
+    pub version: usize,
+}
+
+impl SSAVariable {
+    pub fn new(variable: Variable, version: usize) -> Self {
+        Self { variable, version }
+    }
+}
+
+impl fmt::Debug for SSAVariable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}#{}", self.variable, self.version)
+    }
+}
@@ -0,0 +1,1253 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use binaryninjacore_sys::BNMediumLevelILInstruction;
+
+use std::marker::PhantomData;
+use std::mem;
+use std::slice;
+
+use super::*;
+
+pub struct Operation<'func, A, M, F, O>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+    O: OperationArguments,
+{
+    pub(crate) function: &'func Function<A, M, F>,
+    pub(crate) expr_idx: usize,
+    pub(crate) op: BNMediumLevelILInstruction,
+    _args: PhantomData<O>,
+}
+
+impl<'func, A, M, F, O> Operation<'func, A, M, F, O>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+    O: OperationArguments,
+{
+    pub(crate) fn new(
+        function: &'func Function<A, M, F>,
+        expr_idx: usize,
+        op: BNMediumLevelILInstruction,
+    ) -> Self {
+        Self {
+            function,
+            expr_idx,
+            op,
+            _args: PhantomData,
+        }
+    }
+
+    pub fn address(&self) -> u64 {
+        self.op.address
+    }
+
+    fn expr_operand(&self, operand: usize) -> Expression<'func, A, M, F, ValueExpr> {
+        Expression::new(self.function, self.op.operands[operand] as usize)
+    }
+
+    fn var_operand(&self, operand: usize) -> Variable {
+        Variable::from_identifier(self.op.operands[operand])
+    }
+
+    fn ssa_var_operand(&self, var_operand: usize, version_operand: usize) -> SSAVariable {
+        SSAVariable::new(
+            self.var_operand(var_operand),
+            self.op.operands[version_operand] as usize,
+        )
+    }
+
+    // operands of the sub-expression (MLIL_CALL_OUTPUT, MLIL_CALL_PARAM, ...)
+    // referenced by the given operand
+    fn sub_expr_op(&self, operand: usize) -> (usize, BNMediumLevelILInstruction) {
+        use binaryninjacore_sys::BNGetMediumLevelILByIndex;
+
+        let expr_idx = self.op.operands[operand] as usize;
+        let op = unsafe { BNGetMediumLevelILByIndex(self.function.handle, expr_idx) };
+
+        (expr_idx, op)
+    }
+
+    fn expr_list(
+        &self,
+        expr_idx: usize,
+        operand: usize,
+    ) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        operand_list(self.function, expr_idx, operand)
+            .into_iter()
+            .map(|idx| Expression::new(self.function, idx as usize))
+            .collect()
+    }
+
+    fn var_list(&self, expr_idx: usize, operand: usize) -> Vec<Variable> {
+        operand_list(self.function, expr_idx, operand)
+            .into_iter()
+            .map(Variable::from_identifier)
+            .collect()
+    }
+
+    fn ssa_var_list(&self, expr_idx: usize, operand: usize) -> Vec<SSAVariable> {
+        operand_list(self.function, expr_idx, operand)
+            .chunks(2)
+            .map(|pair| SSAVariable::new(Variable::from_identifier(pair[0]), pair[1] as usize))
+            .collect()
+    }
+}
+
+fn operand_list<A, M, F>(function: &Function<A, M, F>, expr_idx: usize, operand: usize) -> Vec<u64>
+where
+    A: Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    use binaryninjacore_sys::BNMediumLevelILFreeOperandList;
+    use binaryninjacore_sys::BNMediumLevelILGetOperandList;
+
+    unsafe {
+        let mut count = 0;
+        let list = BNMediumLevelILGetOperandList(function.handle, expr_idx, operand, &mut count);
+
+        let result = slice::from_raw_parts(list, count).to_vec();
+
+        BNMediumLevelILFreeOperandList(list);
+
+        result
+    }
+}
+
+// MLIL_NOP, MLIL_NORET, MLIL_BP, MLIL_UNDEF, MLIL_UNIMPL
+pub struct NoArgs;
+
+// MLIL_SET_VAR, MLIL_SET_VAR_SSA
+pub struct SetVar;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, SetVar>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_var(&self) -> Variable {
+        self.var_operand(0)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, SetVar>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+// MLIL_SET_VAR_FIELD, MLIL_SET_VAR_SSA_FIELD, MLIL_SET_VAR_ALIASED_FIELD
+pub struct SetVarField;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, SetVarField>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_var(&self) -> Variable {
+        self.var_operand(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, SetVarField>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+
+    /// The version of the variable whose remaining bytes are carried over
+    pub fn prev_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 2)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[3]
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(4)
+    }
+}
+
+// MLIL_SET_VAR_SPLIT, MLIL_SET_VAR_SPLIT_SSA
+pub struct SetVarSplit;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, SetVarSplit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_var_high(&self) -> Variable {
+        self.var_operand(0)
+    }
+
+    pub fn dest_var_low(&self) -> Variable {
+        self.var_operand(1)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, SetVarSplit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_var_high(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+
+    pub fn dest_var_low(&self) -> SSAVariable {
+        self.ssa_var_operand(2, 3)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(4)
+    }
+}
+
+// MLIL_SET_VAR_ALIASED
+pub struct SetVarAliased;
+
+impl<'func, A, M> Operation<'func, A, M, SSA, SetVarAliased>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+
+    pub fn prev_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 2)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(3)
+    }
+}
+
+// MLIL_LOAD, MLIL_LOAD_SSA
+pub struct Load;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Load>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_mem_expr(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Load>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+}
+
+// MLIL_LOAD_STRUCT, MLIL_LOAD_STRUCT_SSA
+pub struct LoadStruct;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, LoadStruct>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_mem_expr(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, LoadStruct>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+}
+
+// MLIL_STORE, MLIL_STORE_SSA
+pub struct Store;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, Store>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_mem_expr(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Store>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_mem_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(3)
+    }
+}
+
+// MLIL_STORE_STRUCT, MLIL_STORE_STRUCT_SSA
+pub struct StoreStruct;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, StoreStruct>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_mem_expr(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, StoreStruct>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_mem_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[3] as usize
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(4)
+    }
+}
+
+// MLIL_VAR, MLIL_VAR_SSA, MLIL_VAR_ALIASED
+pub struct Var;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, Var>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_var(&self) -> Variable {
+        self.var_operand(0)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Var>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+}
+
+// MLIL_VAR_FIELD, MLIL_VAR_SSA_FIELD, MLIL_VAR_ALIASED_FIELD
+pub struct VarField;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, VarField>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_var(&self) -> Variable {
+        self.var_operand(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, VarField>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[2]
+    }
+}
+
+// MLIL_VAR_SPLIT, MLIL_VAR_SPLIT_SSA
+pub struct VarSplit;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, VarSplit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn high_var(&self) -> Variable {
+        self.var_operand(0)
+    }
+
+    pub fn low_var(&self) -> Variable {
+        self.var_operand(1)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, VarSplit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn high_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+
+    pub fn low_var(&self) -> SSAVariable {
+        self.ssa_var_operand(2, 3)
+    }
+}
+
+// MLIL_ADDRESS_OF
+pub struct AddressOf;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, AddressOf>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_var(&self) -> Variable {
+        self.var_operand(0)
+    }
+}
+
+// MLIL_ADDRESS_OF_FIELD
+pub struct AddressOfField;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, AddressOfField>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_var(&self) -> Variable {
+        self.var_operand(0)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+// MLIL_JUMP, MLIL_RET_HINT
+pub struct Jump;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Jump>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn target(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+}
+
+// MLIL_JUMP_TO
+pub struct JumpTo;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, JumpTo>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn target(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    /// Returns the known destinations of this jump, keyed by target address
+    pub fn target_list(&self) -> Vec<(u64, Instruction<'func, A, M, F>)> {
+        operand_list(self.function, self.expr_idx, 1)
+            .chunks(2)
+            .map(|pair| {
+                let instr = Instruction {
+                    function: self.function,
+                    instr_idx: pair[1] as usize,
+                };
+
+                (pair[0], instr)
+            })
+            .collect()
+    }
+}
+
+// MLIL_CALL, MLIL_CALL_SSA, MLIL_TAILCALL, MLIL_TAILCALL_SSA
+pub struct Call;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, Call>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn output(&self) -> Vec<Variable> {
+        self.var_list(self.expr_idx, 0)
+    }
+
+    pub fn target(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, NonSSA, ValueExpr>> {
+        self.expr_list(self.expr_idx, 3)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Call>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn output(&self) -> Vec<SSAVariable> {
+        let (output_idx, _) = self.sub_expr_op(0);
+        self.ssa_var_list(output_idx, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        let (_, output) = self.sub_expr_op(0);
+        output.operands[0] as usize
+    }
+
+    pub fn target(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, SSA, ValueExpr>> {
+        self.expr_list(self.expr_idx, 2)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[4] as usize
+    }
+}
+
+// MLIL_CALL_UNTYPED, MLIL_CALL_UNTYPED_SSA, MLIL_TAILCALL_UNTYPED,
+// MLIL_TAILCALL_UNTYPED_SSA
+pub struct CallUntyped;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, CallUntyped>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn output(&self) -> Vec<Variable> {
+        let (output_idx, _) = self.sub_expr_op(0);
+        self.var_list(output_idx, 0)
+    }
+
+    pub fn target(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+
+    pub fn params(&self) -> Vec<Variable> {
+        let (params_idx, _) = self.sub_expr_op(2);
+        self.var_list(params_idx, 0)
+    }
+
+    pub fn stack(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(3)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, CallUntyped>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn output(&self) -> Vec<SSAVariable> {
+        let (output_idx, _) = self.sub_expr_op(0);
+        self.ssa_var_list(output_idx, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        let (_, output) = self.sub_expr_op(0);
+        output.operands[0] as usize
+    }
+
+    pub fn target(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(1)
+    }
+
+    pub fn params(&self) -> Vec<SSAVariable> {
+        let (params_idx, _) = self.sub_expr_op(2);
+        self.ssa_var_list(params_idx, 1)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        let (_, params) = self.sub_expr_op(2);
+        params.operands[0] as usize
+    }
+
+    pub fn stack(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(3)
+    }
+}
+
+// MLIL_SYSCALL, MLIL_SYSCALL_SSA
+pub struct Syscall;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, Syscall>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn output(&self) -> Vec<Variable> {
+        self.var_list(self.expr_idx, 0)
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, NonSSA, ValueExpr>> {
+        self.expr_list(self.expr_idx, 2)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Syscall>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn output(&self) -> Vec<SSAVariable> {
+        let (output_idx, _) = self.sub_expr_op(0);
+        self.ssa_var_list(output_idx, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        let (_, output) = self.sub_expr_op(0);
+        output.operands[0] as usize
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, SSA, ValueExpr>> {
+        self.expr_list(self.expr_idx, 1)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[3] as usize
+    }
+}
+
+// MLIL_SYSCALL_UNTYPED, MLIL_SYSCALL_UNTYPED_SSA
+pub struct SyscallUntyped;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, SyscallUntyped>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn output(&self) -> Vec<Variable> {
+        let (output_idx, _) = self.sub_expr_op(0);
+        self.var_list(output_idx, 0)
+    }
+
+    pub fn params(&self) -> Vec<Variable> {
+        let (params_idx, _) = self.sub_expr_op(1);
+        self.var_list(params_idx, 0)
+    }
+
+    pub fn stack(&self) -> Expression<'func, A, M, NonSSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, SyscallUntyped>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn output(&self) -> Vec<SSAVariable> {
+        let (output_idx, _) = self.sub_expr_op(0);
+        self.ssa_var_list(output_idx, 1)
+    }
+
+    pub fn dest_memory_version(&self) -> usize {
+        let (_, output) = self.sub_expr_op(0);
+        output.operands[0] as usize
+    }
+
+    pub fn params(&self) -> Vec<SSAVariable> {
+        let (params_idx, _) = self.sub_expr_op(1);
+        self.ssa_var_list(params_idx, 1)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        let (_, params) = self.sub_expr_op(1);
+        params.operands[0] as usize
+    }
+
+    pub fn stack(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+// MLIL_INTRINSIC, MLIL_INTRINSIC_SSA
+pub struct Intrinsic;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Intrinsic>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn intrinsic_id(&self) -> u32 {
+        self.op.operands[2] as u32
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.expr_list(self.expr_idx, 3)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, Intrinsic>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn output(&self) -> Vec<Variable> {
+        self.var_list(self.expr_idx, 0)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, Intrinsic>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn output(&self) -> Vec<SSAVariable> {
+        self.ssa_var_list(self.expr_idx, 0)
+    }
+}
+
+// MLIL_FREE_VAR_SLOT, MLIL_FREE_VAR_SLOT_SSA
+pub struct FreeVarSlot;
+
+impl<'func, A, M> Operation<'func, A, M, NonSSA, FreeVarSlot>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_var(&self) -> Variable {
+        self.var_operand(0)
+    }
+}
+
+impl<'func, A, M> Operation<'func, A, M, SSA, FreeVarSlot>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+
+    pub fn prev_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 2)
+    }
+}
+
+// MLIL_RET
+pub struct Ret;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Ret>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn source_exprs(&self) -> Vec<Expression<'func, A, M, F, ValueExpr>> {
+        self.expr_list(self.expr_idx, 0)
+    }
+}
+
+// MLIL_IF
+pub struct If;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, If>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn condition(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn true_target(&self) -> Instruction<'func, A, M, F> {
+        Instruction {
+            function: self.function,
+            instr_idx: self.op.operands[1] as usize,
+        }
+    }
+
+    pub fn false_target(&self) -> Instruction<'func, A, M, F> {
+        Instruction {
+            function: self.function,
+            instr_idx: self.op.operands[2] as usize,
+        }
+    }
+}
+
+// MLIL_GOTO
+pub struct Goto;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Goto>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn target(&self) -> Instruction<'func, A, M, F> {
+        Instruction {
+            function: self.function,
+            instr_idx: self.op.operands[0] as usize,
+        }
+    }
+}
+
+// MLIL_TRAP
+pub struct Trap;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Trap>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn vector(&self) -> u64 {
+        self.op.operands[0]
+    }
+}
+
+// MLIL_VAR_PHI
+pub struct VarPhi;
+
+impl<'func, A, M> Operation<'func, A, M, SSA, VarPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_var(&self) -> SSAVariable {
+        self.ssa_var_operand(0, 1)
+    }
+
+    pub fn sources(&self) -> Vec<SSAVariable> {
+        self.ssa_var_list(self.expr_idx, 2)
+    }
+}
+
+// MLIL_MEM_PHI
+pub struct MemPhi;
+
+impl<'func, A, M> Operation<'func, A, M, SSA, MemPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[0] as usize
+    }
+
+    pub fn source_memory_versions(&self) -> Vec<usize> {
+        operand_list(self.function, self.expr_idx, 1)
+            .into_iter()
+            .map(|v| v as usize)
+            .collect()
+    }
+}
+
+// MLIL_CONST, MLIL_CONST_PTR, MLIL_IMPORT
+pub struct Const;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Const>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn value(&self) -> u64 {
+        let mut mask = -1i64 as u64;
+
+        if self.op.size > 0 && self.op.size < mem::size_of::<u64>() {
+            mask <<= self.op.size * 8;
+            mask = !mask;
+        }
+
+        self.op.operands[0] & mask
+    }
+}
+
+// MLIL_EXTERN_PTR
+pub struct ExternPtr;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, ExternPtr>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn value(&self) -> u64 {
+        self.op.operands[0]
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.op.operands[1]
+    }
+}
+
+// MLIL_FLOAT_CONST
+pub struct FloatConst;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, FloatConst>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn value(&self) -> f64 {
+        match self.op.size {
+            4 => f32::from_bits(self.op.operands[0] as u32) as f64,
+            _ => f64::from_bits(self.op.operands[0]),
+        }
+    }
+}
+
+// MLIL_ADD, MLIL_SUB, MLIL_AND, MLIL_OR
+// MLIL_XOR, MLIL_LSL, MLIL_LSR, MLIL_ASR
+// MLIL_ROL, MLIL_ROR, MLIL_MUL, MLIL_MULU_DP,
+// MLIL_MULS_DP, MLIL_DIVU, MLIL_DIVU_DP, MLIL_DIVS,
+// MLIL_DIVS_DP, MLIL_MODU, MLIL_MODU_DP, MLIL_MODS,
+// MLIL_MODS_DP, MLIL_ADD_OVERFLOW, MLIL_FADD, MLIL_FSUB,
+// MLIL_FMUL, MLIL_FDIV
+pub struct BinaryOp;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, BinaryOp>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn right(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+// MLIL_ADC, MLIL_SBB, MLIL_RLC, MLIL_RRC
+pub struct BinaryOpCarry;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, BinaryOpCarry>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn right(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(1)
+    }
+
+    pub fn carry(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(2)
+    }
+}
+
+// MLIL_NEG, MLIL_NOT, MLIL_SX, MLIL_ZX, MLIL_LOW_PART,
+// MLIL_BOOL_TO_INT, MLIL_FSQRT, MLIL_FNEG, MLIL_FABS,
+// MLIL_FLOAT_TO_INT, MLIL_INT_TO_FLOAT, MLIL_FLOAT_CONV,
+// MLIL_ROUND_TO_INT, MLIL_FLOOR, MLIL_CEIL, MLIL_FTRUNC
+pub struct UnaryOp;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, UnaryOp>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn operand(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+}
+
+// MLIL_CMP_X, MLIL_FCMP_X, MLIL_TEST_BIT
+pub struct Condition;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, Condition>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn left(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+
+    pub fn right(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(1)
+    }
+}
+
+// MLIL_UNIMPL_MEM
+pub struct UnimplMem;
+
+impl<'func, A, M, F> Operation<'func, A, M, F, UnimplMem>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    F: FunctionForm,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn mem_expr(&self) -> Expression<'func, A, M, F, ValueExpr> {
+        self.expr_operand(0)
+    }
+}
+
+pub trait OperationArguments: 'static {}
+
+impl OperationArguments for NoArgs {}
+impl OperationArguments for SetVar {}
+impl OperationArguments for SetVarField {}
+impl OperationArguments for SetVarSplit {}
+impl OperationArguments for SetVarAliased {}
+impl OperationArguments for Load {}
+impl OperationArguments for LoadStruct {}
+impl OperationArguments for Store {}
+impl OperationArguments for StoreStruct {}
+impl OperationArguments for Var {}
+impl OperationArguments for VarField {}
+impl OperationArguments for VarSplit {}
+impl OperationArguments for AddressOf {}
+impl OperationArguments for AddressOfField {}
+impl OperationArguments for Jump {}
+impl OperationArguments for JumpTo {}
+impl OperationArguments for Call {}
+impl OperationArguments for CallUntyped {}
+impl OperationArguments for Syscall {}
+impl OperationArguments for SyscallUntyped {}
+impl OperationArguments for Intrinsic {}
+impl OperationArguments for FreeVarSlot {}
+impl OperationArguments for Ret {}
+impl OperationArguments for If {}
+impl OperationArguments for Goto {}
+impl OperationArguments for Trap {}
+impl OperationArguments for VarPhi {}
+impl OperationArguments for MemPhi {}
+impl OperationArguments for Const {}
+impl OperationArguments for ExternPtr {}
+impl OperationArguments for FloatConst {}
+impl OperationArguments for BinaryOp {}
+impl OperationArguments for BinaryOpCarry {}
+impl OperationArguments for UnaryOp {}
+impl OperationArguments for Condition {}
+impl OperationArguments for UnimplMem {}
@@ -12,7 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{borrow::Borrow, collections::HashMap, os::raw, path::Path, ptr, slice};
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    fs,
+    os::raw,
+    path::{Path, PathBuf},
+    ptr, slice,
+};
 
 use binaryninjacore_sys::*;
 
@@ -21,6 +28,7 @@ use crate::{
     callingconvention::CallingConvention,
     rc::*,
     string::*,
+    typelibrary::TypeLibrary,
     types::{QualifiedName, QualifiedNameAndType, Type},
 };
 
@@ -204,6 +212,31 @@ impl Platform {
         BNSetPlatformSystemCallConvention
     );
 
+    pub fn type_libraries(&self) -> Array<TypeLibrary> {
+        unsafe {
+            let mut count = 0;
+            let libs = BNGetPlatformTypeLibraries(self.handle, &mut count);
+
+            Array::new(libs, count, ())
+        }
+    }
+
+    /// Returns the type libraries for this platform with the dependency name `name`.
+    pub fn type_libraries_by_name<S: BnStrCompatible>(&self, name: S) -> Array<TypeLibrary> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            let mut count = 0;
+            let libs = BNGetPlatformTypeLibrariesByName(
+                self.handle,
+                name.as_ref().as_ptr() as *mut _,
+                &mut count,
+            );
+
+            Array::new(libs, count, ())
+        }
+    }
+
     pub fn types(&self) -> Array<QualifiedNameAndType> {
         unsafe {
             let mut count = 0;
@@ -242,6 +275,16 @@ pub trait TypeParser {
     ) -> Result<TypeParserResult, String> {
         Err(String::new())
     }
+
+    /// Parses the header at `filename`, following `#include`s through
+    /// `options.include_directories`.
+    fn parse_types_from_source_file<P: AsRef<Path>>(
+        &self,
+        _filename: P,
+        _options: &TypeParserOptions,
+    ) -> Result<TypeParserResult, Vec<TypeParserDiagnostic>> {
+        Err(Vec::new())
+    }
 }
 
 #[derive(Clone, Default)]
@@ -251,6 +294,137 @@ pub struct TypeParserResult {
     pub functions: HashMap<String, Ref<Type>>,
 }
 
+impl TypeParserResult {
+    unsafe fn from_raw(result: &BNTypeParserResult) -> Self {
+        let mut type_parser_result = TypeParserResult::default();
+
+        for i in slice::from_raw_parts(result.types, result.typeCount) {
+            let name = QualifiedName(i.name);
+            type_parser_result
+                .types
+                .insert(name.string(), Type::ref_from_raw(i.type_));
+        }
+
+        for i in slice::from_raw_parts(result.functions, result.functionCount) {
+            let name = QualifiedName(i.name);
+            type_parser_result
+                .functions
+                .insert(name.string(), Type::ref_from_raw(i.type_));
+        }
+
+        for i in slice::from_raw_parts(result.variables, result.variableCount) {
+            let name = QualifiedName(i.name);
+            type_parser_result
+                .variables
+                .insert(name.string(), Type::ref_from_raw(i.type_));
+        }
+
+        type_parser_result
+    }
+}
+
+/// Options for `TypeParser::parse_types_from_source_file`.
+#[derive(Clone, Debug, Default)]
+pub struct TypeParserOptions {
+    pub include_directories: Vec<PathBuf>,
+    /// Macros to define before parsing, as `(name, value)`. A `None` value defines the macro
+    /// with no replacement, like `-DNAME`.
+    pub macros: Vec<(String, Option<String>)>,
+    /// Extra arguments in clang syntax. Only `-I`, `-D` and `-U` are understood, since the core
+    /// doesn't take arbitrary arguments; anything else is reported as an error.
+    pub clang_args: Vec<String>,
+    pub auto_type_source: String,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TypeParserSeverity {
+    Note,
+    Warning,
+    Error,
+    Fatal,
+}
+
+/// A single message from the type parser, e.g. `foo.h:12:3: error: unknown type name 'bar'`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeParserDiagnostic {
+    pub severity: TypeParserSeverity,
+    pub file: Option<String>,
+    pub line: Option<u64>,
+    pub column: Option<u64>,
+    pub message: String,
+}
+
+impl TypeParserDiagnostic {
+    fn error<S: Into<String>>(message: S) -> Self {
+        Self {
+            severity: TypeParserSeverity::Error,
+            file: None,
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// Splits the parser's error output into diagnostics. Lines that don't look like clang
+    /// diagnostics are kept as file-less errors so nothing gets lost.
+    fn parse_errors(errors: &str) -> Vec<Self> {
+        const SEVERITIES: [(&str, TypeParserSeverity); 4] = [
+            (": fatal error: ", TypeParserSeverity::Fatal),
+            (": error: ", TypeParserSeverity::Error),
+            (": warning: ", TypeParserSeverity::Warning),
+            (": note: ", TypeParserSeverity::Note),
+        ];
+
+        errors
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                for &(marker, severity) in SEVERITIES.iter() {
+                    if let Some(pos) = line.find(marker) {
+                        let message = line[pos + marker.len()..].to_string();
+                        let (file, line, column) = Self::parse_location(&line[..pos]);
+
+                        return Self {
+                            severity,
+                            file,
+                            line,
+                            column,
+                            message,
+                        };
+                    }
+                }
+
+                Self::error(line)
+            })
+            .collect()
+    }
+
+    /// Parses `file:line:column` or `file:line`, allowing `:` in the file name.
+    fn parse_location(location: &str) -> (Option<String>, Option<u64>, Option<u64>) {
+        let mut parts = location.rsplitn(3, ':');
+        let last = parts.next().and_then(|s| s.parse().ok());
+        let middle = parts.next();
+        let rest = parts.next();
+
+        match (last, middle, rest) {
+            (Some(column), Some(line), Some(file)) if line.parse::<u64>().is_ok() => {
+                (Some(file.to_string()), line.parse().ok(), Some(column))
+            }
+            (Some(line), Some(file), rest) => {
+                let file = match rest {
+                    Some(rest) => format!("{}:{}", rest, file),
+                    None => file.to_string(),
+                };
+
+                (Some(file), Some(line), None)
+            }
+            _ if !location.is_empty() => (Some(location.to_string()), None, None),
+            _ => (None, None, None),
+        }
+    }
+}
+
 impl TypeParser for Platform {
     fn parse_types_from_source<S: BnStrCompatible, P: AsRef<Path>>(
         &self,
@@ -268,20 +442,20 @@ impl TypeParser for Platform {
             variables: ptr::null_mut(),
         };
 
-        let mut type_parser_result = TypeParserResult::default();
-
         let mut error_string: *mut raw::c_char = ptr::null_mut();
 
         let src = source.as_bytes_with_nul();
         let filename = filename.as_bytes_with_nul();
         let auto_type_source = auto_type_source.as_bytes_with_nul();
 
-        let mut include_dirs = vec![];
-
-        for dir in include_directories.iter() {
-            let d = dir.as_ref().to_string_lossy().as_bytes_with_nul();
-            include_dirs.push(d.as_ptr() as _);
-        }
+        let include_dir_strings: Vec<_> = include_directories
+            .iter()
+            .map(|dir| dir.as_ref().to_string_lossy().as_bytes_with_nul())
+            .collect();
+        let mut include_dirs: Vec<*const raw::c_char> = include_dir_strings
+            .iter()
+            .map(|dir| dir.as_ptr() as _)
+            .collect();
 
         unsafe {
             let success = BNParseTypesFromSource(
@@ -301,29 +475,158 @@ impl TypeParser for Platform {
                 return Err(error_msg.to_string());
             }
 
-            for i in slice::from_raw_parts(result.types, result.typeCount) {
-                let name = QualifiedName(i.name);
-                type_parser_result
-                    .types
-                    .insert(name.string(), Type::ref_from_raw(i.type_));
-            }
+            Ok(TypeParserResult::from_raw(&result))
+        }
+    }
 
-            for i in slice::from_raw_parts(result.functions, result.functionCount) {
-                let name = QualifiedName(i.name);
-                type_parser_result
-                    .functions
-                    .insert(name.string(), Type::ref_from_raw(i.type_));
+    fn parse_types_from_source_file<P: AsRef<Path>>(
+        &self,
+        filename: P,
+        options: &TypeParserOptions,
+    ) -> Result<TypeParserResult, Vec<TypeParserDiagnostic>> {
+        let filename = filename.as_ref();
+
+        let mut include_directories = options.include_directories.clone();
+        let mut macros: Vec<(String, Option<Option<String>>)> = options
+            .macros
+            .iter()
+            .map(|(name, value)| (name.clone(), Some(value.clone())))
+            .collect();
+
+        // `Some(None)` defines a macro without a value, `None` undefines it
+        let mut args = options.clang_args.iter();
+        while let Some(arg) = args.next() {
+            let (flag, value) = match arg.get(..2) {
+                Some(flag @ "-I") | Some(flag @ "-D") | Some(flag @ "-U") => {
+                    let value = if arg.len() > 2 {
+                        Some(arg[2..].to_string())
+                    } else {
+                        args.next().cloned()
+                    };
+
+                    match value {
+                        Some(value) => (flag, value),
+                        None => {
+                            return Err(vec![TypeParserDiagnostic::error(format!(
+                                "missing value for argument '{}'",
+                                arg
+                            ))])
+                        }
+                    }
+                }
+                _ => {
+                    return Err(vec![TypeParserDiagnostic::error(format!(
+                        "unsupported argument '{}'",
+                        arg
+                    ))])
+                }
+            };
+
+            match flag {
+                "-I" => include_directories.push(PathBuf::from(value)),
+                "-D" => match value.find('=') {
+                    Some(pos) => macros.push((
+                        value[..pos].to_string(),
+                        Some(Some(value[pos + 1..].to_string())),
+                    )),
+                    None => macros.push((value, Some(None))),
+                },
+                _ => macros.push((value, None)),
             }
+        }
+
+        let filename_str = filename.to_string_lossy().into_owned();
+
+        if macros.is_empty() {
+            return self.parse_types_from_file_raw(
+                &filename_str,
+                &include_directories,
+                &options.auto_type_source,
+            );
+        }
 
-            for i in slice::from_raw_parts(result.variables, result.variableCount) {
-                let name = QualifiedName(i.name);
-                type_parser_result
-                    .variables
-                    .insert(name.string(), Type::ref_from_raw(i.type_));
+        // the core has no way to pass macros along, so define them at the top of the source and
+        // reset the line numbers so diagnostics still point into the original file
+        let contents = fs::read_to_string(filename).map_err(|e| {
+            vec![TypeParserDiagnostic {
+                file: Some(filename_str.clone()),
+                ..TypeParserDiagnostic::error(e.to_string())
+            }]
+        })?;
+
+        let mut source = String::new();
+        for (name, value) in &macros {
+            match value {
+                Some(Some(value)) => source.push_str(&format!("#define {} {}\n", name, value)),
+                Some(None) => source.push_str(&format!("#define {}\n", name)),
+                None => source.push_str(&format!("#undef {}\n", name)),
             }
         }
+        source.push_str(&format!(
+            "#line 1 \"{}\"\n",
+            filename_str.replace('\\', "\\\\")
+        ));
+        source.push_str(&contents);
+
+        self.parse_types_from_source(
+            source,
+            filename_str,
+            &include_directories,
+            options.auto_type_source.clone(),
+        )
+        .map_err(|errors| TypeParserDiagnostic::parse_errors(&errors))
+    }
+}
+
+impl Platform {
+    fn parse_types_from_file_raw(
+        &self,
+        filename: &str,
+        include_directories: &[PathBuf],
+        auto_type_source: &str,
+    ) -> Result<TypeParserResult, Vec<TypeParserDiagnostic>> {
+        let mut result = BNTypeParserResult {
+            functionCount: 0,
+            typeCount: 0,
+            variableCount: 0,
+            functions: ptr::null_mut(),
+            types: ptr::null_mut(),
+            variables: ptr::null_mut(),
+        };
 
-        Ok(type_parser_result)
+        let mut error_string: *mut raw::c_char = ptr::null_mut();
+
+        let filename = filename.as_bytes_with_nul();
+        let auto_type_source = auto_type_source.as_bytes_with_nul();
+
+        let include_dir_strings: Vec<_> = include_directories
+            .iter()
+            .map(|dir| dir.to_string_lossy().as_bytes_with_nul())
+            .collect();
+        let mut include_dirs: Vec<*const raw::c_char> = include_dir_strings
+            .iter()
+            .map(|dir| dir.as_ptr() as _)
+            .collect();
+
+        unsafe {
+            let success = BNParseTypesFromSourceFile(
+                self.handle,
+                filename.as_ptr() as _,
+                &mut result,
+                &mut error_string,
+                include_dirs.as_mut_ptr(),
+                include_dirs.len(),
+                auto_type_source.as_ptr() as _,
+            );
+
+            let errors = BnString::from_raw(error_string);
+
+            if !success {
+                return Err(TypeParserDiagnostic::parse_errors(errors.as_str()));
+            }
+
+            Ok(TypeParserResult::from_raw(&result))
+        }
     }
 }
 
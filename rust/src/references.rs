@@ -0,0 +1,197 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cross references between code, data and types, as returned by `BinaryViewExt::code_refs_to`
+//! and friends.
+//!
+//! ```ignore
+//! use binaryninja::binaryview::BinaryViewExt;
+//!
+//! for source in bv.code_refs_to(global).iter() {
+//!     if let Some(func) = &source.function {
+//!         println!("{:x} in {:x}", source.address, func.start());
+//!     }
+//! }
+//!
+//! // every instruction that accesses `task_t.state`
+//! for field_ref in bv.code_refs_for_type_field("task_t", 0x10).iter() {
+//!     println!("{:x}: {} byte access", field_ref.address, field_ref.size);
+//! }
+//! ```
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNTypeReferenceType as TypeReferenceType;
+
+use std::mem;
+use std::slice;
+
+use crate::architecture::CoreArchitecture;
+use crate::function::Function;
+use crate::types::{Conf, QualifiedName, Type};
+
+use crate::rc::*;
+
+/// An instruction that refers to an address.
+pub struct ReferenceSource {
+    /// The function containing the instruction, if the core reported one.
+    pub function: Option<Ref<Function>>,
+    pub arch: Option<CoreArchitecture>,
+    pub address: u64,
+}
+
+impl ReferenceSource {
+    pub(crate) unsafe fn from_raw(raw: &BNReferenceSource) -> Self {
+        Self {
+            function: function_from_raw(raw.func),
+            arch: arch_from_raw(raw.arch),
+            address: raw.addr,
+        }
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for ReferenceSource {
+    type Raw = BNReferenceSource;
+    type Context = ();
+
+    unsafe fn free(raw: *mut BNReferenceSource, count: usize, _context: &()) {
+        BNFreeCodeReferences(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for ReferenceSource {
+    type Wrapped = ReferenceSource;
+
+    unsafe fn wrap_raw(raw: &'a BNReferenceSource, _context: &'a ()) -> ReferenceSource {
+        ReferenceSource::from_raw(raw)
+    }
+}
+
+/// An instruction that accesses a member of a structure.
+pub struct TypeFieldReference {
+    /// The function containing the instruction, if the core reported one.
+    pub function: Option<Ref<Function>>,
+    pub arch: Option<CoreArchitecture>,
+    pub address: u64,
+    /// The size of the access, in bytes.
+    pub size: usize,
+    /// The type of the value being accessed, as seen by the instruction, if known.
+    pub incoming_type: Option<Conf<Ref<Type>>>,
+}
+
+unsafe impl CoreOwnedArrayProvider for TypeFieldReference {
+    type Raw = BNTypeFieldReference;
+    type Context = ();
+
+    unsafe fn free(raw: *mut BNTypeFieldReference, count: usize, _context: &()) {
+        BNFreeTypeFieldReferences(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for TypeFieldReference {
+    type Wrapped = TypeFieldReference;
+
+    unsafe fn wrap_raw(raw: &'a BNTypeFieldReference, _context: &'a ()) -> TypeFieldReference {
+        let incoming_type = if raw.incomingType.type_.is_null() {
+            None
+        } else {
+            Some(Conf::new(
+                Type {
+                    handle: raw.incomingType.type_,
+                }
+                .to_owned(),
+                raw.incomingType.confidence,
+            ))
+        };
+
+        TypeFieldReference {
+            function: function_from_raw(raw.func),
+            arch: arch_from_raw(raw.arch),
+            address: raw.addr,
+            size: raw.size,
+            incoming_type,
+        }
+    }
+}
+
+/// A type that refers to another type, through one of its members.
+#[repr(transparent)]
+pub struct TypeReferenceSource(BNTypeReferenceSource);
+
+impl TypeReferenceSource {
+    /// The name of the referring type. Pass a clone of it to `BinaryViewExt::type_refs_for_type`
+    /// to walk type references transitively.
+    pub fn name(&self) -> &QualifiedName {
+        unsafe { mem::transmute(&self.0.name) }
+    }
+
+    /// The offset of the referring member within the referring type.
+    pub fn offset(&self) -> u64 {
+        self.0.offset
+    }
+
+    /// Whether the member is the referenced type itself, or a pointer to it.
+    pub fn reference_type(&self) -> TypeReferenceType {
+        self.0.type_
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for TypeReferenceSource {
+    type Raw = BNTypeReferenceSource;
+    type Context = ();
+
+    unsafe fn free(raw: *mut BNTypeReferenceSource, count: usize, _context: &()) {
+        BNFreeTypeReferences(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for TypeReferenceSource {
+    type Wrapped = &'a TypeReferenceSource;
+
+    unsafe fn wrap_raw(raw: &'a BNTypeReferenceSource, _context: &'a ()) -> Self::Wrapped {
+        mem::transmute(raw)
+    }
+}
+
+unsafe fn function_from_raw(handle: *mut BNFunction) -> Option<Ref<Function>> {
+    if handle.is_null() {
+        None
+    } else {
+        Some(Function { handle }.to_owned())
+    }
+}
+
+unsafe fn arch_from_raw(handle: *mut BNArchitecture) -> Option<CoreArchitecture> {
+    if handle.is_null() {
+        None
+    } else {
+        Some(CoreArchitecture::from_raw(handle))
+    }
+}
+
+/// Copies a list of addresses returned by the core and frees it with `free`.
+pub(crate) unsafe fn addresses_from_raw(
+    raw: *mut u64,
+    count: usize,
+    free: unsafe extern "C" fn(*mut u64),
+) -> Vec<u64> {
+    if raw.is_null() {
+        return Vec::new();
+    }
+
+    let addresses = slice::from_raw_parts(raw, count).to_vec();
+    free(raw);
+
+    addresses
+}
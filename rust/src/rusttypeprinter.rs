@@ -0,0 +1,827 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Printing types as Rust source.
+//!
+//! Structures and unions become `#[repr(C)]` items with explicit padding so that their layout
+//! matches the analysis exactly, along with `#[test]` functions asserting their size and member
+//! offsets. Named types that are referenced but not defined are emitted as opaque structures.
+//!
+//! ```ignore
+//! use binaryninja::rusttypeprinter::RustTypePrinter;
+//!
+//! let source = RustTypePrinter::new().print_view(&bv);
+//! std::fs::write(out_dir.join("target_types.rs"), source).unwrap();
+//! ```
+//!
+//! Names are kept as they are in the view, so the output is best included in its own module:
+//!
+//! ```ignore
+//! #[allow(non_camel_case_types, non_snake_case, non_upper_case_globals, dead_code)]
+//! mod target {
+//!     include!(concat!(env!("OUT_DIR"), "/target_types.rs"));
+//! }
+//! ```
+//!
+//! Pointers are emitted as raw pointers when they're the same width as pointers on the host, and
+//! as integers of the right width otherwise.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::mem;
+
+use crate::binaryview::BinaryViewExt;
+use crate::types::{
+    Enumeration, QualifiedNameAndType, Structure, StructureMember, Type, TypeClass,
+};
+
+const RESERVED: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "bool", "box", "break", "char", "const",
+    "continue", "crate", "do", "dyn", "else", "enum", "extern", "f32", "f64", "false", "final",
+    "fn", "for", "i128", "i16", "i32", "i64", "i8", "if", "impl", "in", "isize", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self",
+    "static", "str", "struct", "super", "trait", "true", "try", "type", "typeof", "u128", "u16",
+    "u32", "u64", "u8", "union", "unsafe", "unsized", "use", "usize", "virtual", "where", "while",
+    "yield",
+];
+
+pub struct RustTypePrinter {
+    layout_tests: bool,
+    newtype_enums: bool,
+}
+
+impl RustTypePrinter {
+    pub fn new() -> Self {
+        Self {
+            layout_tests: true,
+            newtype_enums: false,
+        }
+    }
+
+    /// Whether to emit `#[test]` functions asserting the size and member offsets of each type.
+    /// Enabled by default.
+    pub fn set_layout_tests<'a>(&'a mut self, layout_tests: bool) -> &'a mut Self {
+        self.layout_tests = layout_tests;
+        self
+    }
+
+    /// Emits enumerations as `#[repr(transparent)]` wrappers around their underlying integer,
+    /// with a constant per member, instead of as Rust enums.
+    ///
+    /// Reading a Rust enum whose value isn't one of its variants is undefined behavior, so this
+    /// should be used when the values come from untrusted memory.
+    pub fn set_newtype_enums<'a>(&'a mut self, newtype_enums: bool) -> &'a mut Self {
+        self.newtype_enums = newtype_enums;
+        self
+    }
+
+    /// Returns Rust definitions for all of `types`.
+    pub fn print_types<'a, I>(&self, types: I) -> String
+    where
+        I: IntoIterator<Item = &'a QualifiedNameAndType>,
+    {
+        let mut generator = Generator {
+            options: self,
+            idents: HashMap::new(),
+            function_typedefs: HashSet::new(),
+            used: HashSet::new(),
+            opaque: BTreeMap::new(),
+            items: String::new(),
+            tests: String::new(),
+        };
+
+        let entries: Vec<(String, String, _)> = types
+            .into_iter()
+            .map(|t| {
+                let name = t.name().string();
+                let ident = generator.unique_ident(&name);
+                (name, ident, t.type_object().to_owned())
+            })
+            .collect();
+
+        for (name, ident, ty) in &entries {
+            generator.idents.insert(name.clone(), ident.clone());
+
+            if ty.type_class() == TypeClass::FunctionTypeClass {
+                generator.function_typedefs.insert(ident.clone());
+            }
+        }
+
+        for (name, ident, ty) in &entries {
+            generator.define(name, ident, ty);
+        }
+
+        generator.finish()
+    }
+
+    /// Returns Rust definitions for all types defined in `view`.
+    pub fn print_view<V: BinaryViewExt>(&self, view: &V) -> String {
+        self.print_types(&view.types())
+    }
+}
+
+impl Default for RustTypePrinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Generator<'a> {
+    options: &'a RustTypePrinter,
+    /// Identifiers of the types being printed, by name.
+    idents: HashMap<String, String>,
+    /// Identifiers of function typedefs, which are printed as function pointers.
+    function_typedefs: HashSet<String>,
+    used: HashSet<String>,
+    /// Named types that are referenced but not defined, by identifier, with their width.
+    opaque: BTreeMap<String, u64>,
+    items: String,
+    tests: String,
+}
+
+impl<'a> Generator<'a> {
+    fn finish(self) -> String {
+        let mut out = String::new();
+
+        for (ident, width) in &self.opaque {
+            let _ = writeln!(out, "/// Referenced, but not defined in the view.");
+            let _ = writeln!(out, "#[repr(C)]");
+            let _ = writeln!(out, "#[derive(Clone, Copy)]");
+            let _ = writeln!(out, "pub struct {} {{", ident);
+            let _ = writeln!(out, "    _opaque: [u8; {:#x}],", width);
+            let _ = writeln!(out, "}}\n");
+        }
+
+        out.push_str(&self.items);
+
+        if !self.tests.is_empty() {
+            out.push_str(&self.tests);
+        }
+
+        out
+    }
+
+    fn unique_ident(&mut self, name: &str) -> String {
+        let base = sanitize(name);
+
+        let mut ident = base.clone();
+        let mut i = 1;
+        while !self.used.insert(ident.clone()) {
+            ident = format!("{}_{}", base, i);
+            i += 1;
+        }
+
+        ident
+    }
+
+    fn define(&mut self, name: &str, ident: &str, ty: &Type) {
+        match ty.type_class() {
+            TypeClass::StructureTypeClass => {
+                if let Ok(structure) = ty.get_structure() {
+                    self.define_structure(name, ident, &structure);
+                }
+            }
+            TypeClass::EnumerationTypeClass => {
+                if let Ok(enumeration) = ty.get_enumeration() {
+                    let signed = ty.is_signed().contents;
+                    self.define_enumeration(name, ident, &enumeration, ty.width(), signed);
+                }
+            }
+            TypeClass::FunctionTypeClass => {
+                let function = self.function(ty, ident);
+                let _ = writeln!(self.items, "/// `{}`", name);
+                let _ = writeln!(self.items, "pub type {} = {};\n", ident, function);
+            }
+            _ => {
+                let target = self.rust_type(ty, ident);
+                let _ = writeln!(self.items, "/// `{}`", name);
+                let _ = writeln!(self.items, "pub type {} = {};\n", ident, target);
+            }
+        }
+    }
+
+    fn define_structure(&mut self, name: &str, ident: &str, structure: &Structure) {
+        let is_union = structure.is_union();
+        let width = structure.width();
+
+        let mut members = structure.members();
+        members.sort_by_key(|m| m.offset);
+
+        let layout = layout(
+            is_union,
+            width,
+            structure.alignment() as u64,
+            structure.packed(),
+            &members
+                .iter()
+                .map(|m| MemberLayout {
+                    offset: m.offset,
+                    width: m.ty.contents.width(),
+                    alignment: m.ty.contents.alignment() as u64,
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        let mut fields = Vec::new();
+        let mut field_names = HashSet::new();
+
+        for slot in &layout.slots {
+            match *slot {
+                Slot::Member(i) => {
+                    let member = &members[i];
+                    let field = unique_field(&mut field_names, &member_name(member));
+                    let ty = self.rust_type(&member.ty.contents, &format!("{}_{}", ident, field));
+                    fields.push((field, ty, member.offset, true));
+                }
+                Slot::Padding { offset, len } => {
+                    let padding = if is_union {
+                        unique_field(&mut field_names, "_padding")
+                    } else {
+                        unique_field(&mut field_names, &format!("_padding_{:x}", offset))
+                    };
+                    fields.push((padding, bytes(len), offset, false));
+                }
+            }
+        }
+
+        let skipped = layout.skipped.iter().map(|&i| &members[i]);
+        let repr = layout.repr;
+
+        let out = &mut self.items;
+        let _ = writeln!(out, "/// `{}`", name);
+        let _ = writeln!(out, "#[repr({})]", repr);
+        let _ = writeln!(out, "#[derive(Clone, Copy)]");
+        let _ = writeln!(
+            out,
+            "pub {} {} {{",
+            if is_union { "union" } else { "struct" },
+            ident
+        );
+
+        for (field, ty, offset, _) in &fields {
+            let _ = writeln!(out, "    /// Offset {:#x}", offset);
+            let _ = writeln!(out, "    pub {}: {},", field, ty);
+        }
+
+        for member in skipped {
+            let _ = writeln!(
+                out,
+                "    // {} at {:#x} overlaps another member",
+                member.name, member.offset
+            );
+        }
+
+        out.push_str("}\n\n");
+
+        if self.options.layout_tests {
+            let out = &mut self.tests;
+            let _ = writeln!(out, "#[test]");
+            let _ = writeln!(out, "fn layout_{}() {{", ident);
+            let _ = writeln!(
+                out,
+                "    assert_eq!(::std::mem::size_of::<{}>(), {:#x});",
+                ident, width
+            );
+
+            if fields.iter().any(|(_, _, _, real)| *real) {
+                let _ = writeln!(
+                    out,
+                    "    let uninit = ::std::mem::MaybeUninit::<{}>::uninit();",
+                    ident
+                );
+                let _ = writeln!(out, "    let ptr = uninit.as_ptr();");
+
+                for (field, _, offset, _) in fields.iter().filter(|(_, _, _, real)| *real) {
+                    let _ = writeln!(
+                        out,
+                        "    assert_eq!(unsafe {{ ::std::ptr::addr_of!((*ptr).{}) }} as usize - ptr as usize, {:#x});",
+                        field, offset
+                    );
+                }
+            }
+
+            out.push_str("}\n\n");
+        }
+    }
+
+    fn define_enumeration(
+        &mut self,
+        name: &str,
+        ident: &str,
+        enumeration: &Enumeration,
+        width: u64,
+        signed: bool,
+    ) {
+        let repr = match integer(width, signed) {
+            Some(repr) => repr,
+            None => {
+                let _ = writeln!(self.items, "/// `{}`", name);
+                let _ = writeln!(self.items, "pub type {} = [u8; {:#x}];\n", ident, width);
+                return;
+            }
+        };
+
+        let members = enumeration.members();
+
+        let mut variants = Vec::new();
+        let mut names = HashSet::new();
+        for member in &members {
+            let variant = unique_field(&mut names, member.name.as_str());
+            variants.push((variant, literal(member.value, width, signed)));
+        }
+
+        let out = &mut self.items;
+        let _ = writeln!(out, "/// `{}`", name);
+
+        if self.options.newtype_enums || variants.is_empty() {
+            let _ = writeln!(out, "#[repr(transparent)]");
+            let _ = writeln!(out, "#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]");
+            let _ = writeln!(out, "pub struct {}(pub {});\n", ident, repr);
+
+            if !variants.is_empty() {
+                let _ = writeln!(out, "impl {} {{", ident);
+                for (variant, value) in &variants {
+                    let _ = writeln!(out, "    pub const {}: Self = Self({});", variant, value);
+                }
+                out.push_str("}\n\n");
+            }
+        } else {
+            // discriminants have to be unique, later members with the same value become
+            // associated constants
+            let mut seen: HashMap<&str, &str> = HashMap::new();
+            let mut aliases = Vec::new();
+
+            let _ = writeln!(out, "#[repr({})]", repr);
+            let _ = writeln!(out, "#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]");
+            let _ = writeln!(out, "pub enum {} {{", ident);
+            for (variant, value) in &variants {
+                match seen.get(value.as_str()) {
+                    Some(original) => aliases.push((variant, *original)),
+                    None => {
+                        seen.insert(value, variant);
+                        let _ = writeln!(out, "    {} = {},", variant, value);
+                    }
+                }
+            }
+            out.push_str("}\n\n");
+
+            if !aliases.is_empty() {
+                let _ = writeln!(out, "impl {} {{", ident);
+                for (alias, original) in aliases {
+                    let _ = writeln!(out, "    pub const {}: Self = Self::{};", alias, original);
+                }
+                out.push_str("}\n\n");
+            }
+        }
+
+        if self.options.layout_tests {
+            let _ = writeln!(self.tests, "#[test]");
+            let _ = writeln!(self.tests, "fn layout_{}() {{", ident);
+            let _ = writeln!(
+                self.tests,
+                "    assert_eq!(::std::mem::size_of::<{}>(), {:#x});",
+                ident, width
+            );
+            self.tests.push_str("}\n\n");
+        }
+    }
+
+    /// Returns the rust type for `ty`. Structures and enumerations defined inline are given
+    /// their own definition named `context`.
+    fn rust_type(&mut self, ty: &Type, context: &str) -> String {
+        let width = ty.width();
+
+        match ty.type_class() {
+            TypeClass::VoidTypeClass => "::std::ffi::c_void".to_string(),
+            TypeClass::BoolTypeClass if width == 1 => "bool".to_string(),
+            TypeClass::BoolTypeClass | TypeClass::WideCharTypeClass => {
+                bytes_or(integer(width, false), width)
+            }
+            TypeClass::IntegerTypeClass => bytes_or(integer(width, ty.is_signed().contents), width),
+            TypeClass::FloatTypeClass => match width {
+                4 => "f32".to_string(),
+                8 => "f64".to_string(),
+                _ => bytes(width),
+            },
+            TypeClass::PointerTypeClass => {
+                if width != mem::size_of::<usize>() as u64 {
+                    return bytes_or(integer(width, false), width);
+                }
+
+                let target = match ty.target() {
+                    Ok(target) => target.contents,
+                    Err(_) => return "*mut ::std::ffi::c_void".to_string(),
+                };
+
+                match target.type_class() {
+                    TypeClass::FunctionTypeClass => {
+                        format!("Option<{}>", self.function(&target, context))
+                    }
+                    TypeClass::NamedTypeReferenceClass => {
+                        let pointee = self.rust_type(&target, context);
+                        if self.function_typedefs.contains(&pointee) {
+                            format!("Option<{}>", pointee)
+                        } else {
+                            pointer(&target, &pointee)
+                        }
+                    }
+                    _ => {
+                        let pointee = self.rust_type(&target, context);
+                        pointer(&target, &pointee)
+                    }
+                }
+            }
+            TypeClass::ArrayTypeClass => match ty.element_type() {
+                Ok(element) => {
+                    let element = self.rust_type(&element.contents, context);
+                    format!("[{}; {:#x}]", element, ty.count())
+                }
+                Err(_) => bytes(width),
+            },
+            TypeClass::StructureTypeClass => match ty.get_structure() {
+                Ok(structure) => {
+                    let ident = self.unique_ident(context);
+                    self.define_structure(context, &ident, &structure);
+                    ident
+                }
+                Err(_) => bytes(width),
+            },
+            TypeClass::EnumerationTypeClass => match ty.get_enumeration() {
+                Ok(enumeration) => {
+                    let ident = self.unique_ident(context);
+                    let signed = ty.is_signed().contents;
+                    self.define_enumeration(context, &ident, &enumeration, width, signed);
+                    ident
+                }
+                Err(_) => bytes(width),
+            },
+            TypeClass::FunctionTypeClass => self.function(ty, context),
+            TypeClass::NamedTypeReferenceClass => match ty.get_named_type_reference() {
+                Ok(ntr) => {
+                    let name = ntr.name().string();
+
+                    if let Some(ident) = self.idents.get(&name) {
+                        if !self.opaque.contains_key(ident) {
+                            return ident.clone();
+                        }
+                    }
+
+                    // forward declaration without a definition
+                    let ident = match self.idents.get(&name) {
+                        Some(ident) => ident.clone(),
+                        None => {
+                            let ident = self.unique_ident(&name);
+                            self.idents.insert(name, ident.clone());
+                            ident
+                        }
+                    };
+
+                    let opaque_width = self.opaque.entry(ident.clone()).or_insert(0);
+                    *opaque_width = (*opaque_width).max(width);
+
+                    ident
+                }
+                Err(_) => bytes(width),
+            },
+            _ => bytes(width),
+        }
+    }
+
+    fn function(&mut self, ty: &Type, context: &str) -> String {
+        let mut parameters: Vec<String> = ty
+            .parameters()
+            .iter()
+            .map(|p| self.rust_type(&p.t.contents, context))
+            .collect();
+
+        if ty.has_variable_arguments().contents {
+            parameters.push("...".to_string());
+        }
+
+        let mut function = format!("unsafe extern \"C\" fn({})", parameters.join(", "));
+
+        if let Ok(return_type) = ty.return_value() {
+            if return_type.contents.type_class() != TypeClass::VoidTypeClass {
+                let _ = write!(
+                    function,
+                    " -> {}",
+                    self.rust_type(&return_type.contents, context)
+                );
+            }
+        }
+
+        function
+    }
+}
+
+/// The parts of a structure member that decide where it can go.
+#[derive(Clone, Copy)]
+struct MemberLayout {
+    offset: u64,
+    width: u64,
+    alignment: u64,
+}
+
+#[derive(Debug, PartialEq)]
+enum Slot {
+    /// The member at this index.
+    Member(usize),
+    Padding {
+        offset: u64,
+        len: u64,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+struct Layout {
+    repr: String,
+    slots: Vec<Slot>,
+    /// Members that can't be represented, by index.
+    skipped: Vec<usize>,
+}
+
+/// Lays out `members`, sorted by offset, as the fields of a `#[repr(C)]` item that is `width`
+/// bytes wide with the given alignment.
+fn layout(
+    is_union: bool,
+    width: u64,
+    alignment: u64,
+    packed: bool,
+    members: &[MemberLayout],
+) -> Layout {
+    let alignment = alignment.max(1);
+
+    let mut packed = packed || width % alignment != 0;
+    let mut natural_alignment = 1;
+
+    let mut slots = Vec::new();
+    let mut skipped = Vec::new();
+    let mut cursor = 0;
+
+    for (i, member) in members.iter().enumerate() {
+        let member_alignment = member.alignment.max(1);
+
+        // rust has no way to express overlapping structure members, or union members that
+        // don't start at the beginning of the union
+        if (is_union && member.offset != 0) || (!is_union && member.offset < cursor) {
+            skipped.push(i);
+            continue;
+        }
+
+        if !is_union && member.offset > cursor {
+            slots.push(Slot::Padding {
+                offset: cursor,
+                len: member.offset - cursor,
+            });
+        }
+
+        if member.offset % member_alignment != 0 {
+            packed = true;
+        }
+        natural_alignment = natural_alignment.max(member_alignment);
+
+        slots.push(Slot::Member(i));
+
+        cursor = cursor.max(member.offset + member.width);
+    }
+
+    // rust rounds the size up to a multiple of the alignment
+    if width % natural_alignment != 0 {
+        packed = true;
+    }
+
+    if width > cursor {
+        if is_union {
+            slots.push(Slot::Padding {
+                offset: 0,
+                len: width,
+            });
+        } else {
+            slots.push(Slot::Padding {
+                offset: cursor,
+                len: width - cursor,
+            });
+        }
+    }
+
+    let repr = if packed {
+        "C, packed".to_string()
+    } else if alignment > natural_alignment {
+        format!("C, align({})", alignment)
+    } else {
+        "C".to_string()
+    };
+
+    Layout {
+        repr,
+        slots,
+        skipped,
+    }
+}
+
+fn sanitize(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    if RESERVED.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+
+    ident
+}
+
+fn member_name(member: &StructureMember) -> String {
+    if member.name.as_str().is_empty() {
+        format!("field_{:x}", member.offset)
+    } else {
+        member.name.as_str().to_string()
+    }
+}
+
+fn unique_field(used: &mut HashSet<String>, name: &str) -> String {
+    let base = sanitize(name);
+
+    let mut field = base.clone();
+    let mut i = 1;
+    while !used.insert(field.clone()) {
+        field = format!("{}_{}", base, i);
+        i += 1;
+    }
+
+    field
+}
+
+fn integer(width: u64, signed: bool) -> Option<String> {
+    match width {
+        1 | 2 | 4 | 8 | 16 => Some(format!("{}{}", if signed { 'i' } else { 'u' }, width * 8)),
+        _ => None,
+    }
+}
+
+fn bytes(width: u64) -> String {
+    format!("[u8; {:#x}]", width)
+}
+
+fn bytes_or(ty: Option<String>, width: u64) -> String {
+    ty.unwrap_or_else(|| bytes(width))
+}
+
+fn pointer(target: &Type, pointee: &str) -> String {
+    if target.is_const().contents {
+        format!("*const {}", pointee)
+    } else {
+        format!("*mut {}", pointee)
+    }
+}
+
+/// Formats an enumeration value as a literal of the enumeration's underlying type.
+fn literal(value: u64, width: u64, signed: bool) -> String {
+    let bits = (width * 8).min(64) as u32;
+    let shift = 64 - bits;
+
+    if signed {
+        let value = ((value << shift) as i64) >> shift;
+        value.to_string()
+    } else {
+        let value = (value << shift) >> shift;
+        format!("{:#x}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(offset: u64, width: u64, alignment: u64) -> MemberLayout {
+        MemberLayout {
+            offset,
+            width,
+            alignment,
+        }
+    }
+
+    #[test]
+    fn natural_layout() {
+        let members = [member(0, 4, 4), member(4, 2, 2), member(6, 2, 2)];
+        let layout = layout(false, 8, 4, false, &members);
+
+        assert_eq!(layout.repr, "C");
+        assert_eq!(
+            layout.slots,
+            vec![Slot::Member(0), Slot::Member(1), Slot::Member(2)]
+        );
+        assert!(layout.skipped.is_empty());
+    }
+
+    #[test]
+    fn padding_between_and_after_members() {
+        let members = [member(0, 1, 1), member(8, 4, 4)];
+        let layout = layout(false, 0x10, 4, false, &members);
+
+        assert_eq!(layout.repr, "C");
+        assert_eq!(
+            layout.slots,
+            vec![
+                Slot::Member(0),
+                Slot::Padding { offset: 1, len: 7 },
+                Slot::Member(1),
+                Slot::Padding { offset: 12, len: 4 },
+            ]
+        );
+    }
+
+    #[test]
+    fn misaligned_member_is_packed() {
+        let members = [member(0, 1, 1), member(1, 4, 4)];
+        let layout = layout(false, 5, 1, false, &members);
+
+        assert_eq!(layout.repr, "C, packed");
+    }
+
+    #[test]
+    fn trailing_width_not_a_multiple_of_alignment_is_packed() {
+        // rust would pad this to 8 bytes
+        let members = [member(0, 4, 4), member(4, 1, 1)];
+        let layout = layout(false, 5, 1, false, &members);
+
+        assert_eq!(layout.repr, "C, packed");
+        assert_eq!(layout.slots, vec![Slot::Member(0), Slot::Member(1)]);
+    }
+
+    #[test]
+    fn explicitly_packed() {
+        let members = [member(0, 4, 4), member(4, 4, 4)];
+        let layout = layout(false, 8, 4, true, &members);
+
+        assert_eq!(layout.repr, "C, packed");
+    }
+
+    #[test]
+    fn over_aligned() {
+        let members = [member(0, 4, 4)];
+        let layout = layout(false, 0x10, 0x10, false, &members);
+
+        assert_eq!(layout.repr, "C, align(16)");
+        assert_eq!(
+            layout.slots,
+            vec![Slot::Member(0), Slot::Padding { offset: 4, len: 12 }]
+        );
+    }
+
+    #[test]
+    fn union_members() {
+        let members = [member(0, 4, 4), member(0, 8, 8)];
+        let layout = layout(true, 8, 8, false, &members);
+
+        assert_eq!(layout.repr, "C");
+        assert_eq!(layout.slots, vec![Slot::Member(0), Slot::Member(1)]);
+        assert!(layout.skipped.is_empty());
+    }
+
+    #[test]
+    fn union_padding_and_offset_members() {
+        let members = [member(0, 2, 2), member(2, 2, 2)];
+        let layout = layout(true, 6, 2, false, &members);
+
+        assert_eq!(layout.repr, "C");
+        assert_eq!(
+            layout.slots,
+            vec![Slot::Member(0), Slot::Padding { offset: 0, len: 6 }]
+        );
+        assert_eq!(layout.skipped, vec![1]);
+    }
+
+    #[test]
+    fn overlapping_members_are_skipped() {
+        let members = [member(0, 8, 8), member(4, 4, 4), member(8, 4, 4)];
+        let layout = layout(false, 0x10, 8, false, &members);
+
+        assert_eq!(layout.repr, "C");
+        assert_eq!(
+            layout.slots,
+            vec![
+                Slot::Member(0),
+                Slot::Member(2),
+                Slot::Padding { offset: 12, len: 4 },
+            ]
+        );
+        assert_eq!(layout.skipped, vec![1]);
+    }
+}
@@ -0,0 +1,294 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tags mark addresses, functions and data with a `TagType` and a string of data.
+//!
+//! ```ignore
+//! use binaryninja::binaryview::BinaryViewExt;
+//!
+//! let suspicious = bv.create_tag_type("Suspicious", "⚠");
+//! func.create_user_address_tag(&func.arch(), addr, &suspicious, "call through user pointer");
+//!
+//! for reference in bv.tag_references_of_type(&suspicious).iter() {
+//!     println!("{:x}: {}", reference.address(), reference.tag().data());
+//! }
+//! ```
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNTagReferenceType as TagReferenceType;
+pub use binaryninjacore_sys::BNTagTypeType as TagTypeType;
+
+use crate::architecture::CoreArchitecture;
+use crate::binaryview::BinaryView;
+use crate::function::Function;
+
+use crate::rc::*;
+use crate::string::*;
+
+//////////////
+// TagType
+
+#[derive(PartialEq, Eq, Hash)]
+pub struct TagType {
+    pub(crate) handle: *mut BNTagType,
+}
+
+unsafe impl Send for TagType {}
+unsafe impl Sync for TagType {}
+
+impl TagType {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNTagType) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+
+        Ref::new(Self { handle })
+    }
+
+    pub fn view(&self) -> Ref<BinaryView> {
+        unsafe { BinaryView::from_raw(BNTagTypeGetView(self.handle)) }
+    }
+
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTagTypeGetId(self.handle)) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTagTypeGetName(self.handle)) }
+    }
+
+    pub fn set_name<S: BnStrCompatible>(&self, name: S) {
+        let name = name.as_bytes_with_nul();
+
+        unsafe { BNTagTypeSetName(self.handle, name.as_ref().as_ptr() as *mut _) }
+    }
+
+    /// The icon shown next to tags of this type, usually a single emoji.
+    pub fn icon(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTagTypeGetIcon(self.handle)) }
+    }
+
+    pub fn set_icon<S: BnStrCompatible>(&self, icon: S) {
+        let icon = icon.as_bytes_with_nul();
+
+        unsafe { BNTagTypeSetIcon(self.handle, icon.as_ref().as_ptr() as *mut _) }
+    }
+
+    pub fn visible(&self) -> bool {
+        unsafe { BNTagTypeGetVisible(self.handle) }
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        unsafe { BNTagTypeSetVisible(self.handle, visible) }
+    }
+
+    pub fn kind(&self) -> TagTypeType {
+        unsafe { BNTagTypeGetType(self.handle) }
+    }
+
+    pub fn set_kind(&self, kind: TagTypeType) {
+        unsafe { BNTagTypeSetType(self.handle, kind) }
+    }
+}
+
+impl ToOwned for TagType {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for TagType {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewTagTypeReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeTagType(handle.handle);
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for TagType {
+    type Raw = *mut BNTagType;
+    type Context = ();
+
+    unsafe fn free(raw: *mut *mut BNTagType, count: usize, _context: &()) {
+        BNFreeTagTypeList(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for TagType {
+    type Wrapped = Guard<'a, TagType>;
+
+    unsafe fn wrap_raw(raw: &'a *mut BNTagType, context: &'a ()) -> Guard<'a, TagType> {
+        debug_assert!(!raw.is_null());
+        Guard::new(TagType { handle: *raw }, context)
+    }
+}
+
+//////////
+// Tag
+
+#[derive(PartialEq, Eq, Hash)]
+pub struct Tag {
+    pub(crate) handle: *mut BNTag,
+}
+
+unsafe impl Send for Tag {}
+unsafe impl Sync for Tag {}
+
+impl Tag {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNTag) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+
+        Ref::new(Self { handle })
+    }
+
+    /// Creates a tag that isn't known to any view yet. `BinaryViewExt::create_tag` creates one
+    /// and adds it to the view in one go.
+    pub fn new<S: BnStrCompatible>(tag_type: &TagType, data: S) -> Ref<Self> {
+        let data = data.as_bytes_with_nul();
+
+        unsafe {
+            Self::ref_from_raw(BNCreateTag(
+                tag_type.handle,
+                data.as_ref().as_ptr() as *mut _,
+            ))
+        }
+    }
+
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTagGetId(self.handle)) }
+    }
+
+    pub fn tag_type(&self) -> Ref<TagType> {
+        unsafe { TagType::ref_from_raw(BNTagGetType(self.handle)) }
+    }
+
+    pub fn data(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTagGetData(self.handle)) }
+    }
+
+    pub fn set_data<S: BnStrCompatible>(&self, data: S) {
+        let data = data.as_bytes_with_nul();
+
+        unsafe { BNTagSetData(self.handle, data.as_ref().as_ptr() as *mut _) }
+    }
+}
+
+impl ToOwned for Tag {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for Tag {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewTagReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeTag(handle.handle);
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for Tag {
+    type Raw = *mut BNTag;
+    type Context = ();
+
+    unsafe fn free(raw: *mut *mut BNTag, count: usize, _context: &()) {
+        BNFreeTagList(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for Tag {
+    type Wrapped = Guard<'a, Tag>;
+
+    unsafe fn wrap_raw(raw: &'a *mut BNTag, context: &'a ()) -> Guard<'a, Tag> {
+        debug_assert!(!raw.is_null());
+        Guard::new(Tag { handle: *raw }, context)
+    }
+}
+
+///////////////////
+// TagReference
+
+/// Where a tag has been placed: on an address within a function, on a function as a whole, or
+/// on data.
+#[repr(transparent)]
+pub struct TagReference(BNTagReference);
+
+impl TagReference {
+    pub fn reference_type(&self) -> TagReferenceType {
+        self.0.refType
+    }
+
+    pub fn is_auto(&self) -> bool {
+        self.0.autoDefined
+    }
+
+    pub fn tag(&self) -> Ref<Tag> {
+        Tag { handle: self.0.tag }.to_owned()
+    }
+
+    /// The architecture of the tagged address, for address tags.
+    pub fn arch(&self) -> Option<CoreArchitecture> {
+        if self.0.arch.is_null() {
+            None
+        } else {
+            Some(unsafe { CoreArchitecture::from_raw(self.0.arch) })
+        }
+    }
+
+    /// The tagged function, or the function containing the tagged address. `None` for data tags.
+    pub fn function(&self) -> Option<Ref<Function>> {
+        if self.0.func.is_null() {
+            None
+        } else {
+            Some(
+                Function {
+                    handle: self.0.func,
+                }
+                .to_owned(),
+            )
+        }
+    }
+
+    pub fn address(&self) -> u64 {
+        self.0.addr
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for TagReference {
+    type Raw = BNTagReference;
+    type Context = ();
+
+    unsafe fn free(raw: *mut BNTagReference, count: usize, _context: &()) {
+        BNFreeTagReferences(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for TagReference {
+    type Wrapped = &'a TagReference;
+
+    unsafe fn wrap_raw(raw: &'a BNTagReference, _context: &'a ()) -> &'a TagReference {
+        &*(raw as *const BNTagReference as *const TagReference)
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reading typed data out of a `BinaryView`.
+//!
+//! `TypedValue::read` decodes the bytes at an address according to a `Type`, producing a tree of
+//! values that mirrors the type: structures keep their member names, enumerations are resolved
+//! to member names where possible and named type references are resolved through the view.
+
+use crate::binaryview::{BinaryView, BinaryViewBase, BinaryViewExt, Result};
+use crate::rc::*;
+use crate::types::{Structure, Type, TypeClass};
+use crate::Endianness;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypedValue {
+    Void,
+    Bool(bool),
+    /// Raw bits of an integer (or wide character) `width` bytes wide.
+    ///
+    /// Use `signed_value` to get a sign extended value for signed integers.
+    Integer {
+        value: u64,
+        width: usize,
+        signed: bool,
+    },
+    Float(f64),
+    Pointer(u64),
+    Array(Vec<TypedValue>),
+    Structure(Vec<TypedMember>),
+    /// `name` is `None` when the value doesn't match any member of the enumeration.
+    Enumeration {
+        value: u64,
+        name: Option<String>,
+    },
+    /// Data that can't be decoded any further (functions, unusual widths, unresolved types).
+    Bytes(Vec<u8>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypedMember {
+    pub name: String,
+    pub offset: u64,
+    pub value: TypedValue,
+}
+
+impl TypedValue {
+    /// Reads a value of type `ty` from `view` at `addr`.
+    ///
+    /// Fails if the full width of the type can't be read.
+    pub fn read<V: BinaryViewBase + ?Sized>(view: &V, addr: u64, ty: &Type) -> Result<Self> {
+        let view_ref = view.as_ref();
+        let ty = resolve(view_ref, ty).ok_or(())?;
+
+        let mut data = vec![0; ty.width() as usize];
+        if view.read(&mut data, addr) != data.len() {
+            return Err(());
+        }
+
+        let reader = Reader {
+            view: view_ref,
+            endianness: view.default_endianness(),
+        };
+
+        reader.decode(&ty, &data)
+    }
+
+    /// Returns an integer value sign extended to 64 bits if it is signed.
+    pub fn signed_value(&self) -> Option<i64> {
+        match *self {
+            TypedValue::Integer {
+                value,
+                width,
+                signed,
+            } => {
+                if signed {
+                    Some(sign_extend(value, width) as i64)
+                } else {
+                    Some(value as i64)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the member named `name` if this is a structure value.
+    pub fn member<S: AsRef<str>>(&self, name: S) -> Option<&TypedValue> {
+        match self {
+            TypedValue::Structure(members) => members
+                .iter()
+                .find(|m| m.name == name.as_ref())
+                .map(|m| &m.value),
+            _ => None,
+        }
+    }
+}
+
+fn resolve(view: &BinaryView, ty: &Type) -> Option<Ref<Type>> {
+    if ty.type_class() != TypeClass::NamedTypeReferenceClass {
+        return Some(ty.to_owned());
+    }
+
+    let ntr = ty.get_named_type_reference().ok()?;
+    let id = ntr.id();
+
+    let resolved = if id.as_str().is_empty() {
+        None
+    } else {
+        view.get_type_by_id(id)
+    };
+
+    // don't chase chains of references; the core resolves these for us
+    resolved
+        .or_else(|| view.get_type_by_name(ntr.name()))
+        .filter(|resolved| resolved.type_class() != TypeClass::NamedTypeReferenceClass)
+}
+
+fn sign_extend(value: u64, width: usize) -> u64 {
+    if width == 0 || width >= 8 {
+        return value;
+    }
+
+    let shift = 64 - width * 8;
+    (((value << shift) as i64) >> shift) as u64
+}
+
+struct Reader<'a> {
+    view: &'a BinaryView,
+    endianness: Endianness,
+}
+
+impl<'a> Reader<'a> {
+    fn uint(&self, data: &[u8]) -> u64 {
+        let fold = |acc: u64, b: &u8| (acc << 8) | *b as u64;
+
+        match self.endianness {
+            Endianness::LittleEndian => data.iter().rev().fold(0, fold),
+            Endianness::BigEndian => data.iter().fold(0, fold),
+        }
+    }
+
+    fn decode(&self, ty: &Type, data: &[u8]) -> Result<TypedValue> {
+        let width = data.len();
+
+        let value = match ty.type_class() {
+            TypeClass::VoidTypeClass if width == 0 => TypedValue::Void,
+            TypeClass::BoolTypeClass => TypedValue::Bool(data.iter().any(|b| *b != 0)),
+            TypeClass::IntegerTypeClass | TypeClass::WideCharTypeClass if width <= 8 => {
+                TypedValue::Integer {
+                    value: self.uint(data),
+                    width,
+                    signed: ty.is_signed().contents,
+                }
+            }
+            TypeClass::FloatTypeClass if width == 4 => {
+                TypedValue::Float(f32::from_bits(self.uint(data) as u32) as f64)
+            }
+            TypeClass::FloatTypeClass if width == 8 => {
+                TypedValue::Float(f64::from_bits(self.uint(data)))
+            }
+            TypeClass::PointerTypeClass if width <= 8 => TypedValue::Pointer(self.uint(data)),
+            TypeClass::ArrayTypeClass => self.decode_array(ty, data)?,
+            TypeClass::StructureTypeClass => {
+                let structure = ty.get_structure()?;
+                self.decode_structure(&structure, data)?
+            }
+            TypeClass::EnumerationTypeClass if width <= 8 => {
+                let enumeration = ty.get_enumeration()?;
+
+                let mut value = self.uint(data);
+                if ty.is_signed().contents {
+                    value = sign_extend(value, width);
+                }
+
+                let name = enumeration
+                    .members()
+                    .into_iter()
+                    .find(|m| m.value == value)
+                    .map(|m| m.name.as_str().to_owned());
+
+                TypedValue::Enumeration { value, name }
+            }
+            TypeClass::NamedTypeReferenceClass => match resolve(self.view, ty) {
+                Some(resolved) if resolved.width() as usize == width => {
+                    self.decode(&resolved, data)?
+                }
+                _ => TypedValue::Bytes(data.to_vec()),
+            },
+            _ => TypedValue::Bytes(data.to_vec()),
+        };
+
+        Ok(value)
+    }
+
+    fn decode_array(&self, ty: &Type, data: &[u8]) -> Result<TypedValue> {
+        let element_type = ty.element_type()?.contents;
+        let element_width = element_type.width() as usize;
+        let count = ty.count() as usize;
+
+        if element_width == 0 || element_width * count > data.len() {
+            return Err(());
+        }
+
+        data.chunks_exact(element_width)
+            .take(count)
+            .map(|element| self.decode(&element_type, element))
+            .collect::<Result<Vec<_>>>()
+            .map(TypedValue::Array)
+    }
+
+    fn decode_structure(&self, structure: &Structure, data: &[u8]) -> Result<TypedValue> {
+        structure
+            .members()
+            .into_iter()
+            .map(|member| {
+                let start = member.offset as usize;
+                let end = start + member.ty.contents.width() as usize;
+                let member_data = data.get(start..end).ok_or(())?;
+
+                Ok(TypedMember {
+                    name: member.name.as_str().to_owned(),
+                    offset: member.offset,
+                    value: self.decode(&member.ty.contents, member_data)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(TypedValue::Structure)
+    }
+}
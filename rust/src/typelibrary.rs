@@ -0,0 +1,321 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Type libraries are collections of types and object (function/variable) prototypes that can be
+//! imported into a `BinaryView` on demand.
+//!
+//! ```ignore
+//! use binaryninja::typelibrary::TypeLibrary;
+//!
+//! let lib = TypeLibrary::new(&arch, "librtos.so");
+//! lib.add_platform(&platform);
+//! lib.add_named_type("task_t", &task_type);
+//! lib.add_named_object("task_create", &task_create_type);
+//! lib.finalize();
+//! lib.write_to_file("librtos.so.bntl");
+//! ```
+
+use binaryninjacore_sys::*;
+
+use crate::architecture::CoreArchitecture;
+use crate::metadata::{CoreMetadata, Metadata};
+use crate::platform::Platform;
+use crate::rc::*;
+use crate::string::*;
+use crate::types::{QualifiedName, QualifiedNameAndType, Type};
+
+#[derive(PartialEq, Eq, Hash)]
+pub struct TypeLibrary {
+    pub(crate) handle: *mut BNTypeLibrary,
+}
+
+unsafe impl Send for TypeLibrary {}
+unsafe impl Sync for TypeLibrary {}
+
+impl TypeLibrary {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNTypeLibrary) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+
+        Ref::new(Self { handle })
+    }
+
+    unsafe fn option_from_raw(handle: *mut BNTypeLibrary) -> Option<Ref<Self>> {
+        if handle.is_null() {
+            None
+        } else {
+            Some(Self::ref_from_raw(handle))
+        }
+    }
+
+    /// Creates an empty type library. Call `finalize` once it's been populated.
+    pub fn new<S: BnStrCompatible>(arch: &CoreArchitecture, name: S) -> Ref<Self> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe { Self::ref_from_raw(BNNewTypeLibrary(arch.0, name.as_ref().as_ptr() as *mut _)) }
+    }
+
+    pub fn load_from_file<S: BnStrCompatible>(path: S) -> Option<Ref<Self>> {
+        let path = path.as_bytes_with_nul();
+
+        unsafe {
+            Self::option_from_raw(BNLoadTypeLibraryFromFile(path.as_ref().as_ptr() as *mut _))
+        }
+    }
+
+    /// Looks up a type library that has already been loaded for `arch`.
+    pub fn from_name<S: BnStrCompatible>(arch: &CoreArchitecture, name: S) -> Option<Ref<Self>> {
+        let name = name.as_bytes_with_nul();
+
+        unsafe {
+            Self::option_from_raw(BNLookupTypeLibraryByName(
+                arch.0,
+                name.as_ref().as_ptr() as *mut _,
+            ))
+        }
+    }
+
+    pub fn from_guid<S: BnStrCompatible>(arch: &CoreArchitecture, guid: S) -> Option<Ref<Self>> {
+        let guid = guid.as_bytes_with_nul();
+
+        unsafe {
+            Self::option_from_raw(BNLookupTypeLibraryByGuid(
+                arch.0,
+                guid.as_ref().as_ptr() as *mut _,
+            ))
+        }
+    }
+
+    pub fn list_by_arch(arch: &CoreArchitecture) -> Array<TypeLibrary> {
+        unsafe {
+            let mut count = 0;
+            let handles = BNGetArchitectureTypeLibraries(arch.0, &mut count);
+
+            Array::new(handles, count, ())
+        }
+    }
+
+    /// Returns a copy of this library that can be modified.
+    pub fn duplicate(&self) -> Ref<Self> {
+        unsafe { Self::ref_from_raw(BNDuplicateTypeLibrary(self.handle)) }
+    }
+
+    /// Prepares the library for use once all types and objects have been added.
+    pub fn finalize(&self) {
+        unsafe { BNFinalizeTypeLibrary(self.handle) }
+    }
+
+    pub fn write_to_file<S: BnStrCompatible>(&self, path: S) {
+        let path = path.as_bytes_with_nul();
+
+        unsafe { BNWriteTypeLibraryToFile(self.handle, path.as_ref().as_ptr() as *mut _) }
+    }
+
+    pub fn arch(&self) -> CoreArchitecture {
+        unsafe { CoreArchitecture::from_raw(BNGetTypeLibraryArchitecture(self.handle)) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTypeLibraryName(self.handle)) }
+    }
+
+    pub fn set_name<S: BnStrCompatible>(&self, name: S) {
+        let name = name.as_bytes_with_nul();
+
+        unsafe { BNSetTypeLibraryName(self.handle, name.as_ref().as_ptr() as *mut _) }
+    }
+
+    pub fn alternate_names(&self) -> Array<BnString> {
+        unsafe {
+            let mut count = 0;
+            let names = BNGetTypeLibraryAlternateNames(self.handle, &mut count);
+
+            Array::new(names, count, ())
+        }
+    }
+
+    pub fn add_alternate_name<S: BnStrCompatible>(&self, name: S) {
+        let name = name.as_bytes_with_nul();
+
+        unsafe { BNAddTypeLibraryAlternateName(self.handle, name.as_ref().as_ptr() as *mut _) }
+    }
+
+    /// The name used to refer to this library from other libraries.
+    pub fn dependency_name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTypeLibraryDependencyName(self.handle)) }
+    }
+
+    pub fn set_dependency_name<S: BnStrCompatible>(&self, name: S) {
+        let name = name.as_bytes_with_nul();
+
+        unsafe { BNSetTypeLibraryDependencyName(self.handle, name.as_ref().as_ptr() as *mut _) }
+    }
+
+    pub fn guid(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTypeLibraryGuid(self.handle)) }
+    }
+
+    pub fn set_guid<S: BnStrCompatible>(&self, guid: S) {
+        let guid = guid.as_bytes_with_nul();
+
+        unsafe { BNSetTypeLibraryGuid(self.handle, guid.as_ref().as_ptr() as *mut _) }
+    }
+
+    /// Names of the platforms this library applies to.
+    pub fn platform_names(&self) -> Array<BnString> {
+        unsafe {
+            let mut count = 0;
+            let names = BNGetTypeLibraryPlatforms(self.handle, &mut count);
+
+            Array::new(names, count, ())
+        }
+    }
+
+    pub fn add_platform(&self, platform: &Platform) {
+        unsafe { BNAddTypeLibraryPlatform(self.handle, platform.handle) }
+    }
+
+    pub fn clear_platforms(&self) {
+        unsafe { BNClearTypeLibraryPlatforms(self.handle) }
+    }
+
+    /// Adds the prototype of a function or variable exported by the library.
+    pub fn add_named_object<N: Into<QualifiedName>>(&self, name: N, type_obj: &Type) {
+        let mut name = name.into();
+
+        unsafe { BNAddTypeLibraryNamedObject(self.handle, &mut name.0, type_obj.handle) }
+    }
+
+    pub fn add_named_type<N: Into<QualifiedName>>(&self, name: N, type_obj: &Type) {
+        let mut name = name.into();
+
+        unsafe { BNAddTypeLibraryNamedType(self.handle, &mut name.0, type_obj.handle) }
+    }
+
+    pub fn get_named_object<N: Into<QualifiedName>>(&self, name: N) -> Option<Ref<Type>> {
+        let mut name = name.into();
+
+        unsafe {
+            let raw = BNGetTypeLibraryNamedObject(self.handle, &mut name.0);
+
+            if raw.is_null() {
+                None
+            } else {
+                Some(Type::ref_from_raw(raw))
+            }
+        }
+    }
+
+    pub fn get_named_type<N: Into<QualifiedName>>(&self, name: N) -> Option<Ref<Type>> {
+        let mut name = name.into();
+
+        unsafe {
+            let raw = BNGetTypeLibraryNamedType(self.handle, &mut name.0);
+
+            if raw.is_null() {
+                None
+            } else {
+                Some(Type::ref_from_raw(raw))
+            }
+        }
+    }
+
+    pub fn named_objects(&self) -> Array<QualifiedNameAndType> {
+        unsafe {
+            let mut count = 0;
+            let objects = BNGetTypeLibraryNamedObjects(self.handle, &mut count);
+
+            Array::new(objects, count, ())
+        }
+    }
+
+    pub fn named_types(&self) -> Array<QualifiedNameAndType> {
+        unsafe {
+            let mut count = 0;
+            let types = BNGetTypeLibraryNamedTypes(self.handle, &mut count);
+
+            Array::new(types, count, ())
+        }
+    }
+
+    /// Stores `value` under `key` in the library, replacing any existing value.
+    ///
+    /// Fails without storing anything if a string in `value` contains a nul byte.
+    pub fn store_metadata<S: BnStrCompatible, V: Into<Metadata>>(
+        &self,
+        key: S,
+        value: V,
+    ) -> Result<(), ()> {
+        let key = key.as_bytes_with_nul();
+        let value = value.into().to_raw()?;
+
+        unsafe {
+            BNTypeLibraryStoreMetadata(self.handle, key.as_ref().as_ptr() as *const _, value.0)
+        }
+
+        Ok(())
+    }
+
+    pub fn query_metadata<S: BnStrCompatible>(&self, key: S) -> Option<Metadata> {
+        let key = key.as_bytes_with_nul();
+
+        unsafe {
+            let raw = BNTypeLibraryQueryMetadata(self.handle, key.as_ref().as_ptr() as *const _);
+            CoreMetadata::from_raw(raw).and_then(|m| m.value())
+        }
+    }
+
+    pub fn remove_metadata<S: BnStrCompatible>(&self, key: S) {
+        let key = key.as_bytes_with_nul();
+
+        unsafe { BNTypeLibraryRemoveMetadata(self.handle, key.as_ref().as_ptr() as *const _) }
+    }
+}
+
+impl ToOwned for TypeLibrary {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for TypeLibrary {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewTypeLibraryReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeTypeLibrary(handle.handle);
+    }
+}
+
+unsafe impl CoreOwnedArrayProvider for TypeLibrary {
+    type Raw = *mut BNTypeLibrary;
+    type Context = ();
+
+    unsafe fn free(raw: *mut *mut BNTypeLibrary, count: usize, _context: &()) {
+        BNFreeTypeLibraryList(raw, count);
+    }
+}
+
+unsafe impl<'a> CoreOwnedArrayWrapper<'a> for TypeLibrary {
+    type Wrapped = Guard<'a, TypeLibrary>;
+
+    unsafe fn wrap_raw(raw: &'a *mut BNTypeLibrary, context: &'a ()) -> Guard<'a, TypeLibrary> {
+        debug_assert!(!raw.is_null());
+        Guard::new(TypeLibrary { handle: *raw }, context)
+    }
+}
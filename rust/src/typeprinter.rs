@@ -0,0 +1,279 @@
+// Copyright 2021 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Printing types as C declarations.
+//!
+//! ```ignore
+//! use binaryninja::typeprinter::TypePrinter;
+//!
+//! let header = TypePrinter::new().print_view(&bv);
+//! std::fs::write("types.h", header).unwrap();
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::binaryview::BinaryViewExt;
+use crate::disassembly::InstructionTextToken;
+use crate::platform::Platform;
+use crate::rc::*;
+use crate::types::{QualifiedName, QualifiedNameAndType, StructureType, Type, TypeClass};
+
+pub struct TypePrinter {
+    platform: Option<Ref<Platform>>,
+}
+
+impl TypePrinter {
+    pub fn new() -> Self {
+        Self { platform: None }
+    }
+
+    /// Uses `platform` to render calling conventions and platform specific types.
+    pub fn with_platform(platform: &Platform) -> Self {
+        Self {
+            platform: Some(platform.to_owned()),
+        }
+    }
+
+    /// Returns the declaration of `ty` as `name`, e.g. `struct foo { ... };` for structures or
+    /// a `typedef` for anything else.
+    pub fn print_type(&self, name: &QualifiedName, ty: &Type) -> String {
+        let mut out = String::new();
+        self.write_type(&mut out, &name.string(), ty);
+        out
+    }
+
+    /// Returns declarations for all of `types`, ordered so that every type is declared before
+    /// it's used. Structures and unions are forward declared so they can be referred to through
+    /// pointers before their definition.
+    pub fn print_types<'a, I>(&self, types: I) -> String
+    where
+        I: IntoIterator<Item = &'a QualifiedNameAndType>,
+    {
+        let entries: Vec<(String, Ref<Type>)> = types
+            .into_iter()
+            .map(|t| (t.name().string(), t.type_object().to_owned()))
+            .collect();
+
+        let index: HashMap<&str, usize> = entries
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| (name.as_str(), i))
+            .collect();
+
+        let mut out = String::new();
+
+        for (name, ty) in &entries {
+            if ty.type_class() == TypeClass::StructureTypeClass {
+                if let Ok(structure) = ty.get_structure() {
+                    let _ = writeln!(out, "{} {};", keyword(structure.structure_type()), name);
+                }
+            }
+        }
+
+        if !out.is_empty() {
+            out.push('\n');
+        }
+
+        let mut emitted = vec![false; entries.len()];
+        for i in 0..entries.len() {
+            self.emit(&mut out, i, &entries, &index, &mut emitted);
+        }
+
+        out
+    }
+
+    /// Returns declarations for all types defined in `view`, using the view's default platform
+    /// unless this printer was created with one.
+    pub fn print_view<V: BinaryViewExt>(&self, view: &V) -> String {
+        let types = view.types();
+
+        match (&self.platform, view.default_platform()) {
+            (None, Some(platform)) => TypePrinter::with_platform(&platform).print_types(&types),
+            _ => self.print_types(&types),
+        }
+    }
+
+    fn emit(
+        &self,
+        out: &mut String,
+        i: usize,
+        entries: &[(String, Ref<Type>)],
+        index: &HashMap<&str, usize>,
+        emitted: &mut [bool],
+    ) {
+        if emitted[i] {
+            return;
+        }
+
+        // marking before visiting dependencies breaks cycles, which can only be formed through
+        // pointers and are covered by the forward declarations
+        emitted[i] = true;
+
+        let (name, ty) = &entries[i];
+
+        let mut deps = Vec::new();
+        dependencies(ty, false, &mut deps);
+
+        for (dep, through_pointer) in deps {
+            if let Some(&j) = index.get(dep.as_str()) {
+                let forward_declared = entries[j].1.type_class() == TypeClass::StructureTypeClass;
+                if !(through_pointer && forward_declared) {
+                    self.emit(out, j, entries, index, emitted);
+                }
+            }
+        }
+
+        self.write_type(out, name, ty);
+        out.push('\n');
+    }
+
+    fn write_type(&self, out: &mut String, name: &str, ty: &Type) {
+        match ty.type_class() {
+            TypeClass::StructureTypeClass => {
+                if let Ok(structure) = ty.get_structure() {
+                    let packed = if structure.packed() {
+                        " __attribute__((packed))"
+                    } else {
+                        ""
+                    };
+
+                    let _ = writeln!(
+                        out,
+                        "{}{} {}\n{{",
+                        keyword(structure.structure_type()),
+                        packed,
+                        name
+                    );
+
+                    let is_union = structure.is_union();
+                    let mut cursor = 0;
+
+                    for member in structure.members() {
+                        if !is_union && member.offset > cursor {
+                            let _ = writeln!(
+                                out,
+                                "    uint8_t __padding_{:x}[{:#x}];",
+                                cursor,
+                                member.offset - cursor
+                            );
+                        }
+
+                        let _ = writeln!(
+                            out,
+                            "    {};",
+                            self.declarator(&member.ty.contents, member.name.as_str())
+                        );
+
+                        cursor = cursor.max(member.offset + member.ty.contents.width());
+                    }
+
+                    if !is_union && structure.width() > cursor {
+                        let _ = writeln!(
+                            out,
+                            "    uint8_t __padding_{:x}[{:#x}];",
+                            cursor,
+                            structure.width() - cursor
+                        );
+                    }
+
+                    out.push_str("};\n");
+                }
+            }
+            TypeClass::EnumerationTypeClass => {
+                if let Ok(enumeration) = ty.get_enumeration() {
+                    let _ = writeln!(out, "enum {}\n{{", name);
+
+                    for member in enumeration.members() {
+                        let _ = writeln!(out, "    {} = {:#x},", member.name, member.value);
+                    }
+
+                    out.push_str("};\n");
+                }
+            }
+            _ => {
+                let _ = writeln!(out, "typedef {};", self.declarator(ty, name));
+            }
+        }
+    }
+
+    fn declarator(&self, ty: &Type, name: &str) -> String {
+        let platform = self.platform.as_deref();
+
+        let before = tokens_to_string(&ty.tokens_before_name(platform));
+        let after = tokens_to_string(&ty.tokens_after_name(platform));
+
+        if before.is_empty() || before.ends_with('(') || before.ends_with(' ') {
+            format!("{}{}{}", before, name, after)
+        } else {
+            format!("{} {}{}", before, name, after)
+        }
+    }
+}
+
+impl Default for TypePrinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn keyword(structure_type: StructureType) -> &'static str {
+    match structure_type {
+        StructureType::UnionStructureType => "union",
+        _ => "struct",
+    }
+}
+
+fn tokens_to_string(tokens: &Array<InstructionTextToken>) -> String {
+    tokens.iter().map(|t| t.text().as_str()).collect()
+}
+
+/// Collects the names of the named types `ty` refers to, and whether each one is only referred
+/// to through a pointer or function type.
+fn dependencies(ty: &Type, through_pointer: bool, deps: &mut Vec<(String, bool)>) {
+    match ty.type_class() {
+        TypeClass::NamedTypeReferenceClass => {
+            if let Ok(ntr) = ty.get_named_type_reference() {
+                deps.push((ntr.name().string(), through_pointer));
+            }
+        }
+        TypeClass::StructureTypeClass => {
+            if let Ok(structure) = ty.get_structure() {
+                for member in structure.members() {
+                    dependencies(&member.ty.contents, through_pointer, deps);
+                }
+            }
+        }
+        TypeClass::ArrayTypeClass => {
+            if let Ok(element) = ty.element_type() {
+                dependencies(&element.contents, through_pointer, deps);
+            }
+        }
+        TypeClass::PointerTypeClass => {
+            if let Ok(target) = ty.target() {
+                dependencies(&target.contents, true, deps);
+            }
+        }
+        TypeClass::FunctionTypeClass => {
+            if let Ok(return_type) = ty.return_value() {
+                dependencies(&return_type.contents, true, deps);
+            }
+
+            for parameter in ty.parameters() {
+                dependencies(&parameter.t.contents, true, deps);
+            }
+        }
+        _ => {}
+    }
+}
@@ -21,6 +21,8 @@ use std::{fmt, mem, ptr, result, slice};
 
 use crate::architecture::{Architecture, CoreArchitecture};
 use crate::callingconvention::CallingConvention;
+use crate::disassembly::InstructionTextToken;
+use crate::platform::Platform;
 use crate::string::{raw_to_string, BnStr, BnStrCompatible, BnString};
 
 use crate::rc::*;
@@ -193,6 +195,12 @@ impl TypeBuilder {
         self
     }
 
+    pub fn set_can_return<'a, T: Into<Conf<bool>>>(&'a mut self, value: T) -> &'a mut Self {
+        let mut bool_with_confidence = value.into().into();
+        unsafe { BNSetFunctionTypeBuilderCanReturn(self.handle, &mut bool_with_confidence) };
+        self
+    }
+
     // Chainable terminal
 
     pub fn finalize(&self) -> Ref<Type> {
@@ -265,12 +273,22 @@ impl TypeBuilder {
         }
     }
 
-    // pub fn parameters(&self) -> ? {}
+    pub fn parameters(&self) -> Vec<FunctionParameter<BnString>> {
+        unsafe {
+            let mut count: usize = mem::zeroed();
+            let parameters_raw = BNGetTypeBuilderParameters(self.handle, &mut count);
+            FunctionParameter::from_raw_list(parameters_raw, count)
+        }
+    }
 
     pub fn has_variable_arguments(&self) -> Conf<bool> {
         unsafe { BNTypeBuilderHasVariableArguments(self.handle).into() }
     }
 
+    pub fn can_return(&self) -> Conf<bool> {
+        unsafe { BNFunctionTypeBuilderCanReturn(self.handle).into() }
+    }
+
     pub fn get_structure(&self) -> Result<Ref<Structure>> {
         let result = unsafe { BNGetTypeBuilderStructure(self.handle) };
         if result.is_null() {
@@ -310,8 +328,52 @@ impl TypeBuilder {
         unsafe { BNGetTypeBuilderStackAdjustment(self.handle).into() }
     }
 
-    // TODO : This and properties
-    // pub fn tokens(&self) -> ? {}
+    pub fn tokens(&self, platform: Option<&Platform>) -> Array<InstructionTextToken> {
+        let platform = platform.map_or(ptr::null_mut(), |p| p.handle);
+
+        unsafe {
+            let mut count = 0;
+            let tokens =
+                BNGetTypeBuilderTokens(self.handle, platform, max_confidence(), &mut count);
+
+            Array::new(tokens, count, ())
+        }
+    }
+
+    /// Tokens that come before the name in a declaration of this type.
+    pub fn tokens_before_name(&self, platform: Option<&Platform>) -> Array<InstructionTextToken> {
+        let platform = platform.map_or(ptr::null_mut(), |p| p.handle);
+
+        unsafe {
+            let mut count = 0;
+            let tokens = BNGetTypeBuilderTokensBeforeName(
+                self.handle,
+                platform,
+                max_confidence(),
+                &mut count,
+            );
+
+            Array::new(tokens, count, ())
+        }
+    }
+
+    /// Tokens that come after the name in a declaration of this type, e.g. array bounds or
+    /// function parameters.
+    pub fn tokens_after_name(&self, platform: Option<&Platform>) -> Array<InstructionTextToken> {
+        let platform = platform.map_or(ptr::null_mut(), |p| p.handle);
+
+        unsafe {
+            let mut count = 0;
+            let tokens = BNGetTypeBuilderTokensAfterName(
+                self.handle,
+                platform,
+                max_confidence(),
+                &mut count,
+            );
+
+            Array::new(tokens, count, ())
+        }
+    }
 
     pub fn void() -> Self {
         unsafe { Self::from_raw(BNCreateVoidTypeBuilder()) }
@@ -608,8 +670,13 @@ impl Type {
         }
     }
 
-    // TODO : This
-    // pub fn parameters(&self) -> ? {}
+    pub fn parameters(&self) -> Vec<FunctionParameter<BnString>> {
+        unsafe {
+            let mut count: usize = mem::zeroed();
+            let parameters_raw = BNGetTypeParameters(self.handle, &mut count);
+            FunctionParameter::from_raw_list(parameters_raw, count)
+        }
+    }
 
     pub fn has_variable_arguments(&self) -> Conf<bool> {
         unsafe { BNTypeHasVariableArguments(self.handle).into() }
@@ -667,8 +734,43 @@ impl Type {
         }
     }
 
-    // TODO : This and properties
-    // pub fn tokens(&self) -> ? {}
+    pub fn tokens(&self, platform: Option<&Platform>) -> Array<InstructionTextToken> {
+        let platform = platform.map_or(ptr::null_mut(), |p| p.handle);
+
+        unsafe {
+            let mut count = 0;
+            let tokens = BNGetTypeTokens(self.handle, platform, max_confidence(), &mut count);
+
+            Array::new(tokens, count, ())
+        }
+    }
+
+    /// Tokens that come before the name in a declaration of this type.
+    pub fn tokens_before_name(&self, platform: Option<&Platform>) -> Array<InstructionTextToken> {
+        let platform = platform.map_or(ptr::null_mut(), |p| p.handle);
+
+        unsafe {
+            let mut count = 0;
+            let tokens =
+                BNGetTypeTokensBeforeName(self.handle, platform, max_confidence(), &mut count);
+
+            Array::new(tokens, count, ())
+        }
+    }
+
+    /// Tokens that come after the name in a declaration of this type, e.g. array bounds or
+    /// function parameters.
+    pub fn tokens_after_name(&self, platform: Option<&Platform>) -> Array<InstructionTextToken> {
+        let platform = platform.map_or(ptr::null_mut(), |p| p.handle);
+
+        unsafe {
+            let mut count = 0;
+            let tokens =
+                BNGetTypeTokensAfterName(self.handle, platform, max_confidence(), &mut count);
+
+            Array::new(tokens, count, ())
+        }
+    }
 
     pub fn void() -> Ref<Self> {
         unsafe { Self::ref_from_raw(BNCreateVoidType()) }
@@ -934,6 +1036,23 @@ impl Type {
         }
     }
 
+    /// Generates a type id for use with `BinaryViewExt::define_auto_type`, unique to `source`
+    /// (e.g. the name of the loader defining the type) and `name`.
+    pub fn generate_auto_type_id<S: BnStrCompatible, N: Into<QualifiedName>>(
+        source: S,
+        name: N,
+    ) -> BnString {
+        let source = source.as_bytes_with_nul();
+        let mut name = name.into();
+
+        unsafe {
+            BnString::from_raw(BNGenerateAutoTypeId(
+                source.as_ref().as_ptr() as *const _,
+                &mut name.0,
+            ))
+        }
+    }
+
     pub fn generate_auto_demangled_type_id<'a, S: BnStrCompatible>(name: S) -> &'a BnStr {
         let mut name = QualifiedName::from(name);
         unsafe { BnStr::from_raw(BNGenerateAutoDemangledTypeId(&mut name.0)) }
@@ -994,9 +1113,255 @@ impl<'a, S: BnStrCompatible> FunctionParameter<S> {
     }
 }
 
+impl FunctionParameter<BnString> {
+    pub(crate) unsafe fn from_raw(raw: &BNFunctionParameter) -> Self {
+        Self {
+            t: Conf::new(Type { handle: raw.type_ }.to_owned(), raw.typeConfidence),
+            name: BnString::new(BnStr::from_raw(raw.name)),
+            location: if raw.defaultLocation {
+                None
+            } else {
+                Some(Variable::from_raw(raw.location))
+            },
+        }
+    }
+
+    /// Converts and frees a parameter list returned by the core.
+    unsafe fn from_raw_list(parameters_raw: *mut BNFunctionParameter, count: usize) -> Vec<Self> {
+        let result = slice::from_raw_parts(parameters_raw, count)
+            .iter()
+            .map(|parameter| Self::from_raw(parameter))
+            .collect();
+
+        BNFreeTypeParameterList(parameters_raw, count);
+
+        result
+    }
+}
+
+/////////////////////////
+// FunctionTypeBuilder
+
+/// Builds function types, either from scratch or by editing an existing prototype.
+///
+/// Unlike `TypeBuilder` this keeps every property on the Rust side until `finalize` is called,
+/// since the core can't change the calling convention, variadic flag or stack adjustment of a
+/// type builder after it has been created.
+pub struct FunctionTypeBuilder {
+    return_type: Conf<Ref<Type>>,
+    calling_convention: Option<Conf<Ref<CallingConvention<CoreArchitecture>>>>,
+    parameters: Vec<FunctionParameter<BnString>>,
+    variable_arguments: Conf<bool>,
+    can_return: Conf<bool>,
+    stack_adjust: Conf<i64>,
+}
+
+impl FunctionTypeBuilder {
+    pub fn new<'a, T: Into<Conf<&'a Type>>>(return_type: T) -> Self {
+        let return_type = return_type.into();
+
+        Self {
+            return_type: Conf::new(return_type.contents.to_owned(), return_type.confidence),
+            calling_convention: None,
+            parameters: Vec::new(),
+            variable_arguments: Conf::new(false, max_confidence()),
+            can_return: Conf::new(true, min_confidence()),
+            stack_adjust: Conf::new(0, min_confidence()),
+        }
+    }
+
+    /// Starts from the prototype of an existing function type.
+    pub fn from_type(t: &Type) -> Result<Self> {
+        if t.type_class() != TypeClass::FunctionTypeClass {
+            return Err(());
+        }
+
+        Ok(Self {
+            return_type: t.return_value()?,
+            calling_convention: t.calling_convention().ok(),
+            parameters: t.parameters(),
+            variable_arguments: t.has_variable_arguments(),
+            can_return: t.can_return(),
+            stack_adjust: t.stack_adjustment(),
+        })
+    }
+
+    // Chainable terminal
+
+    pub fn finalize(&self) -> Ref<Type> {
+        let mut return_type = BNTypeWithConfidence {
+            type_: self.return_type.contents.handle,
+            confidence: self.return_type.confidence,
+        };
+        let mut calling_convention = match &self.calling_convention {
+            Some(cc) => BNCallingConventionWithConfidence {
+                convention: cc.contents.handle,
+                confidence: cc.confidence,
+            },
+            None => BNCallingConventionWithConfidence {
+                convention: ptr::null_mut(),
+                confidence: min_confidence(),
+            },
+        };
+        let mut variable_arguments = self.variable_arguments.clone().into();
+        let mut can_return = self.can_return.clone().into();
+        let mut stack_adjust = self.stack_adjust.clone().into();
+
+        let mut raw_parameters: Vec<BNFunctionParameter> = self
+            .parameters
+            .iter()
+            .map(|parameter| BNFunctionParameter {
+                name: parameter.name.as_cstr().as_ptr() as *mut _,
+                type_: parameter.t.contents.handle,
+                typeConfidence: parameter.t.confidence,
+                defaultLocation: parameter.location.is_none(),
+                location: match &parameter.location {
+                    Some(location) => location.into_raw(),
+                    None => unsafe { mem::zeroed() },
+                },
+            })
+            .collect();
+
+        unsafe {
+            let builder = TypeBuilder::from_raw(BNCreateFunctionTypeBuilder(
+                &mut return_type,
+                &mut calling_convention,
+                raw_parameters.as_mut_ptr(),
+                raw_parameters.len(),
+                &mut variable_arguments,
+                &mut stack_adjust,
+            ));
+            BNSetFunctionTypeBuilderCanReturn(builder.handle, &mut can_return);
+
+            builder.finalize()
+        }
+    }
+
+    // Chainable builders/setters
+
+    pub fn set_return_type<'a, 'b, T: Into<Conf<&'b Type>>>(
+        &'a mut self,
+        return_type: T,
+    ) -> &'a mut Self {
+        let return_type = return_type.into();
+        self.return_type = Conf::new(return_type.contents.to_owned(), return_type.confidence);
+        self
+    }
+
+    pub fn set_calling_convention<'a, A: Architecture>(
+        &'a mut self,
+        calling_convention: Conf<&CallingConvention<A>>,
+    ) -> &'a mut Self {
+        let cc = unsafe {
+            let handle = calling_convention.contents.handle;
+            CallingConvention::ref_from_raw(
+                BNNewCallingConventionReference(handle),
+                CoreArchitecture::from_raw(BNGetCallingConventionArchitecture(handle)),
+            )
+        };
+
+        self.calling_convention = Some(Conf::new(cc, calling_convention.confidence));
+        self
+    }
+
+    /// Removes the calling convention so analysis picks one.
+    pub fn clear_calling_convention<'a>(&'a mut self) -> &'a mut Self {
+        self.calling_convention = None;
+        self
+    }
+
+    pub fn set_parameters<'a, S: BnStrCompatible, I: IntoIterator<Item = FunctionParameter<S>>>(
+        &'a mut self,
+        parameters: I,
+    ) -> &'a mut Self {
+        self.parameters = parameters.into_iter().map(Self::owned_parameter).collect();
+        self
+    }
+
+    pub fn append_parameter<'a, S: BnStrCompatible>(
+        &'a mut self,
+        parameter: FunctionParameter<S>,
+    ) -> &'a mut Self {
+        self.parameters.push(Self::owned_parameter(parameter));
+        self
+    }
+
+    /// Replaces the parameter at `idx`.
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn set_parameter<'a, S: BnStrCompatible>(
+        &'a mut self,
+        idx: usize,
+        parameter: FunctionParameter<S>,
+    ) -> &'a mut Self {
+        self.parameters[idx] = Self::owned_parameter(parameter);
+        self
+    }
+
+    /// Removes the parameter at `idx`.
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn remove_parameter<'a>(&'a mut self, idx: usize) -> &'a mut Self {
+        self.parameters.remove(idx);
+        self
+    }
+
+    pub fn set_variable_arguments<'a, T: Into<Conf<bool>>>(&'a mut self, value: T) -> &'a mut Self {
+        self.variable_arguments = value.into();
+        self
+    }
+
+    pub fn set_can_return<'a, T: Into<Conf<bool>>>(&'a mut self, value: T) -> &'a mut Self {
+        self.can_return = value.into();
+        self
+    }
+
+    pub fn set_stack_adjustment<'a, T: Into<Conf<i64>>>(&'a mut self, value: T) -> &'a mut Self {
+        self.stack_adjust = value.into();
+        self
+    }
+
+    // Getters
+
+    pub fn return_type(&self) -> &Conf<Ref<Type>> {
+        &self.return_type
+    }
+
+    pub fn calling_convention(&self) -> Option<&Conf<Ref<CallingConvention<CoreArchitecture>>>> {
+        self.calling_convention.as_ref()
+    }
+
+    pub fn parameters(&self) -> &[FunctionParameter<BnString>] {
+        &self.parameters
+    }
+
+    pub fn has_variable_arguments(&self) -> Conf<bool> {
+        self.variable_arguments.clone()
+    }
+
+    pub fn can_return(&self) -> Conf<bool> {
+        self.can_return.clone()
+    }
+
+    pub fn stack_adjustment(&self) -> Conf<i64> {
+        self.stack_adjust.clone()
+    }
+
+    fn owned_parameter<S: BnStrCompatible>(
+        parameter: FunctionParameter<S>,
+    ) -> FunctionParameter<BnString> {
+        FunctionParameter {
+            t: parameter.t,
+            name: BnString::new(parameter.name),
+            location: parameter.location,
+        }
+    }
+}
+
 //////////////
 // Variable
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Variable {
     pub t: BNVariableSourceType,
     pub index: u32,
@@ -1008,13 +1373,17 @@ impl Variable {
         Self { t, index, storage }
     }
 
-    // pub(crate) unsafe fn from_raw(var: *mut BNVariable) -> Self {
-    //     Self {
-    //         t: (*var).type_,
-    //         index: (*var).index,
-    //         storage: (*var).storage,
-    //     }
-    // }
+    pub(crate) fn from_raw(var: BNVariable) -> Self {
+        Self {
+            t: var.type_,
+            index: var.index,
+            storage: var.storage,
+        }
+    }
+
+    pub(crate) fn from_identifier(id: u64) -> Self {
+        unsafe { Self::from_raw(BNFromVariableIdentifier(id)) }
+    }
 
     pub(crate) fn into_raw(&self) -> BNVariable {
         BNVariable {
@@ -1025,6 +1394,142 @@ impl Variable {
     }
 }
 
+///////////////////
+// RegisterValue
+
+/// The value dataflow analysis determined for a register, flag or expression
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RegisterValue {
+    Undetermined,
+    /// The value the given register held on entry to the function
+    EntryValue(u32),
+    Constant(i64),
+    ConstantPointer(i64),
+    /// An external pointer and the offset from it
+    ExternalPointer(i64, i64),
+    StackFrameOffset(i64),
+    ReturnAddress,
+    ImportedAddress(i64),
+}
+
+impl RegisterValue {
+    pub(crate) fn from_raw(value: BNRegisterValue) -> Self {
+        use self::BNRegisterValueType::*;
+
+        match value.state {
+            EntryValue => RegisterValue::EntryValue(value.value as u32),
+            ConstantValue => RegisterValue::Constant(value.value),
+            ConstantPointerValue => RegisterValue::ConstantPointer(value.value),
+            ExternalPointerValue => RegisterValue::ExternalPointer(value.value, value.offset),
+            StackFrameOffset => RegisterValue::StackFrameOffset(value.value),
+            ReturnAddressValue => RegisterValue::ReturnAddress,
+            ImportedAddressValue => RegisterValue::ImportedAddress(value.value),
+            _ => RegisterValue::Undetermined,
+        }
+    }
+
+    /// Returns the value if it is a constant or constant pointer
+    pub fn as_constant(&self) -> Option<i64> {
+        match *self {
+            RegisterValue::Constant(value) | RegisterValue::ConstantPointer(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+//////////////////////
+// PossibleValueSet
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ValueRange<T> {
+    pub start: T,
+    pub end: T,
+    pub step: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LookupTableEntry {
+    pub from_values: Vec<i64>,
+    pub to_value: i64,
+}
+
+/// The set of values dataflow analysis determined an expression may take
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PossibleValueSet {
+    Undetermined,
+    EntryValue(u32),
+    Constant(i64),
+    ConstantPointer(i64),
+    ExternalPointer(i64, i64),
+    StackFrameOffset(i64),
+    ReturnAddress,
+    ImportedAddress(i64),
+    SignedRange(Vec<ValueRange<i64>>),
+    UnsignedRange(Vec<ValueRange<u64>>),
+    /// The result of indexing a table with a value from the entry's inputs
+    LookupTable(Vec<LookupTableEntry>),
+    InSet(Vec<i64>),
+    NotInSet(Vec<i64>),
+}
+
+impl PossibleValueSet {
+    /// Copies `value` into an owned set, then frees it
+    pub(crate) unsafe fn from_raw(mut value: BNPossibleValueSet) -> Self {
+        use self::BNRegisterValueType::*;
+
+        let result = match value.state {
+            EntryValue => PossibleValueSet::EntryValue(value.value as u32),
+            ConstantValue => PossibleValueSet::Constant(value.value),
+            ConstantPointerValue => PossibleValueSet::ConstantPointer(value.value),
+            ExternalPointerValue => PossibleValueSet::ExternalPointer(value.value, value.offset),
+            StackFrameOffset => PossibleValueSet::StackFrameOffset(value.value),
+            ReturnAddressValue => PossibleValueSet::ReturnAddress,
+            ImportedAddressValue => PossibleValueSet::ImportedAddress(value.value),
+            SignedRangeValue => PossibleValueSet::SignedRange(
+                slice::from_raw_parts(value.ranges, value.count)
+                    .iter()
+                    .map(|r| ValueRange {
+                        start: r.start as i64,
+                        end: r.end as i64,
+                        step: r.step,
+                    })
+                    .collect(),
+            ),
+            UnsignedRangeValue => PossibleValueSet::UnsignedRange(
+                slice::from_raw_parts(value.ranges, value.count)
+                    .iter()
+                    .map(|r| ValueRange {
+                        start: r.start,
+                        end: r.end,
+                        step: r.step,
+                    })
+                    .collect(),
+            ),
+            LookupTableValue => PossibleValueSet::LookupTable(
+                slice::from_raw_parts(value.table, value.count)
+                    .iter()
+                    .map(|entry| LookupTableEntry {
+                        from_values: slice::from_raw_parts(entry.fromValues, entry.fromCount)
+                            .to_vec(),
+                        to_value: entry.toValue,
+                    })
+                    .collect(),
+            ),
+            InSetOfValues => PossibleValueSet::InSet(
+                slice::from_raw_parts(value.valueSet, value.count).to_vec(),
+            ),
+            NotInSetOfValues => PossibleValueSet::NotInSet(
+                slice::from_raw_parts(value.valueSet, value.count).to_vec(),
+            ),
+            UndeterminedValue => PossibleValueSet::Undetermined,
+        };
+
+        BNFreePossibleValueSet(&mut value);
+
+        result
+    }
+}
+
 ////////////////////////
 // EnumerationBuilder
 
@@ -1250,6 +1755,14 @@ impl StructureBuilder {
         }
     }
 
+    /// Creates an empty builder for a structure of type `structure_type`, which can be used to
+    /// build unions.
+    pub fn with_options(structure_type: StructureType, packed: bool) -> Self {
+        Self {
+            handle: unsafe { BNCreateStructureBuilderWithOptions(structure_type, packed) },
+        }
+    }
+
     pub(crate) unsafe fn from_raw(handle: *mut BNStructureBuilder) -> Self {
         debug_assert!(!handle.is_null());
         Self { handle }
@@ -1291,6 +1804,7 @@ impl StructureBuilder {
         self
     }
 
+    /// Same as `add_member_at_offset`.
     pub fn insert<'a, 'b, S: BnStrCompatible, T: Into<Conf<&'b Type>>>(
         &'a mut self,
         t: T,
@@ -1299,6 +1813,22 @@ impl StructureBuilder {
         overwrite_existing: bool,
         access: MemberAccess,
         scope: MemberScope,
+    ) -> &'a mut Self {
+        self.add_member_at_offset(t, name, offset, overwrite_existing, access, scope)
+    }
+
+    /// Adds a member at `offset`.
+    ///
+    /// With `overwrite_existing` set, any members overlapping the new one are removed. Union
+    /// members all live at offset 0, so leave it unset when building unions.
+    pub fn add_member_at_offset<'a, 'b, S: BnStrCompatible, T: Into<Conf<&'b Type>>>(
+        &'a mut self,
+        t: T,
+        name: S,
+        offset: u64,
+        overwrite_existing: bool,
+        access: MemberAccess,
+        scope: MemberScope,
     ) -> &'a mut Self {
         let name = name.as_bytes_with_nul();
         unsafe {
@@ -1316,22 +1846,97 @@ impl StructureBuilder {
         self
     }
 
+    /// Removes the member at index `idx` of `members()`.
+    pub fn remove<'a>(&'a mut self, idx: usize) -> &'a mut Self {
+        unsafe { BNRemoveStructureBuilderMember(self.handle, idx) };
+        self
+    }
+
+    /// Replaces the member at index `idx` of `members()`, keeping its offset.
+    pub fn replace<'a, 'b, S: BnStrCompatible, T: Into<Conf<&'b Type>>>(
+        &'a mut self,
+        idx: usize,
+        t: T,
+        name: S,
+        overwrite_existing: bool,
+    ) -> &'a mut Self {
+        let name = name.as_bytes_with_nul();
+        unsafe {
+            BNReplaceStructureBuilderMember(
+                self.handle,
+                idx,
+                &t.into().into(),
+                name.as_ref().as_ptr() as _,
+                overwrite_existing,
+            );
+        }
+
+        self
+    }
+
     pub fn set_structure_type<'a>(&'a mut self, t: StructureType) -> &'a Self {
         unsafe { BNSetStructureBuilderType(self.handle, t) };
         self
     }
 
+    pub fn set_alignment<'a>(&'a mut self, alignment: usize) -> &'a mut Self {
+        unsafe { BNSetStructureBuilderAlignment(self.handle, alignment) };
+        self
+    }
+
+    pub fn set_packed<'a>(&'a mut self, packed: bool) -> &'a mut Self {
+        unsafe { BNSetStructureBuilderPacked(self.handle, packed) };
+        self
+    }
+
     // Getters
 
     pub fn width(&self) -> u64 {
         unsafe { BNGetStructureBuilderWidth(self.handle) }
     }
 
+    pub fn alignment(&self) -> usize {
+        unsafe { BNGetStructureBuilderAlignment(self.handle) }
+    }
+
+    pub fn packed(&self) -> bool {
+        unsafe { BNIsStructureBuilderPacked(self.handle) }
+    }
+
+    pub fn is_union(&self) -> bool {
+        unsafe { BNIsStructureBuilderUnion(self.handle) }
+    }
+
     pub fn structure_type(&self) -> StructureType {
         unsafe { BNGetStructureBuilderType(self.handle) }
     }
 
-    // TODO : The other methods in the python version (alignment, packed, type, members, remove, replace, etc)
+    pub fn members(&self) -> Vec<StructureMember> {
+        unsafe {
+            let mut count: usize = mem::zeroed();
+            let members_raw = BNGetStructureBuilderMembers(self.handle, &mut count);
+            StructureMember::from_raw_list(members_raw, count)
+        }
+    }
+
+    pub fn member_by_name<S: BnStrCompatible>(&self, name: S) -> Option<StructureMember> {
+        let name = name.as_bytes_with_nul();
+        unsafe {
+            StructureMember::from_owned_raw(BNGetStructureBuilderMemberByName(
+                self.handle,
+                name.as_ref().as_ptr() as _,
+            ))
+        }
+    }
+
+    /// Returns the member covering `offset` along with its index in `members()`.
+    pub fn member_at_offset(&self, offset: i64) -> Option<(usize, StructureMember)> {
+        unsafe {
+            let mut idx: usize = 0;
+            let member = BNGetStructureBuilderMemberAtOffset(self.handle, offset, &mut idx);
+            StructureMember::from_owned_raw(member).map(|member| (idx, member))
+        }
+    }
 }
 
 impl From<&Structure> for StructureBuilder {
@@ -1405,7 +2010,44 @@ impl Structure {
         unsafe { BNGetStructureType(self.handle) }
     }
 
-    // TODO : The other methods in the python version (alignment, packed, type, members, remove, replace, etc)
+    pub fn alignment(&self) -> usize {
+        unsafe { BNGetStructureAlignment(self.handle) }
+    }
+
+    pub fn packed(&self) -> bool {
+        unsafe { BNIsStructurePacked(self.handle) }
+    }
+
+    pub fn is_union(&self) -> bool {
+        unsafe { BNIsStructureUnion(self.handle) }
+    }
+
+    pub fn members(&self) -> Vec<StructureMember> {
+        unsafe {
+            let mut count: usize = mem::zeroed();
+            let members_raw = BNGetStructureMembers(self.handle, &mut count);
+            StructureMember::from_raw_list(members_raw, count)
+        }
+    }
+
+    pub fn member_by_name<S: BnStrCompatible>(&self, name: S) -> Option<StructureMember> {
+        let name = name.as_bytes_with_nul();
+        unsafe {
+            StructureMember::from_owned_raw(BNGetStructureMemberByName(
+                self.handle,
+                name.as_ref().as_ptr() as _,
+            ))
+        }
+    }
+
+    /// Returns the member covering `offset` along with its index in `members()`.
+    pub fn member_at_offset(&self, offset: i64) -> Option<(usize, StructureMember)> {
+        unsafe {
+            let mut idx: usize = 0;
+            let member = BNGetStructureMemberAtOffset(self.handle, offset, &mut idx);
+            StructureMember::from_owned_raw(member).map(|member| (idx, member))
+        }
+    }
 }
 
 impl From<&StructureBuilder> for Ref<Structure> {
@@ -1432,15 +2074,55 @@ impl ToOwned for Structure {
     }
 }
 
-// TODO : Use
-// #[derive(PartialEq, Eq, Hash)]
-// pub struct StructureMember {
-//     pub(crate) handle: *mut BNStructureMember,
-// }
+pub struct StructureMember {
+    pub ty: Conf<Ref<Type>>,
+    pub name: BnString,
+    pub offset: u64,
+    pub access: MemberAccess,
+    pub scope: MemberScope,
+}
 
-// impl StructureMember {
-//     // pub fn new() -> Self {}
-// }
+impl StructureMember {
+    pub(crate) unsafe fn from_raw(handle: &BNStructureMember) -> Self {
+        Self {
+            ty: Conf::new(
+                Type {
+                    handle: handle.type_,
+                }
+                .to_owned(),
+                handle.typeConfidence,
+            ),
+            name: BnString::new(BnStr::from_raw(handle.name)),
+            offset: handle.offset,
+            access: handle.access,
+            scope: handle.scope,
+        }
+    }
+
+    /// Converts and frees a single member returned by the core, which may be null.
+    unsafe fn from_owned_raw(handle: *mut BNStructureMember) -> Option<Self> {
+        if handle.is_null() {
+            return None;
+        }
+
+        let result = Self::from_raw(&*handle);
+        BNFreeStructureMember(handle);
+
+        Some(result)
+    }
+
+    /// Converts and frees a member list returned by the core.
+    unsafe fn from_raw_list(members_raw: *mut BNStructureMember, count: usize) -> Vec<Self> {
+        let result = slice::from_raw_parts(members_raw, count)
+            .iter()
+            .map(|member| Self::from_raw(member))
+            .collect();
+
+        BNFreeStructureMemberList(members_raw, count);
+
+        result
+    }
+}
 
 ////////////////////////
 // NamedTypeReference
@@ -1470,6 +2152,14 @@ impl NamedTypeReference {
             },
         }
     }
+
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTypeReferenceId(self.handle)) }
+    }
+
+    pub fn name(&self) -> QualifiedName {
+        QualifiedName(unsafe { BNGetTypeReferenceName(self.handle) })
+    }
 }
 
 ///////////////////
@@ -1507,6 +2197,18 @@ impl<S: BnStrCompatible> From<S> for QualifiedName {
     }
 }
 
+impl Clone for QualifiedName {
+    fn clone(&self) -> Self {
+        unsafe {
+            QualifiedName(BNQualifiedName {
+                name: BNAllocStringList(self.0.name as *mut *const _, self.0.nameCount),
+                join: BNAllocString(self.0.join),
+                nameCount: self.0.nameCount,
+            })
+        }
+    }
+}
+
 impl Drop for QualifiedName {
     fn drop(&mut self) {
         unsafe {
@@ -1568,7 +2270,7 @@ impl NameAndType<String> {
     pub(crate) fn from_raw(raw: &BNNameAndType) -> Self {
         Self::new(
             raw_to_string(raw.name).unwrap(),
-            unsafe { &Type::ref_from_raw(raw.type_) },
+            &Type { handle: raw.type_ }.to_owned(),
             raw.typeConfidence,
         )
     }
@@ -1607,44 +2309,10 @@ unsafe impl<'a, S: 'a + BnStrCompatible> CoreOwnedArrayWrapper<'a> for NameAndTy
 //////////////////
 // DataVariable
 
-pub struct DataVariable {
-    pub address: u64,
-    pub t: Conf<Ref<Type>>,
-    pub auto_discovered: bool,
-}
-
-// impl DataVariable {
-//     pub(crate) fn from_raw(var: &BNDataVariable) -> Self {
-//         Self {
-//             address: var.address,
-//             t: Conf::new(unsafe { Type::ref_from_raw(var.type_) }, var.typeConfidence),
-//             auto_discovered: var.autoDiscovered,
-//         }
-//     }
-// }
-
-impl DataVariable {
-    pub fn type_with_confidence(&self) -> Conf<Ref<Type>> {
-        Conf::new(self.t.contents.clone(), self.t.confidence)
-    }
-}
-
-unsafe impl CoreOwnedArrayProvider for DataVariable {
-    type Raw = BNDataVariable;
-    type Context = ();
-
-    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
-        BNFreeDataVariables(raw, count);
-    }
-}
-
-unsafe impl<'a> CoreOwnedArrayWrapper<'a> for DataVariable {
-    type Wrapped = &'a DataVariable;
-
-    unsafe fn wrap_raw(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped {
-        mem::transmute(raw)
-    }
-}
+// moved to `datavariable` and re-exported so the old path still resolves; note this is a breaking
+// change, as the public `address`, `t` and `auto_discovered` fields are now the `address()`,
+// `type_with_confidence()` and `auto_discovered()` accessors
+pub use crate::datavariable::DataVariable;
 
 /////////////////////////
 // DataVariableAndName
@@ -1689,3 +2357,116 @@ impl<S: BnStrCompatible> DataVariableAndName<S> {
 //         mem::transmute(raw)
 //     }
 // }
+
+////////////
+// BnType
+
+#[cfg(feature = "derive")]
+pub use binaryninja_derive::BnType;
+
+/// Rust types that have an equivalent Binary Ninja type.
+///
+/// This is implemented for primitives, arrays and raw pointers. With the `derive` feature
+/// enabled, `#[derive(BnType)]` implements it for `#[repr(C)]` structs and fieldless enums with
+/// a `#[repr(C)]` or `#[repr(<integer>)]`:
+///
+/// ```ignore
+/// use binaryninja::types::BnType;
+///
+/// #[derive(BnType)]
+/// #[repr(C)]
+/// struct TaskHeader {
+///     id: u32,
+///     name: [u8; 16],
+///     next: *const TaskHeader,
+/// }
+///
+/// bv.define_user_type("TaskHeader", &TaskHeader::bn_type(&arch));
+/// ```
+pub trait BnType {
+    /// Returns the type of `Self`, using `arch` for the width of pointers.
+    fn bn_type<A: Architecture>(arch: &A) -> Ref<Type>;
+
+    /// Returns the type to use when `Self` is the target of a pointer.
+    ///
+    /// Derived structures return a reference to the named type here instead of the full
+    /// definition, so that self-referential structures terminate.
+    fn bn_pointee_type<A: Architecture>(arch: &A) -> Ref<Type> {
+        Self::bn_type(arch)
+    }
+}
+
+macro_rules! bn_type_int {
+    ($($t:ty => $signed:expr),*) => {
+        $(
+            impl BnType for $t {
+                fn bn_type<A: Architecture>(_arch: &A) -> Ref<Type> {
+                    Type::int(mem::size_of::<$t>(), $signed)
+                }
+            }
+        )*
+    };
+}
+
+bn_type_int!(u8 => false, u16 => false, u32 => false, u64 => false, u128 => false);
+bn_type_int!(i8 => true, i16 => true, i32 => true, i64 => true, i128 => true);
+
+impl BnType for usize {
+    fn bn_type<A: Architecture>(arch: &A) -> Ref<Type> {
+        Type::int(arch.address_size(), false)
+    }
+}
+
+impl BnType for isize {
+    fn bn_type<A: Architecture>(arch: &A) -> Ref<Type> {
+        Type::int(arch.address_size(), true)
+    }
+}
+
+impl BnType for f32 {
+    fn bn_type<A: Architecture>(_arch: &A) -> Ref<Type> {
+        Type::float(4)
+    }
+}
+
+impl BnType for f64 {
+    fn bn_type<A: Architecture>(_arch: &A) -> Ref<Type> {
+        Type::float(8)
+    }
+}
+
+impl BnType for bool {
+    fn bn_type<A: Architecture>(_arch: &A) -> Ref<Type> {
+        Type::bool()
+    }
+}
+
+impl BnType for () {
+    fn bn_type<A: Architecture>(_arch: &A) -> Ref<Type> {
+        Type::void()
+    }
+}
+
+impl BnType for std::ffi::c_void {
+    fn bn_type<A: Architecture>(_arch: &A) -> Ref<Type> {
+        Type::void()
+    }
+}
+
+impl<T: BnType, const N: usize> BnType for [T; N] {
+    fn bn_type<A: Architecture>(arch: &A) -> Ref<Type> {
+        Type::array(&*T::bn_type(arch), N as u64)
+    }
+}
+
+impl<T: BnType> BnType for *const T {
+    fn bn_type<A: Architecture>(arch: &A) -> Ref<Type> {
+        Type::pointer(arch, &*T::bn_pointee_type(arch))
+    }
+}
+
+impl<T: BnType> BnType for *mut T {
+    fn bn_type<A: Architecture>(arch: &A) -> Ref<Type> {
+        Type::pointer(arch, &*T::bn_pointee_type(arch))
+    }
+}